@@ -0,0 +1,248 @@
+//! Solves the inputs of many accounts in one go, from a directory with a
+//! directory per account holding the inputs as `dNN.txt`.
+
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use common::explain::json_string;
+use common::progress::{Progress, Stderr};
+use crate::days::{self, N_DAYS};
+
+/// One input to solve: the file of `day` of `account`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Job {
+    pub account: String,
+    pub day: usize,
+    pub path: String
+}
+
+/// The day of an input file named `dNN.txt`.
+fn day_of(file_name: &str) -> Option<usize> {
+    let number = file_name.strip_prefix('d')?.strip_suffix(".txt")?;
+    let day: usize = number.parse().ok().filter(|_| number.len() == 2)?;
+    (1..=N_DAYS).contains(&day).then_some(day)
+}
+
+/// The inputs in `dir` of the given days, ordered by account and day. Other
+/// files are ignored.
+pub fn find_jobs(dir: &str, days: &[usize]) -> Result<Vec<Job>, String> {
+    let entries = |dir: &Path| -> Result<Vec<_>, String> {
+        let read = fs::read_dir(dir).map_err(|e| format!("Could not read {}: {}", dir.display(), e))?;
+        read.map(|entry| entry.map_err(|e| format!("Could not read {}: {}", dir.display(), e))).collect()
+    };
+    let mut jobs = vec!();
+    for account in entries(Path::new(dir))? {
+        if !account.path().is_dir() {
+            continue
+        }
+        for file in entries(&account.path())? {
+            if let Some(day) = day_of(&file.file_name().to_string_lossy()).filter(|day| days.contains(day)) {
+                jobs.push(Job {
+                    account: account.file_name().to_string_lossy().into_owned(),
+                    day,
+                    path: file.path().to_string_lossy().into_owned()
+                })
+            }
+        }
+    }
+    jobs.sort_by(|a, b| (&a.account, a.day).cmp(&(&b.account, b.day)));
+    Ok(jobs)
+}
+
+/// The answer of a part, or why there is none, and how long it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+    pub answer: Result<String, String>,
+    pub time: Duration
+}
+
+/// Everything found out about one input. If it could not be read or
+/// parsed, there are no parts and `error` says why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub job: Job,
+    pub parse_time: Duration,
+    pub error: Option<String>,
+    pub parts: Vec<Part>
+}
+
+impl Row {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none() && self.parts.iter().all(|p| p.answer.is_ok())
+    }
+}
+
+/// Solves both parts of a job. A part without an answer, or whose solver
+/// panics or runs out of `budget`, only fails itself.
+pub fn solve(job: &Job, budget: Option<Duration>) -> Row {
+    let mut row = Row { job: job.clone(), parse_time: Duration::ZERO, error: None, parts: vec!() };
+    let input = match crate::read_input(&job.path) {
+        Ok(input) => input,
+        Err(e) => {
+            row.error = Some(e);
+            return row
+        }
+    };
+    let puzzle = days::get(job.day).unwrap();
+    let start = Instant::now();
+    let parsed = puzzle.parse(&input);
+    row.parse_time = start.elapsed();
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            row.error = Some(e.diagnostic(&input));
+            return row
+        }
+    };
+    for part in 1..=2 {
+        let mut progress = Progress::new(Stderr);
+        if let Some(budget) = budget {
+            progress = progress.with_budget(budget)
+        }
+        let start = Instant::now();
+        let answer = panic::catch_unwind(AssertUnwindSafe(|| progress.run(|| puzzle.solve(parsed.as_ref(), part))));
+        let answer = match answer {
+            Ok(Ok(answer)) => answer,
+            Ok(Err(_)) => Err(format!("stopped after the budget of {:.2?}", budget.unwrap_or_default())),
+            Err(payload) => Err(format!("panicked: {}", crate::panic_message(payload)))
+        };
+        row.parts.push(Part { answer, time: start.elapsed() })
+    }
+    row
+}
+
+/// Solves all jobs, one after the other.
+#[cfg(not(feature = "parallel"))]
+pub fn solve_all(jobs: &[Job], budget: Option<Duration>) -> Vec<Row> {
+    jobs.iter().map(|job| solve(job, budget)).collect()
+}
+
+/// Solves all jobs at the same time.
+#[cfg(feature = "parallel")]
+pub fn solve_all(jobs: &[Job], budget: Option<Duration>) -> Vec<Row> {
+    use rayon::prelude::*;
+
+    jobs.par_iter().map(|job| solve(job, budget)).collect()
+}
+
+fn json_or_null(value: Option<&String>) -> String {
+    value.map(|s| json_string(s)).unwrap_or_else(|| String::from("null"))
+}
+
+pub fn to_json(rows: &[Row]) -> String {
+    let rows: Vec<String> = rows.iter().map(|row| {
+        let mut json = format!("    {{\"account\": {}, \"day\": {}, \"file\": {}, \"parse_ns\": {}, \"error\": {}",
+            json_string(&row.job.account), row.job.day, json_string(&row.job.path), row.parse_time.as_nanos(), json_or_null(row.error.as_ref()));
+        for (ind, part) in row.parts.iter().enumerate() {
+            json += &format!(", \"part{}\": {{\"answer\": {}, \"error\": {}, \"ns\": {}}}",
+                ind + 1, json_or_null(part.answer.as_ref().ok()), json_or_null(part.answer.as_ref().err()), part.time.as_nanos());
+        }
+        json + "}"
+    }).collect();
+    format!("[\n{}\n]\n", rows.join(",\n"))
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        String::from(s)
+    }
+}
+
+const CSV_HEADER: &str = "account,day,part,answer,error,ns";
+
+/// A line per part, or a single line without a part for an input that could
+/// not be read or parsed.
+pub fn to_csv(rows: &[Row]) -> String {
+    let mut csv = format!("{}\n", CSV_HEADER);
+    for row in rows {
+        let account = csv_field(&row.job.account);
+        if let Some(error) = &row.error {
+            csv += &format!("{},{},,,{},{}\n", account, row.job.day, csv_field(error), row.parse_time.as_nanos());
+        }
+        for (ind, part) in row.parts.iter().enumerate() {
+            let (answer, error) = match &part.answer {
+                Ok(answer) => (csv_field(answer), String::new()),
+                Err(e) => (String::new(), csv_field(e))
+            };
+            csv += &format!("{},{},{},{},{},{}\n", account, row.job.day, ind + 1, answer, error, part.time.as_nanos());
+        }
+    }
+    csv
+}
+
+/// A table for the terminal with a line per input. Answers spanning several
+/// lines are left out, errors are shown in full below the table.
+pub fn format_table(rows: &[Row]) -> String {
+    let width = rows.iter().map(|r| r.job.account.len()).max().unwrap_or(0).max(7);
+    let mut table = format!("{:<width$}  {:>3}  {:>16} {:>16}  {:>10}", "Account", "Day", "Part 1", "Part 2", "Time");
+    for row in rows {
+        let cell = |ind: usize| match row.parts.get(ind).map(|p| &p.answer) {
+            Some(Ok(answer)) if answer.contains('\n') => String::from("(lines)"),
+            Some(Ok(answer)) => answer.clone(),
+            Some(Err(_)) | None => String::from("ERROR")
+        };
+        let time = row.parse_time + row.parts.iter().map(|p| p.time).sum::<Duration>();
+        table += &format!("\n{:<width$}  {:>3}  {:>16} {:>16}  {:>10}", row.job.account, row.job.day, cell(0), cell(1), format!("{:.2?}", time));
+    }
+    for row in rows.iter().filter(|r| !r.is_ok()) {
+        let errors = row.error.iter().cloned()
+            .chain(row.parts.iter().enumerate().filter_map(|(ind, p)| p.answer.as_ref().err().map(|e| format!("part {}: {}", ind + 1, e))));
+        for error in errors {
+            table += &format!("\n\n{} day {}: {}", row.job.account, row.job.day, error);
+        }
+    }
+    let n_ok = rows.iter().filter(|r| r.is_ok()).count();
+    table += &format!("\n\n{} of {} inputs solved", n_ok, rows.len());
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn day_from_file_name() {
+        assert_eq!(day_of("d07.txt"), Some(7));
+        assert_eq!(day_of("d25.txt"), Some(25));
+        assert_eq!(day_of("d7.txt"), None);
+        assert_eq!(day_of("d26.txt"), None);
+        assert_eq!(day_of("d07.in"), None);
+    }
+
+    #[test]
+    fn solve_a_directory() {
+        let dir = std::env::temp_dir().join(format!("aoc-batch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("alice")).unwrap();
+        fs::create_dir_all(dir.join("bob")).unwrap();
+        fs::write(dir.join("alice/d01.txt"), include_str!("../../d01/test")).unwrap();
+        fs::write(dir.join("alice/notes.txt"), "").unwrap();
+        fs::write(dir.join("bob/d01.txt"), "199\n2x0").unwrap();
+        fs::write(dir.join("bob/d02.txt"), include_str!("../../d02/test")).unwrap();
+        // The product of three literals of 2^60, too large for a usize.
+        fs::write(dir.join("bob/d16.txt"), "0600C48C210842108421084200123084210842108421080048C210842108421084200").unwrap();
+        let jobs = find_jobs(dir.to_str().unwrap(), &[1, 2, 16]).unwrap();
+        let rows = solve_all(&jobs, None);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(rows.iter().map(|r| (r.job.account.as_str(), r.job.day)).collect::<Vec<_>>(), vec!(("alice", 1), ("bob", 1), ("bob", 2), ("bob", 16)));
+        assert_eq!(rows[0].parts.iter().map(|p| p.answer.clone()).collect::<Vec<_>>(), vec!(Ok(String::from("7")), Ok(String::from("5"))));
+        assert!(rows[1].error.as_ref().unwrap().contains("2x0"));
+        assert!(rows[2].is_ok());
+        assert_eq!(rows[3].parts[1].answer, Err(String::from("the count does not fit in its type")));
+        let csv = to_csv(&rows);
+        assert!(csv.starts_with("account,day,part,answer,error,ns\nalice,1,1,7,,"));
+        assert!(to_json(&rows).contains("\"account\": \"bob\", \"day\": 1"));
+        assert!(format_table(&rows).ends_with("2 of 4 inputs solved"));
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(json_string("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+        assert_eq!(csv_field("#.\n.#"), "\"#.\n.#\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("42"), "42");
+    }
+}
@@ -0,0 +1,210 @@
+//! Times parsing and both parts of each day, writes reports and compares
+//! them against a stored baseline.
+
+use std::fmt::{self, Display};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use common::ParseError;
+use crate::days::Puzzle;
+
+/// Timings below this are too noisy to count as regressions.
+pub const NOISE_FLOOR: Duration = Duration::from_millis(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Parse, Stage::Part1, Stage::Part2];
+
+    fn from_name(name: &str) -> Option<Stage> {
+        Stage::ALL.into_iter().find(|stage| stage.to_string() == name)
+    }
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part1 => write!(f, "part1"),
+            Stage::Part2 => write!(f, "part2")
+        }
+    }
+}
+
+/// Timing of one stage of a day over a number of runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    pub day: usize,
+    pub stage: Stage,
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration
+}
+
+impl Measurement {
+    fn new(day: usize, stage: Stage, mut times: Vec<Duration>) -> Self {
+        times.sort();
+        Self { day, stage, runs: times.len(), min: times[0], median: times[times.len() / 2] }
+    }
+}
+
+/// Times `puzzle` on `input` `runs` times. The input is parsed again for
+/// every run, so nothing a day caches in its parsed input carries over.
+pub fn bench_day(day: usize, puzzle: &dyn Puzzle, input: &str, runs: usize) -> Result<Vec<Measurement>, ParseError> {
+    let mut times = [vec!(), vec!(), vec!()];
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        let parsed = puzzle.parse(black_box(input))?;
+        times[0].push(start.elapsed());
+        for part in 1..=2 {
+            let start = Instant::now();
+            let _ = black_box(puzzle.solve(parsed.as_ref(), part));
+            times[part].push(start.elapsed());
+        }
+    }
+    Ok(Stage::ALL.into_iter().zip(times).map(|(stage, times)| Measurement::new(day, stage, times)).collect())
+}
+
+pub fn to_json(measurements: &[Measurement]) -> String {
+    let rows: Vec<String> = measurements.iter().map(|m| format!(
+        "    {{\"day\": {}, \"stage\": \"{}\", \"runs\": {}, \"min_ns\": {}, \"median_ns\": {}}}",
+        m.day, m.stage, m.runs, m.min.as_nanos(), m.median.as_nanos()
+    )).collect();
+    format!("[\n{}\n]\n", rows.join(",\n"))
+}
+
+const CSV_HEADER: &str = "day,stage,runs,min_ns,median_ns";
+
+pub fn to_csv(measurements: &[Measurement]) -> String {
+    let mut csv = format!("{}\n", CSV_HEADER);
+    for m in measurements {
+        csv += &format!("{},{},{},{},{}\n", m.day, m.stage, m.runs, m.min.as_nanos(), m.median.as_nanos());
+    }
+    csv
+}
+
+/// Reads a report written by `to_csv`, as used for the baseline.
+pub fn from_csv(csv: &str) -> Result<Vec<Measurement>, String> {
+    let mut lines = csv.lines().enumerate();
+    if lines.next().map(|(_, l)| l) != Some(CSV_HEADER) {
+        return Err(format!("Baseline does not start with {}", CSV_HEADER))
+    }
+    lines.filter(|(_, l)| !l.is_empty()).map(|(ind, l)| {
+        let invalid = || format!("Invalid baseline line {}: {}", ind + 1, l);
+        let fields: Vec<&str> = l.split(',').collect();
+        if fields.len() != 5 {
+            return Err(invalid())
+        }
+        let nanos = |field: &str| field.parse::<u64>().map(Duration::from_nanos).map_err(|_| invalid());
+        Ok(Measurement {
+            day: fields[0].parse().map_err(|_| invalid())?,
+            stage: Stage::from_name(fields[1]).ok_or_else(invalid)?,
+            runs: fields[2].parse().map_err(|_| invalid())?,
+            min: nanos(fields[3])?,
+            median: nanos(fields[4])?
+        })
+    }).collect()
+}
+
+/// A stage whose median got slower than the baseline by more than the threshold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    pub day: usize,
+    pub stage: Stage,
+    pub baseline: Duration,
+    pub current: Duration
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Day {} {}: {:.2?} -> {:.2?} ({})", self.day, self.stage, self.baseline, self.current,
+            format_change(self.baseline, self.current))
+    }
+}
+
+fn change(baseline: Duration, current: Duration) -> f64 {
+    (current.as_secs_f64() - baseline.as_secs_f64()) / baseline.as_secs_f64().max(f64::MIN_POSITIVE)
+}
+
+fn format_change(baseline: Duration, current: Duration) -> String {
+    format!("{:+.1}%", 100.0 * change(baseline, current))
+}
+
+/// The stages that are slower than in `baseline` by more than `threshold`,
+/// a fraction such as 0.1 for 10%. Stages missing from the baseline and
+/// stages faster than the noise floor are left out.
+pub fn find_regressions(current: &[Measurement], baseline: &[Measurement], threshold: f64) -> Vec<Regression> {
+    current.iter().filter_map(|m| {
+        let base = baseline.iter().find(|b| b.day == m.day && b.stage == m.stage)?;
+        let slower = m.median > NOISE_FLOOR && m.median.saturating_sub(base.median) > NOISE_FLOOR;
+        if slower && change(base.median, m.median) > threshold {
+            Some(Regression { day: m.day, stage: m.stage, baseline: base.median, current: m.median })
+        } else {
+            None
+        }
+    }).collect()
+}
+
+/// A table of the timings for the terminal, with the change from the
+/// baseline if one is given.
+pub fn format_table(measurements: &[Measurement], baseline: Option<&[Measurement]>) -> String {
+    let mut table = format!("{:>3}  {:<6} {:>12} {:>12}", "Day", "Stage", "Median", "Min");
+    if baseline.is_some() {
+        table += &format!(" {:>12} {:>8}", "Baseline", "Change");
+    }
+    for m in measurements {
+        table += &format!("\n{:>3}  {:<6} {:>12} {:>12}", m.day, m.stage.to_string(), format!("{:.2?}", m.median), format!("{:.2?}", m.min));
+        if let Some(base) = baseline.and_then(|b| b.iter().find(|b| b.day == m.day && b.stage == m.stage)) {
+            table += &format!(" {:>12} {:>8}", format!("{:.2?}", base.median), format_change(base.median, m.median));
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn measurement(day: usize, stage: Stage, median_ms: u64) -> Measurement {
+        let median = Duration::from_millis(median_ms);
+        Measurement { day, stage, runs: 3, min: median, median }
+    }
+
+    #[test]
+    fn bench_a_day() {
+        let input = include_str!("../../d01/test");
+        let measurements = bench_day(1, crate::days::get(1).unwrap(), input, 3).unwrap();
+        assert_eq!(measurements.iter().map(|m| m.stage).collect::<Vec<_>>(), Stage::ALL.to_vec());
+        assert!(measurements.iter().all(|m| m.runs == 3 && m.min <= m.median));
+        assert!(bench_day(1, crate::days::get(1).unwrap(), "1\nx", 1).is_err());
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let measurements = vec!(measurement(1, Stage::Parse, 2), measurement(14, Stage::Part2, 19000));
+        assert_eq!(from_csv(&to_csv(&measurements)), Ok(measurements));
+        assert!(from_csv("day,stage\n").is_err());
+        assert!(from_csv(&format!("{}\n1,part3,1,1,1", CSV_HEADER)).is_err());
+    }
+
+    #[test]
+    fn json_report() {
+        let json = to_json(&[measurement(1, Stage::Part1, 2)]);
+        assert_eq!(json, "[\n    {\"day\": 1, \"stage\": \"part1\", \"runs\": 3, \"min_ns\": 2000000, \"median_ns\": 2000000}\n]\n");
+    }
+
+    #[test]
+    fn regressions() {
+        let baseline = vec!(measurement(1, Stage::Part1, 100), measurement(2, Stage::Part1, 100), measurement(3, Stage::Part1, 0));
+        let current = vec!(measurement(1, Stage::Part1, 125), measurement(2, Stage::Part1, 105), measurement(3, Stage::Part1, 1), measurement(4, Stage::Part1, 50));
+        assert_eq!(
+            find_regressions(&current, &baseline, 0.1),
+            vec!(Regression { day: 1, stage: Stage::Part1, baseline: Duration::from_millis(100), current: Duration::from_millis(125) })
+        );
+        assert!(find_regressions(&current, &baseline, 0.5).is_empty());
+    }
+}
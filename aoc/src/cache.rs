@@ -0,0 +1,171 @@
+//! Answers kept on disk, so that the slow days need not be solved again for
+//! an input they have already answered.
+//!
+//! An answer is stored in a file of its own, named after the day, the part,
+//! a hash of the input and the version of the solver, so that a changed
+//! input or solver misses the cache.
+
+use std::fmt::{self, Display};
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+use crate::days;
+
+pub const DEFAULT_DIR: &str = ".aoc-cache";
+
+/// FNV-1a, which unlike the hasher of the standard library stays the same
+/// between Rust versions.
+pub fn hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// What an answer is cached under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub day: usize,
+    pub part: usize,
+    pub hash: u64,
+    pub version: u32
+}
+
+impl Key {
+    pub fn new(day: usize, part: usize, input: &str) -> Self {
+        Self { day, part, hash: hash(input), version: days::get(day).unwrap().version() }
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "d{:02}-p{}-{:016x}-v{}", self.day, self.part, self.hash, self.version)
+    }
+}
+
+/// A cache in a directory, which is created on the first answer stored.
+/// Rechecks of cached answers run on threads of their own until `wait`.
+pub struct Cache {
+    dir: PathBuf,
+    rechecks: Mutex<Vec<JoinHandle<Option<String>>>>
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into(), rechecks: Mutex::new(vec!()) }
+    }
+
+    fn path(&self, key: &Key) -> PathBuf {
+        self.dir.join(key.to_string())
+    }
+
+    pub fn get(&self, key: &Key) -> Option<String> {
+        fs::read_to_string(self.path(key)).ok()
+    }
+
+    /// Stores an answer. It is written to a file of its own first and then
+    /// moved in place, so that a reader never sees half an answer.
+    pub fn put(&self, key: &Key, answer: &str) -> Result<(), String> {
+        fs::create_dir_all(&self.dir).map_err(|e| format!("Could not create {}: {}", self.dir.display(), e))?;
+        let path = self.path(key);
+        let partial = path.with_extension(format!("{}.partial", thread_id()));
+        fs::write(&partial, answer)
+            .and_then(|_| fs::rename(&partial, &path))
+            .map_err(|e| format!("Could not write {}: {}", path.display(), e))
+    }
+
+    /// Removes all cached answers and returns how many there were.
+    pub fn clear(&self) -> Result<usize, String> {
+        if !self.dir.exists() {
+            return Ok(0)
+        }
+        let n_answers = fs::read_dir(&self.dir).map(|entries| entries.count()).unwrap_or(0);
+        fs::remove_dir_all(&self.dir).map_err(|e| format!("Could not remove {}: {}", self.dir.display(), e))?;
+        Ok(n_answers)
+    }
+
+    /// Solves the part again in the background. If the answer differs from
+    /// the cached one, the new answer replaces it and `wait` tells about it.
+    pub fn recheck(&self, key: Key, input: String, cached: String) {
+        let cache = Cache::new(self.dir.clone());
+        let handle = thread::spawn(move || {
+            let puzzle = days::get(key.day).unwrap();
+            let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+                let parsed = puzzle.parse(&input).ok()?;
+                puzzle.solve(parsed.as_ref(), key.part).ok()
+            }));
+            match solved {
+                Ok(Some(answer)) if answer == cached => None,
+                Ok(Some(answer)) => {
+                    let mut message = format!("Day {} part {}: cached answer {:?} is now {:?}", key.day, key.part, cached, answer);
+                    if let Err(e) = cache.put(&key, &answer) {
+                        message += &format!(", {}", e)
+                    }
+                    Some(message)
+                },
+                Ok(None) | Err(_) => Some(format!("Day {} part {}: could not be solved again to recheck {:?}", key.day, key.part, cached))
+            }
+        });
+        self.rechecks.lock().unwrap().push(handle)
+    }
+
+    /// Waits for all rechecks and returns what they found wrong.
+    pub fn wait(&self) -> Vec<String> {
+        let handles: Vec<_> = self.rechecks.lock().unwrap().drain(..).collect();
+        handles.into_iter().filter_map(|handle| handle.join().unwrap_or_else(|_| Some(String::from("A recheck failed")))).collect()
+    }
+}
+
+fn thread_id() -> String {
+    format!("{:?}", thread::current().id()).chars().filter(|c| c.is_ascii_digit()).collect()
+}
+
+/// The part of a cache a run uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Neither read nor write the cache.
+    Off,
+    /// Use cached answers and store new ones.
+    On,
+    /// Like `On`, and solve cached parts again in the background to check them.
+    Recheck
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keys() {
+        assert_eq!(hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash("a"), 0xaf63_dc4c_8601_ec8c);
+        let key = Key::new(14, 2, "NNCB");
+        assert_eq!(key.to_string(), format!("d14-p2-{:016x}-v1", hash("NNCB")));
+        assert_ne!(Key::new(14, 1, "NNCB"), key);
+        assert_ne!(Key::new(14, 2, "NNCB\n"), key);
+        assert_eq!(Key::new(17, 1, "").version, 2);
+    }
+
+    #[test]
+    fn store_and_recheck() {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-{}", std::process::id()));
+        let cache = Cache::new(&dir);
+        let input = include_str!("../../d01/test");
+        let (key_1, key_2) = (Key::new(1, 1, input), Key::new(1, 2, input));
+        assert_eq!(cache.get(&key_1), None);
+        cache.put(&key_1, "7").unwrap();
+        cache.put(&key_2, "6").unwrap();
+        assert_eq!(cache.get(&key_1), Some(String::from("7")));
+
+        cache.recheck(key_1, String::from(input), String::from("7"));
+        cache.recheck(key_2, String::from(input), String::from("6"));
+        let found = cache.wait();
+        assert_eq!(found.len(), 1);
+        assert!(found[0].starts_with("Day 1 part 2: cached answer \"6\" is now \"5\""));
+        assert_eq!(cache.get(&key_2), Some(String::from("5")));
+
+        assert_eq!(cache.clear(), Ok(2));
+        assert_eq!(cache.get(&key_1), None);
+        assert_eq!(cache.clear(), Ok(0));
+        assert!(!dir.exists());
+    }
+}
@@ -0,0 +1,116 @@
+//! The days whose answers are counts, with their input types erased like in
+//! `days`, to count them in a wider type than a `usize`.
+
+use std::any::Any;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use common::count::{Count, Counted};
+use common::params::Params;
+
+/// The type a count is taken in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Counter {
+    Usize,
+    U128,
+    /// A `BigUint`, which never overflows.
+    #[cfg(feature = "bigint")]
+    Big
+}
+
+impl FromStr for Counter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Counter, String> {
+        match s {
+            "usize" => Ok(Counter::Usize),
+            "u128" => Ok(Counter::U128),
+            #[cfg(feature = "bigint")]
+            "big" => Ok(Counter::Big),
+            #[cfg(not(feature = "bigint"))]
+            "big" => Err(String::from("--count big needs aoc built with the bigint feature")),
+            _ => Err(format!("Invalid count type: {}", s))
+        }
+    }
+}
+
+pub trait Countable: Sync {
+    /// Answers `part` of an input returned by `Puzzle::parse` of the same
+    /// day, counting in `counter`.
+    fn solve(&self, input: &dyn Any, part: usize, params: &Params, counter: Counter) -> Result<String, String>;
+}
+
+struct Day<S>(PhantomData<fn() -> S>);
+
+impl<S: Counted> Day<S> {
+    fn count<C: Count>(input: &S::Input, part: usize, params: &Params) -> Result<String, String> {
+        let count = match part {
+            1 => S::part_1_as::<C>(input, params),
+            2 => S::part_2_as::<C>(input, params),
+            _ => panic!("Invalid part {}", part)
+        };
+        count.map(|n| n.to_string()).map_err(|e| e.to_string())
+    }
+}
+
+impl<S: Counted> Countable for Day<S> where S::Input: 'static {
+    fn solve(&self, input: &dyn Any, part: usize, params: &Params, counter: Counter) -> Result<String, String> {
+        let input = input.downcast_ref::<S::Input>().expect("Input was parsed by another day");
+        match counter {
+            Counter::Usize => Self::count::<usize>(input, part, params),
+            Counter::U128 => Self::count::<u128>(input, part, params),
+            #[cfg(feature = "bigint")]
+            Counter::Big => Self::count::<common::count::BigUint>(input, part, params)
+        }
+    }
+}
+
+static COUNTED: [(usize, &dyn Countable); 5] = [
+    (6, &Day::<d06::Day06>(PhantomData)),
+    (12, &Day::<d12::Day12>(PhantomData)),
+    (14, &Day::<d14::Day14>(PhantomData)),
+    (16, &Day::<d16::Day16>(PhantomData)),
+    (21, &Day::<d21::Day21>(PhantomData)),
+];
+
+/// The days that can count in another type.
+pub fn days() -> Vec<usize> {
+    COUNTED.iter().map(|(day, _)| *day).collect()
+}
+
+/// The counting of a day, if it can count in another type.
+pub fn get(day: usize) -> Option<&'static dyn Countable> {
+    COUNTED.iter().find(|(d, _)| *d == day).map(|(_, countable)| *countable)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn count_in_u128() {
+        let input = crate::read_input("../d06/test").unwrap();
+        let parsed = days::get(6).unwrap().parse(&input).unwrap();
+        let mut params = Params::default();
+        params.set("days_2=600").unwrap();
+        let overflow = Err(String::from("the count does not fit in its type"));
+        assert_eq!(get(6).unwrap().solve(parsed.as_ref(), 2, &params, Counter::Usize), overflow);
+        assert_eq!(get(6).unwrap().solve(parsed.as_ref(), 1, &params, Counter::U128), Ok(String::from("5934")));
+        assert!(get(6).unwrap().solve(parsed.as_ref(), 2, &params, Counter::U128).is_ok());
+        assert!(get(1).is_none());
+        assert_eq!(days(), vec!(6, 12, 14, 16, 21));
+        assert_eq!("u128".parse(), Ok(Counter::U128));
+        assert_eq!("i64".parse::<Counter>(), Err(String::from("Invalid count type: i64")));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn count_in_big() {
+        let input = crate::read_input("../d06/test").unwrap();
+        let parsed = days::get(6).unwrap().parse(&input).unwrap();
+        let mut params = Params::default();
+        params.set("days_2=1000").unwrap();
+        assert_eq!(get(6).unwrap().solve(parsed.as_ref(), 2, &params, Counter::U128), Err(String::from("the count does not fit in its type")));
+        assert_eq!(get(6).unwrap().solve(parsed.as_ref(), 2, &params, "big".parse().unwrap()), Ok(String::from("379589061144698259131825683795505058481")));
+    }
+}
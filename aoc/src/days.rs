@@ -0,0 +1,113 @@
+use std::any::Any;
+use std::marker::PhantomData;
+use common::{Answer, Generate, Generated, ParseError, Rng};
+
+pub const N_DAYS: usize = 25;
+
+/// The solution of a day with its input and answer types erased, so that
+/// all days can be run from one table.
+pub trait Puzzle: Sync {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+    /// Answers `part` from an input returned by `parse` of the same day, or
+    /// tells why it has no answer.
+    fn solve(&self, input: &dyn Any, part: usize) -> Result<String, String>;
+    /// The generator size that matches the real input.
+    fn input_size(&self) -> usize;
+    /// The `Solution::VERSION` of the day.
+    fn version(&self) -> u32;
+    fn generate(&self, seed: u64, size: usize) -> Generated;
+}
+
+struct Day<S>(PhantomData<fn() -> S>);
+
+impl<S: Generate> Puzzle for Day<S> where S::Input: 'static {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, input: &dyn Any, part: usize) -> Result<String, String> {
+        let input = input.downcast_ref::<S::Input>().expect("Input was parsed by another day");
+        match part {
+            1 => S::part_1(input).answer(),
+            2 => S::part_2(input).answer(),
+            _ => panic!("Invalid part {}", part)
+        }
+    }
+
+    fn input_size(&self) -> usize {
+        S::INPUT_SIZE
+    }
+
+    fn version(&self) -> u32 {
+        S::VERSION
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Generated {
+        S::generate(&mut Rng::new(seed), size)
+    }
+}
+
+static PUZZLES: [&dyn Puzzle; N_DAYS] = [
+    &Day::<d01::Day01>(PhantomData),
+    &Day::<d02::Day02>(PhantomData),
+    &Day::<d03::Day03>(PhantomData),
+    &Day::<d04::Day04>(PhantomData),
+    &Day::<d05::Day05>(PhantomData),
+    &Day::<d06::Day06>(PhantomData),
+    &Day::<d07::Day07>(PhantomData),
+    &Day::<d08::Day08>(PhantomData),
+    &Day::<d09::Day09>(PhantomData),
+    &Day::<d10::Day10>(PhantomData),
+    &Day::<d11::Day11>(PhantomData),
+    &Day::<d12::Day12>(PhantomData),
+    &Day::<d13::Day13>(PhantomData),
+    &Day::<d14::Day14>(PhantomData),
+    &Day::<d15::Day15>(PhantomData),
+    &Day::<d16::Day16>(PhantomData),
+    &Day::<d17::Day17>(PhantomData),
+    &Day::<d18::Day18>(PhantomData),
+    &Day::<d19::Day19>(PhantomData),
+    &Day::<d20::Day20>(PhantomData),
+    &Day::<d21::Day21>(PhantomData),
+    &Day::<d22::Day22>(PhantomData),
+    &Day::<d23::Day23>(PhantomData),
+    &Day::<d24::Day24>(PhantomData),
+    &Day::<d25::Day25>(PhantomData),
+];
+
+/// The puzzle of a day, counting from 1.
+pub fn get(day: usize) -> Option<&'static dyn Puzzle> {
+    day.checked_sub(1).and_then(|ind| PUZZLES.get(ind)).copied()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for day in 1..=N_DAYS {
+            let puzzle = get(day).unwrap();
+            for size in [1, 2, puzzle.input_size()] {
+                let generated = puzzle.generate(day as u64, size);
+                if let Err(e) = puzzle.parse(&generated.input) {
+                    panic!("Day {} size {}\n{}", day, size, e.diagnostic(&generated.input))
+                }
+                assert_eq!(puzzle.generate(day as u64, size), generated);
+            }
+        }
+    }
+
+    #[test]
+    fn saved_inputs_parse() {
+        for day in 1..=N_DAYS {
+            let puzzle = get(day).unwrap();
+            let input = puzzle.generate(day as u64, 2).input;
+            for saved in [format!("{}\n", input), format!("{}\n\n", input), input.replace('\n', "\r\n") + "\r\n", format!("\u{feff}{}", input)] {
+                if let Err(e) = puzzle.parse(&saved) {
+                    panic!("Day {}\n{}", day, e.diagnostic(&saved))
+                }
+            }
+        }
+    }
+}
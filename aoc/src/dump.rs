@@ -0,0 +1,59 @@
+//! The days whose parsed input can be written as JSON, with their input
+//! types erased like in `days`.
+
+use std::any::Any;
+use std::marker::PhantomData;
+use common::Solution;
+
+pub trait Dump: Sync {
+    /// An input returned by `Puzzle::parse` of the same day as JSON.
+    fn to_json(&self, input: &dyn Any) -> String;
+}
+
+struct Day<S>(PhantomData<fn() -> S>);
+
+impl<S: Solution> Dump for Day<S> where S::Input: serde::Serialize + 'static {
+    fn to_json(&self, input: &dyn Any) -> String {
+        let input = input.downcast_ref::<S::Input>().expect("Input was parsed by another day");
+        serde_json::to_string(input).expect("Parsed inputs can always be written as JSON")
+    }
+}
+
+static DUMPS: [(usize, &dyn Dump); 8] = [
+    (4, &Day::<d04::Day04>(PhantomData)),
+    (9, &Day::<d09::Day09>(PhantomData)),
+    (11, &Day::<d11::Day11>(PhantomData)),
+    (16, &Day::<d16::Day16>(PhantomData)),
+    (18, &Day::<d18::Day18>(PhantomData)),
+    (22, &Day::<d22::Day22>(PhantomData)),
+    (23, &Day::<d23::Day23>(PhantomData)),
+    (24, &Day::<d24::Day24>(PhantomData)),
+];
+
+/// The days whose parsed input can be written as JSON.
+pub fn days() -> Vec<usize> {
+    DUMPS.iter().map(|(day, _)| *day).collect()
+}
+
+/// How to write the parsed input of a day, if it can be written.
+pub fn get(day: usize) -> Option<&'static dyn Dump> {
+    DUMPS.iter().find(|(d, _)| *d == day).map(|(_, dump)| *dump)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn examples_dump() {
+        for (day, test) in [(4, "test"), (9, "test"), (11, "test1"), (18, "testhw"), (22, "test5"), (23, "test")] {
+            let input = crate::read_input(&format!("../d{:02}/{}", day, test)).unwrap();
+            let parsed = days::get(day).unwrap().parse(&input).unwrap();
+            let json = get(day).unwrap().to_json(parsed.as_ref());
+            assert!(json.starts_with('[') && json.ends_with(']'), "Day {}: {}", day, json);
+        }
+        assert_eq!(days(), vec!(4, 9, 11, 16, 18, 22, 23, 24));
+        assert!(get(1).is_none());
+    }
+}
@@ -0,0 +1,100 @@
+//! The days that can explain their answers, with their input types erased
+//! like in `days`.
+
+use std::any::Any;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use common::explain::{Explain, Fact};
+
+/// How an explanation is printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Indented text under the answer.
+    Text,
+    /// A JSON object per part, with the day, the part and the answer.
+    Json
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Invalid explanation format: {}", s))
+        }
+    }
+}
+
+pub trait Explainer: Sync {
+    /// Explains a part of an input returned by `Puzzle::parse` of the same day.
+    fn explain(&self, input: &dyn Any, part: usize) -> Fact;
+}
+
+struct Day<S>(PhantomData<fn() -> S>);
+
+impl<S: Explain> Explainer for Day<S> where S::Input: 'static {
+    fn explain(&self, input: &dyn Any, part: usize) -> Fact {
+        let input = input.downcast_ref::<S::Input>().expect("Input was parsed by another day");
+        match part {
+            1 => S::explain_1(input),
+            2 => S::explain_2(input),
+            _ => panic!("Invalid part {}", part)
+        }
+    }
+}
+
+static EXPLAINERS: [(usize, &dyn Explainer); 6] = [
+    (4, &Day::<d04::Day04>(PhantomData)),
+    (8, &Day::<d08::Day08>(PhantomData)),
+    (10, &Day::<d10::Day10>(PhantomData)),
+    (15, &Day::<d15::Day15>(PhantomData)),
+    (19, &Day::<d19::Day19>(PhantomData)),
+    (23, &Day::<d23::Day23>(PhantomData)),
+];
+
+/// The days that can explain their answers.
+pub fn days() -> Vec<usize> {
+    EXPLAINERS.iter().map(|(day, _)| *day).collect()
+}
+
+/// The explainer of a day, if it has one.
+pub fn get(day: usize) -> Option<&'static dyn Explainer> {
+    EXPLAINERS.iter().find(|(d, _)| *d == day).map(|(_, explainer)| *explainer)
+}
+
+/// The answer of a part as a JSON object, with its explanation.
+pub fn to_json(day: usize, part: usize, answer: &str, explanation: &Fact) -> String {
+    format!("{{\"day\": {}, \"part\": {}, \"answer\": {}, \"explanation\": {}}}",
+        day, part, common::explain::json_string(answer), explanation.to_json())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn examples_explain() {
+        // Days 19 and 23 take too long on their examples without optimizations.
+        for (day, test) in [(4, "test"), (8, "test2"), (10, "test"), (15, "test")] {
+            let input = crate::read_input(&format!("../d{:02}/{}", day, test)).unwrap();
+            let parsed = days::get(day).unwrap().parse(&input).unwrap();
+            for part in [1, 2] {
+                let explanation = get(day).unwrap().explain(parsed.as_ref(), part);
+                assert!(matches!(&explanation, Fact::Record(fields) if !fields.is_empty()), "Day {} part {}", day, part);
+            }
+        }
+        assert_eq!(days(), vec!(4, 8, 10, 15, 19, 23));
+        assert!(get(1).is_none());
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn json() {
+        let explanation = Fact::record([("score", Fact::from(7usize))]);
+        assert_eq!(to_json(4, 1, "7", &explanation), "{\"day\": 4, \"part\": 1, \"answer\": \"7\", \"explanation\": {\"score\": 7}}");
+    }
+}
@@ -0,0 +1,54 @@
+//! Runs the solutions of all days from one binary, reading the puzzle input
+//! at runtime instead of compiling it in.
+
+pub mod batch;
+pub mod bench;
+pub mod cache;
+pub mod count;
+pub mod days;
+#[cfg(feature = "serde")]
+pub mod dump;
+pub mod explain;
+pub mod params;
+pub mod server;
+pub mod step;
+pub mod verify;
+pub mod visualize;
+
+use std::any::Any;
+use std::io::Read;
+
+/// Reads a puzzle input from `path`, or from stdin if `path` is `-`.
+pub fn read_input(path: &str) -> Result<String, String> {
+    if path == "-" {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input).map_err(|e| format!("Could not read stdin: {}", e))?;
+        Ok(input)
+    } else {
+        std::fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path, e))
+    }
+}
+
+/// The input file of a day when no other input is given.
+pub fn default_input_path(day: usize) -> String {
+    format!("d{:02}/input", day)
+}
+
+/// The message of a panic caught by `catch_unwind`.
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| String::from("unknown panic"))
+}
+
+/// Days like `4, 8 and 10`, with `last` before the last one.
+pub fn list_days(days: &[usize], last: &str) -> String {
+    match days {
+        [] => String::new(),
+        [day] => day.to_string(),
+        [init @ .., day] => {
+            let init: Vec<_> = init.iter().map(|day| day.to_string()).collect();
+            format!("{} {} {}", init.join(", "), last, day)
+        }
+    }
+}
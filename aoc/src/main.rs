@@ -0,0 +1,1114 @@
+use std::any::Any;
+use std::time::Duration;
+use common::params::Params;
+use common::progress::{Level, Progress, Stderr};
+use render::{Format, Palette, Style};
+use aoc::{batch, bench, explain, params, server, step, verify, visualize};
+use aoc::cache::{self, Cache, Mode};
+use aoc::count::{self, Counter};
+use aoc::days::{self, N_DAYS};
+
+/// How to use aoc, with the days that support each option listed from
+/// their tables.
+fn usage() -> String {
+    format!("\
+Usage: aoc run <day>... [--part <1|2>] [--input <path|->] [--log <level>] [--budget <seconds>]
+       aoc run all [--part <1|2>] [--log <level>] [--budget <seconds>]
+       aoc run ... [--no-cache | --recheck] [--clear-cache] [--cache-dir <path>]
+       aoc run <day>... --explain [text|json]
+       aoc run <day>... --dump-parsed
+       aoc run <day> --param <name>=<value>...
+       aoc run <day>... --count <usize|u128|big>
+       aoc bench <day>...|all [--runs <n>] [--json <path>] [--csv <path>]
+                 [--baseline <path>] [--threshold <percent>]
+       aoc verify [<day>...|all] [--manifest <path>] [--examples]
+       aoc generate <day> [--seed <n>] [--size <n> | --scale <factor>] [--answers]
+       aoc batch <dir> [<day>...|all] [--json <path>] [--csv <path>] [--budget <seconds>]
+       aoc serve [--port <n>] [--max-body <bytes>] [--timeout <seconds>]
+       aoc render <day> [--input <path|->] [--format <ppm|png|svg>] [--scale <n>]
+                  [--palette <gray|heat|colours>] [--out <path> | --frames <dir>]
+       aoc step <day> [--part <1|2>] [--input <path>] [--history <n>]
+                [--param <name>=<value>...]
+
+run solves the given days, reading each input from d<NN>/input unless
+--input is given. An input of - reads from stdin. The slow days report how
+far they got to stderr with --log info, or in more detail with debug or
+trace (default warn). A part still running after --budget seconds is
+stopped.
+Answers are cached in --cache-dir (default .aoc-cache) by day, part, input
+and solver version, and a part answered before is not solved again.
+--no-cache neither reads nor writes the cache. --recheck prints the cached
+answers at once but solves those parts again in the background, and fails
+if an answer changed. --clear-cache empties the cache first; without days
+it only does that.
+--explain prints how days {explain} came to each answer,
+like the winning board or the path taken, as indented text under the
+answer (default) or as a JSON object per part with the day and answer.
+Days {params} have parameters that are not in the input: days_1 and days_2,
+the days the lanternfish grow for (default 80 and 256), and steps, the
+steps counted in part 1 (default 100). They are set by name = value lines in
+d<NN>/params, and --param overrides those for a single day.
+A count that does not fit in a usize fails its part. Days {count} can
+count in a u128 with --count u128, or without a limit with --count big.
+Answers with parameters or --count set are not cached.
+--dump-parsed prints the parsed input as JSON, one line per day, instead of
+solving the days. {dump}
+
+bench times parsing and both parts of the given days and prints the median
+and fastest of --runs runs (default 1). The report can also be written as
+JSON or CSV. A CSV report can be given as --baseline to a later bench, which
+then fails if any stage got slower by more than --threshold percent
+(default 10).
+
+verify checks the answers of the given days, or of all days, against the
+expected answers in --manifest (default answers) and prints a table with a
+diff of every wrong answer. --examples skips the real inputs.
+
+generate writes a synthetic input for a day to stdout, built from --seed
+(default 0). --size is in a unit of the day, like lines or the side of a
+grid; --scale multiplies the size of the real input (default 1). With
+--answers, the answers known from how the input was built go to stderr.
+
+batch solves every <dir>/<account>/d<NN>.txt of the given days, or of all
+days, and prints a table of the answers. The answers, timings and errors
+can also be written as JSON or CSV. An input that cannot be solved, or
+takes longer than --budget seconds for a part, is reported and the rest
+carry on.
+
+serve answers POST /day/<n>/part/<p> with the input as the body on
+127.0.0.1:--port (default 8080) with JSON. Bodies over --max-body bytes
+(default 1048576) are refused and requests not solved within --timeout
+seconds (default 10) fail.
+
+render draws days {render} as a picture of how the puzzle
+ends, written to --out (default d<NN>.<format>), or with --frames as
+numbered frames of every step into a directory. The --format is png by
+default and every cell is --scale pixels wide (default 4). --palette is
+gray, heat or a comma separated list of colours like #ff8000, one per
+level; each day has a palette of its own by default.
+
+step runs the simulation of day {step} for a --part (default 1)
+one step at a time, reading commands from stdin: s [n] steps forward, b [n]
+steps back, g <n> goes to a step and u <counter> <comparison> <n> runs until
+a counter like flashes compares like that. The cells that changed in the
+last step are highlighted. The last --history states (default 1000) are
+kept for stepping back. The parameters of the day are read from
+d<NN>/params and --param like for run, so --param steps=10 stops part 1
+of day 11 after 10 steps.",
+        explain = aoc::list_days(&explain::days(), "and"),
+        params = aoc::list_days(&params::days(), "and"),
+        count = aoc::list_days(&count::days(), "and"),
+        dump = match dump_days()[..] {
+            [] => String::from("It needs the serde feature, which this build is without."),
+            ref days => format!("It needs the serde feature, which is on by default, and\nworks for days {}.", aoc::list_days(days, "and"))
+        },
+        render = aoc::list_days(&visualize::days(), "and"),
+        step = aoc::list_days(&step::days(), "or")
+    )
+}
+
+/// Adds the days given by `arg`, a day number or `all`.
+fn parse_days(arg: &str, days: &mut Vec<usize>) -> Result<(), String> {
+    if arg == "all" {
+        days.extend(1..=N_DAYS);
+        return Ok(())
+    }
+    let day: usize = arg.parse().map_err(|_| format!("Invalid day: {}", arg))?;
+    if day == 0 || day > N_DAYS {
+        return Err(format!("Invalid day: {}", arg))
+    }
+    days.push(day);
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct RunArgs {
+    days: Vec<usize>,
+    parts: Vec<usize>,
+    input: Option<String>,
+    log: Level,
+    budget: Option<Duration>,
+    cache: Mode,
+    cache_dir: String,
+    clear_cache: bool,
+    explain: Option<explain::Format>,
+    dump_parsed: bool,
+    params: Params,
+    count: Option<Counter>
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut days = vec!();
+    let mut parts = vec!(1, 2);
+    let mut input = None;
+    let mut log = Level::Warn;
+    let mut budget = None;
+    let mut cache = Mode::On;
+    let mut cache_dir = String::from(cache::DEFAULT_DIR);
+    let mut clear_cache = false;
+    let mut explain = None;
+    let mut dump_parsed = false;
+    let mut params = Params::default();
+    let mut count = None;
+    let mut it = args.iter().peekable();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--part" => {
+                let part = it.next().ok_or("--part needs a value")?;
+                match part.as_str() {
+                    "1" => parts = vec!(1),
+                    "2" => parts = vec!(2),
+                    _ => return Err(format!("Invalid part: {}", part))
+                }
+            },
+            "--input" => input = Some(it.next().ok_or("--input needs a value")?.clone()),
+            "--log" => log = it.next().ok_or("--log needs a value")?.parse()?,
+            "--budget" => budget = Some(parse_budget(it.next().ok_or("--budget needs a value")?)?),
+            "--no-cache" => cache = Mode::Off,
+            "--recheck" => cache = Mode::Recheck,
+            "--clear-cache" => clear_cache = true,
+            "--cache-dir" => cache_dir = it.next().ok_or("--cache-dir needs a value")?.clone(),
+            "--explain" => explain = Some(match it.next_if(|format| format.parse::<explain::Format>().is_ok()) {
+                Some(format) => format.parse()?,
+                None => explain::Format::Text
+            }),
+            "--dump-parsed" => dump_parsed = true,
+            "--param" => params.set(it.next().ok_or("--param needs a value")?)?,
+            "--count" => count = Some(it.next().ok_or("--count needs a value")?.parse()?),
+            _ => parse_days(arg, &mut days)?
+        }
+    }
+    if days.is_empty() && !clear_cache {
+        return Err(String::from("No day given"))
+    }
+    if input.is_some() && days.len() > 1 {
+        return Err(String::from("--input can only be used with a single day"))
+    }
+    if !params.is_empty() && days.len() > 1 {
+        return Err(String::from("--param can only be used with a single day"))
+    }
+    if let Some(day) = days.iter().find(|day| explain.is_some() && explain::get(**day).is_none()) {
+        return Err(format!("Day {} cannot explain its answers, only days {} can", day, aoc::list_days(&explain::days(), "and")))
+    }
+    if let Some(day) = days.iter().find(|day| count.is_some() && count::get(**day).is_none()) {
+        return Err(format!("Day {} cannot count in another type, only days {} can", day, aoc::list_days(&count::days(), "and")))
+    }
+    if dump_parsed && explain.is_some() {
+        return Err(String::from("--dump-parsed cannot be used with --explain"))
+    }
+    if let Some(day) = days.iter().find(|day| dump_parsed && !dump_days().contains(day)) {
+        return match dump_days()[..] {
+            [] => Err(String::from("--dump-parsed needs aoc built with the serde feature")),
+            ref can => Err(format!("Day {} cannot dump its parsed input, only days {} can", day, aoc::list_days(can, "and")))
+        }
+    }
+    Ok(RunArgs { days, parts, input, log, budget, cache, cache_dir, clear_cache, explain, dump_parsed, params, count })
+}
+
+#[cfg(feature = "serde")]
+fn dump_days() -> Vec<usize> {
+    aoc::dump::days()
+}
+
+/// Without serde no day can write its input as JSON.
+#[cfg(not(feature = "serde"))]
+fn dump_days() -> Vec<usize> {
+    vec!()
+}
+
+#[cfg(feature = "serde")]
+fn dump_parsed(day: usize, parsed: &dyn Any) -> String {
+    aoc::dump::get(day).unwrap().to_json(parsed)
+}
+
+#[cfg(not(feature = "serde"))]
+fn dump_parsed(_day: usize, _parsed: &dyn Any) -> String {
+    unreachable!("No day can dump its parsed input without serde")
+}
+
+#[derive(Debug, PartialEq)]
+struct BenchArgs {
+    days: Vec<usize>,
+    runs: usize,
+    json: Option<String>,
+    csv: Option<String>,
+    baseline: Option<String>,
+    threshold: f64
+}
+
+fn parse_bench_args(args: &[String]) -> Result<BenchArgs, String> {
+    let mut bench_args = BenchArgs { days: vec!(), runs: 1, json: None, csv: None, baseline: None, threshold: 0.1 };
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().cloned().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--runs" => bench_args.runs = value()?.parse().ok().filter(|runs| *runs > 0).ok_or("Invalid number of runs")?,
+            "--json" => bench_args.json = Some(value()?),
+            "--csv" => bench_args.csv = Some(value()?),
+            "--baseline" => bench_args.baseline = Some(value()?),
+            "--threshold" => {
+                let percent: f64 = value()?.parse().ok().filter(|p: &f64| *p >= 0.0).ok_or("Invalid threshold")?;
+                bench_args.threshold = percent / 100.0
+            },
+            _ => parse_days(arg, &mut bench_args.days)?
+        }
+    }
+    if bench_args.days.is_empty() {
+        return Err(String::from("No day given"))
+    }
+    Ok(bench_args)
+}
+
+#[derive(Debug, PartialEq)]
+struct VerifyArgs {
+    days: Vec<usize>,
+    manifest: String,
+    examples: bool
+}
+
+fn parse_verify_args(args: &[String]) -> Result<VerifyArgs, String> {
+    let mut verify_args = VerifyArgs { days: vec!(), manifest: String::from(verify::DEFAULT_MANIFEST), examples: false };
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--manifest" => verify_args.manifest = it.next().ok_or("--manifest needs a value")?.clone(),
+            "--examples" => verify_args.examples = true,
+            _ => parse_days(arg, &mut verify_args.days)?
+        }
+    }
+    if verify_args.days.is_empty() {
+        verify_args.days.extend(1..=N_DAYS)
+    }
+    Ok(verify_args)
+}
+
+#[derive(Debug, PartialEq)]
+struct GenerateArgs {
+    day: usize,
+    seed: u64,
+    size: Option<usize>,
+    scale: f64,
+    answers: bool
+}
+
+fn parse_generate_args(args: &[String]) -> Result<GenerateArgs, String> {
+    let mut days = vec!();
+    let mut generate_args = GenerateArgs { day: 0, seed: 0, size: None, scale: 1.0, answers: false };
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().cloned().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--seed" => generate_args.seed = value()?.parse().map_err(|_| "Invalid seed")?,
+            "--size" => generate_args.size = Some(value()?.parse().ok().filter(|size| *size > 0).ok_or("Invalid size")?),
+            "--scale" => generate_args.scale = value()?.parse().ok().filter(|scale: &f64| *scale > 0.0).ok_or("Invalid scale")?,
+            "--answers" => generate_args.answers = true,
+            _ => parse_days(arg, &mut days)?
+        }
+    }
+    match days[..] {
+        [day] => generate_args.day = day,
+        [] => return Err(String::from("No day given")),
+        _ => return Err(String::from("Only one day can be generated at a time"))
+    }
+    Ok(generate_args)
+}
+
+#[derive(Debug, PartialEq)]
+struct BatchArgs {
+    dir: String,
+    days: Vec<usize>,
+    json: Option<String>,
+    csv: Option<String>,
+    budget: Option<Duration>
+}
+
+fn parse_budget(seconds: &str) -> Result<Duration, String> {
+    let seconds: f64 = seconds.parse().ok().filter(|s: &f64| *s > 0.0).ok_or(format!("Invalid budget: {}", seconds))?;
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+fn parse_batch_args(args: &[String]) -> Result<BatchArgs, String> {
+    let dir = args.first().filter(|dir| !dir.starts_with("--")).ok_or("No directory given")?;
+    let mut batch_args = BatchArgs { dir: dir.clone(), days: vec!(), json: None, csv: None, budget: None };
+    let mut it = args[1..].iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().cloned().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--json" => batch_args.json = Some(value()?),
+            "--csv" => batch_args.csv = Some(value()?),
+            "--budget" => batch_args.budget = Some(parse_budget(&value()?)?),
+            _ => parse_days(arg, &mut batch_args.days)?
+        }
+    }
+    if batch_args.days.is_empty() {
+        batch_args.days.extend(1..=N_DAYS)
+    }
+    Ok(batch_args)
+}
+
+fn parse_serve_args(args: &[String]) -> Result<server::Config, String> {
+    let mut config = server::Config::default();
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().cloned().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--port" => config.port = value()?.parse().map_err(|_| "Invalid port")?,
+            "--max-body" => config.max_body = value()?.parse().map_err(|_| "Invalid body size")?,
+            "--timeout" => config.timeout = parse_budget(&value()?)?,
+            _ => return Err(format!("Unknown argument: {}", arg))
+        }
+    }
+    Ok(config)
+}
+
+#[derive(Debug, PartialEq)]
+struct RenderArgs {
+    day: usize,
+    input: Option<String>,
+    format: Format,
+    scale: usize,
+    palette: Option<Palette>,
+    out: Option<String>,
+    frames: Option<String>
+}
+
+fn parse_render_args(args: &[String]) -> Result<RenderArgs, String> {
+    let mut days = vec!();
+    let mut render_args = RenderArgs { day: 0, input: None, format: Format::Png, scale: 4, palette: None, out: None, frames: None };
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().cloned().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--input" => render_args.input = Some(value()?),
+            "--format" => render_args.format = value()?.parse()?,
+            "--scale" => render_args.scale = value()?.parse().ok().filter(|scale| *scale > 0).ok_or("Invalid scale")?,
+            "--palette" => render_args.palette = Some(value()?.parse()?),
+            "--out" => render_args.out = Some(value()?),
+            "--frames" => render_args.frames = Some(value()?),
+            _ => parse_days(arg, &mut days)?
+        }
+    }
+    match days[..] {
+        [day] if visualize::get(day).is_some() => render_args.day = day,
+        [day] => return Err(format!("Day {} cannot be drawn, only days {} can", day, aoc::list_days(&visualize::days(), "and"))),
+        [] => return Err(String::from("No day given")),
+        _ => return Err(String::from("Only one day can be drawn at a time"))
+    }
+    if render_args.out.is_some() && render_args.frames.is_some() {
+        return Err(String::from("--out and --frames cannot be used together"))
+    }
+    Ok(render_args)
+}
+
+#[derive(Debug, PartialEq)]
+struct StepArgs {
+    day: usize,
+    part: usize,
+    input: Option<String>,
+    history: usize,
+    params: Params
+}
+
+fn parse_step_args(args: &[String]) -> Result<StepArgs, String> {
+    let mut days = vec!();
+    let mut step_args = StepArgs { day: 0, part: 1, input: None, history: 1000, params: Params::default() };
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().cloned().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--part" => step_args.part = value()?.parse().ok().filter(|part| [1, 2].contains(part)).ok_or("Invalid part")?,
+            "--input" => step_args.input = Some(value()?),
+            "--history" => step_args.history = value()?.parse().ok().filter(|history| *history > 0).ok_or("Invalid history")?,
+            "--param" => step_args.params.set(&value()?)?,
+            _ => parse_days(arg, &mut days)?
+        }
+    }
+    match days[..] {
+        [day] if step::get(day).is_some() => step_args.day = day,
+        [day] => return Err(format!("Day {} cannot be stepped through, only days {} can", day, aoc::list_days(&step::days(), "and"))),
+        [] => return Err(String::from("No day given")),
+        _ => return Err(String::from("Only one day can be stepped through at a time"))
+    }
+    if step_args.input.as_deref() == Some("-") {
+        return Err(String::from("The commands are read from stdin, so --input cannot be -"))
+    }
+    Ok(step_args)
+}
+
+fn write_report(path: &Option<String>, report: &str) -> bool {
+    match path {
+        Some(path) => std::fs::write(path, report).map_err(|e| eprintln!("Could not write {}: {}", path, e)).is_ok(),
+        None => true
+    }
+}
+
+fn bench(args: &BenchArgs) -> bool {
+    let baseline = match &args.baseline {
+        Some(path) => match aoc::read_input(path).and_then(|csv| bench::from_csv(&csv)) {
+            Ok(baseline) => Some(baseline),
+            Err(e) => {
+                eprintln!("{}", e);
+                return false
+            }
+        },
+        None => None
+    };
+    let mut all_ok = true;
+    let mut measurements = vec!();
+    for day in &args.days {
+        let path = aoc::default_input_path(*day);
+        let input = match aoc::read_input(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("{}", e);
+                all_ok = false;
+                continue
+            }
+        };
+        match bench::bench_day(*day, days::get(*day).unwrap(), &input, args.runs) {
+            Ok(mut day_measurements) => measurements.append(&mut day_measurements),
+            Err(e) => {
+                eprintln!("Day {}\n{}", day, e.diagnostic(&input));
+                all_ok = false
+            }
+        }
+    }
+    println!("{}", bench::format_table(&measurements, baseline.as_deref()));
+    all_ok &= write_report(&args.json, &bench::to_json(&measurements));
+    all_ok &= write_report(&args.csv, &bench::to_csv(&measurements));
+    if let Some(baseline) = baseline {
+        let regressions = bench::find_regressions(&measurements, &baseline, args.threshold);
+        if !regressions.is_empty() {
+            eprintln!("Slower than the baseline by more than {}%:", args.threshold * 100.0);
+            for regression in regressions {
+                eprintln!("  {}", regression);
+            }
+            all_ok = false
+        }
+    }
+    all_ok
+}
+
+fn verify(args: &VerifyArgs) -> bool {
+    let manifest = match aoc::read_input(&args.manifest) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false
+        }
+    };
+    let manifest = match verify::parse_manifest(&manifest) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e.diagnostic(&manifest));
+            return false
+        }
+    };
+    let selected: Vec<_> = manifest.into_iter()
+        .filter(|e| args.days.contains(&e.day) && !(args.examples && e.is_real_input()))
+        .collect();
+    let checks = verify::verify(&selected);
+    println!("{}", verify::format_report(&checks));
+    checks.iter().all(|c| c.outcome == verify::Outcome::Pass)
+}
+
+fn generate(args: &GenerateArgs) -> bool {
+    let puzzle = days::get(args.day).unwrap();
+    let size = args.size.unwrap_or_else(|| ((puzzle.input_size() as f64 * args.scale).round() as usize).max(1));
+    let generated = puzzle.generate(args.seed, size);
+    print!("{}", generated.input);
+    if args.answers {
+        for (part, answer) in [(1, &generated.part_1), (2, &generated.part_2)] {
+            eprintln!("{}", common::format_answer(part, answer.as_deref().unwrap_or("unknown")));
+        }
+    }
+    true
+}
+
+fn batch(args: &BatchArgs) -> bool {
+    let jobs = match batch::find_jobs(&args.dir, &args.days) {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false
+        }
+    };
+    let rows = batch::solve_all(&jobs, args.budget);
+    println!("{}", batch::format_table(&rows));
+    let mut all_ok = rows.iter().all(|r| r.is_ok());
+    all_ok &= write_report(&args.json, &batch::to_json(&rows));
+    all_ok &= write_report(&args.csv, &batch::to_csv(&rows));
+    all_ok
+}
+
+fn render(args: &RenderArgs) -> Result<(), String> {
+    let path = args.input.clone().unwrap_or_else(|| aoc::default_input_path(args.day));
+    let input = aoc::read_input(&path)?;
+    let parsed = days::get(args.day).unwrap().parse(&input).map_err(|e| e.diagnostic(&input))?;
+    let picture = visualize::get(args.day).unwrap();
+    let frames = picture.frames(parsed.as_ref());
+    let style = Style { palette: args.palette.clone().unwrap_or_else(|| picture.palette()), scale: args.scale };
+    match &args.frames {
+        Some(dir) => {
+            let paths = render::write_frames(std::path::Path::new(dir), &frames, &style, args.format)?;
+            println!("Wrote {} frames to {}", paths.len(), dir);
+        },
+        None => {
+            let out = args.out.clone().unwrap_or_else(|| format!("d{:02}.{}", args.day, args.format));
+            render::write_image(std::path::Path::new(&out), frames.last().unwrap(), &style, args.format)?;
+            println!("Wrote {}", out);
+        }
+    }
+    Ok(())
+}
+
+/// Starts stepping through the input of a day, with its parameters read
+/// like for `run`.
+fn start_session(args: &StepArgs) -> Result<step::Session, String> {
+    let path = args.input.clone().unwrap_or_else(|| aoc::default_input_path(args.day));
+    let input = aoc::read_input(&path)?;
+    let parsed = days::get(args.day).unwrap().parse(&input).map_err(|e| e.diagnostic(&input))?;
+    let params = params::day_params(args.day, &params::default_params_path(args.day), &args.params)?;
+    Ok(step::Session::new(step::get(args.day).unwrap(), parsed.as_ref(), args.part, &params, args.history))
+}
+
+fn step_through(args: &StepArgs) -> Result<(), String> {
+    use std::io::{BufRead, IsTerminal, Write};
+    let mut session = start_session(args)?;
+    let ansi = std::io::stdout().is_terminal();
+    let mut message = None;
+    let mut lines = std::io::stdin().lock().lines();
+    loop {
+        println!("{}", session.screen(ansi));
+        if let Some(message) = message.take() {
+            println!("{}", message);
+        }
+        print!("> ");
+        std::io::stdout().flush().map_err(|e| e.to_string())?;
+        let line = match lines.next() {
+            Some(line) => line.map_err(|e| format!("Could not read stdin: {}", e))?,
+            None => return Ok(())
+        };
+        match line.parse::<step::Command>() {
+            Ok(step::Command::Quit) => return Ok(()),
+            Ok(command) => message = session.run(&command),
+            Err(e) => message = Some(format!("{}, h shows the commands", e))
+        }
+    }
+}
+
+/// What running a day prints, in order: lines for stdout, or errors for stderr.
+type Output = Vec<Result<String, String>>;
+
+fn new_progress(args: &RunArgs) -> Progress {
+    let progress = Progress::new(Stderr).with_level(args.log);
+    match args.budget {
+        Some(budget) => progress.with_budget(budget),
+        None => progress
+    }
+}
+
+/// The lines printed for the answer of a part: the answer, with its
+/// explanation indented below it, or a JSON object holding both.
+fn answer_lines(day: usize, part: usize, answer: &str, parsed: &dyn Any, args: &RunArgs) -> Output {
+    let Some(format) = args.explain else {
+        return vec!(Ok(common::format_answer(part, answer)))
+    };
+    let progress = new_progress(args);
+    let explanation = progress.run(|| explain::get(day).unwrap().explain(parsed, part))
+        .map_err(|_| format!("Explaining part {} stopped after {:.2?}", part, progress.elapsed()));
+    match (format, explanation) {
+        (explain::Format::Text, Ok(explanation)) => {
+            let text: Vec<_> = explanation.to_text().lines().map(|line| format!("  {}", line)).collect();
+            vec!(Ok(common::format_answer(part, answer)), Ok(text.join("\n")))
+        },
+        (explain::Format::Json, Ok(explanation)) => vec!(Ok(explain::to_json(day, part, answer, &explanation))),
+        (explain::Format::Text, Err(e)) => vec!(Ok(common::format_answer(part, answer)), Err(e)),
+        (explain::Format::Json, Err(e)) => vec!(Err(e))
+    }
+}
+
+fn run_day(day: usize, args: &RunArgs, cache: &Cache) -> Output {
+    // Nothing but the JSON is printed for JSON explanations and dumps.
+    let json = args.explain == Some(explain::Format::Json) || args.dump_parsed;
+    let mut output = if json { vec!() } else { vec!(Ok(format!("Day {}", day))) };
+    let path = args.input.clone().unwrap_or_else(|| aoc::default_input_path(day));
+    let input = match aoc::read_input(&path) {
+        Ok(input) => input,
+        Err(e) => {
+            output.push(Err(e));
+            return output
+        }
+    };
+    let puzzle = days::get(day).unwrap();
+    let parsed = match puzzle.parse(&input) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            output.push(Err(e.diagnostic(&input)));
+            return output
+        }
+    };
+    if args.dump_parsed {
+        output.push(Ok(dump_parsed(day, parsed.as_ref())));
+        return output
+    }
+    let params = match params::day_params(day, &params::default_params_path(day), &args.params) {
+        Ok(params) => params,
+        Err(e) => {
+            output.push(Err(e));
+            return output
+        }
+    };
+    // The cache does not tell answers with other parameters or counts apart.
+    let cached = args.cache != Mode::Off && params.is_empty() && args.count.is_none();
+    for part in &args.parts {
+        let key = cache::Key::new(day, *part, &input);
+        if let Some(answer) = cache.get(&key).filter(|_| cached) {
+            if Level::Info <= args.log {
+                eprintln!("[info] Part {} answered from the cache", part)
+            }
+            if args.cache == Mode::Recheck {
+                cache.recheck(key, input.clone(), answer.clone())
+            }
+            output.extend(answer_lines(day, *part, &answer, parsed.as_ref(), args));
+            continue
+        }
+        let progress = new_progress(args);
+        let solve = || match (args.count, params::get(day).filter(|_| !params.is_empty())) {
+            (Some(counter), _) => count::get(day).unwrap().solve(parsed.as_ref(), *part, &params, counter),
+            (None, Some(configurable)) => configurable.solve(parsed.as_ref(), *part, &params),
+            (None, None) => puzzle.solve(parsed.as_ref(), *part)
+        };
+        match progress.run(solve) {
+            Ok(Ok(answer)) => {
+                output.extend(answer_lines(day, *part, &answer, parsed.as_ref(), args));
+                if cached {
+                    if let Err(e) = cache.put(&key, &answer) {
+                        output.push(Err(e))
+                    }
+                }
+            },
+            Ok(Err(e)) => output.push(Err(format!("Part {} has no answer: {}", part, e))),
+            Err(_) => output.push(Err(format!("Part {} stopped after {:.2?}", part, progress.elapsed())))
+        }
+    }
+    output
+}
+
+/// Prints the output of a day and tells whether it had no errors.
+fn print_output(output: Output) -> bool {
+    let mut ok = true;
+    for line in output {
+        match line {
+            Ok(line) => println!("{}", line),
+            Err(e) => {
+                eprintln!("{}", e);
+                ok = false
+            }
+        }
+    }
+    ok
+}
+
+/// Runs the days one after the other, printing each as soon as it is done.
+#[cfg(not(feature = "parallel"))]
+fn run_days(args: &RunArgs, cache: &Cache) -> bool {
+    let mut all_ok = true;
+    for day in &args.days {
+        all_ok &= print_output(run_day(*day, args, cache));
+    }
+    all_ok
+}
+
+/// Runs the days at the same time and prints them in order once all are done.
+#[cfg(feature = "parallel")]
+fn run_days(args: &RunArgs, cache: &Cache) -> bool {
+    use rayon::prelude::*;
+
+    let outputs: Vec<Output> = args.days.par_iter().map(|day| run_day(*day, args, cache)).collect();
+    let mut all_ok = true;
+    for output in outputs {
+        all_ok &= print_output(output);
+    }
+    all_ok
+}
+
+/// Runs the days and then waits for the rechecks of cached answers.
+fn run(args: &RunArgs) -> bool {
+    let cache = Cache::new(&args.cache_dir);
+    if args.clear_cache {
+        match cache.clear() {
+            Ok(n_answers) => eprintln!("Cleared {} cached answers", n_answers),
+            Err(e) => {
+                eprintln!("{}", e);
+                return false
+            }
+        }
+    }
+    let mut all_ok = run_days(args, &cache);
+    for changed in cache.wait() {
+        eprintln!("{}", changed);
+        all_ok = false
+    }
+    all_ok
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let ok = match args.first().map(|s| s.as_str()) {
+        Some("run") => match parse_run_args(&args[1..]) {
+            Ok(run_args) => run(&run_args),
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, usage());
+                false
+            }
+        },
+        Some("bench") => match parse_bench_args(&args[1..]) {
+            Ok(bench_args) => bench(&bench_args),
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, usage());
+                false
+            }
+        },
+        Some("verify") => match parse_verify_args(&args[1..]) {
+            Ok(verify_args) => verify(&verify_args),
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, usage());
+                false
+            }
+        },
+        Some("generate") => match parse_generate_args(&args[1..]) {
+            Ok(generate_args) => generate(&generate_args),
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, usage());
+                false
+            }
+        },
+        Some("serve") => match parse_serve_args(&args[1..]) {
+            Ok(config) => server::serve(config).map_err(|e| eprintln!("Could not serve: {}", e)).is_ok(),
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, usage());
+                false
+            }
+        },
+        Some("batch") => match parse_batch_args(&args[1..]) {
+            Ok(batch_args) => batch(&batch_args),
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, usage());
+                false
+            }
+        },
+        Some("render") => match parse_render_args(&args[1..]) {
+            Ok(render_args) => render(&render_args).map_err(|e| eprintln!("{}", e)).is_ok(),
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, usage());
+                false
+            }
+        },
+        Some("step") => match parse_step_args(&args[1..]) {
+            Ok(step_args) => step_through(&step_args).map_err(|e| eprintln!("{}", e)).is_ok(),
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, usage());
+                false
+            }
+        },
+        _ => {
+            eprintln!("{}", usage());
+            false
+        }
+    };
+    if !ok {
+        std::process::exit(1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_single_day() {
+        assert_eq!(
+            parse_run_args(&args("15 --part 2 --input -")),
+            Ok(RunArgs {
+                days: vec!(15),
+                parts: vec!(2),
+                input: Some(String::from("-")),
+                log: Level::Warn,
+                budget: None,
+                cache: Mode::On,
+                cache_dir: String::from(".aoc-cache"),
+                clear_cache: false,
+                explain: None,
+                dump_parsed: false,
+                params: Params::default(),
+                count: None
+            })
+        );
+        let run_args = parse_run_args(&args("19 --log info --budget 2.5")).unwrap();
+        assert_eq!((run_args.log, run_args.budget), (Level::Info, Some(Duration::from_millis(2500))));
+        let run_args = parse_run_args(&args("14 --recheck --cache-dir answers.d")).unwrap();
+        assert_eq!((run_args.cache, run_args.cache_dir.as_str()), (Mode::Recheck, "answers.d"));
+        assert_eq!(parse_run_args(&args("--clear-cache")).unwrap().days, Vec::<usize>::new());
+        assert_eq!(parse_run_args(&args("4 --explain")).unwrap().explain, Some(explain::Format::Text));
+        let run_args = parse_run_args(&args("4 --explain json 10")).unwrap();
+        assert_eq!((run_args.explain, run_args.days), (Some(explain::Format::Json), vec!(4, 10)));
+        assert_eq!(parse_run_args(&args("6 --param days_1=18 --param days_2=80")).unwrap().params.get("days_2"), Some(80));
+        assert_eq!(parse_run_args(&args("12 16 --count u128")).unwrap().count, Some(Counter::U128));
+        #[cfg(feature = "serde")]
+        assert!(parse_run_args(&args("4 9 --dump-parsed")).unwrap().dump_parsed);
+        #[cfg(not(feature = "serde"))]
+        assert_eq!(parse_run_args(&args("4 --dump-parsed")), Err(String::from("--dump-parsed needs aoc built with the serde feature")));
+    }
+
+    #[test]
+    fn parse_all_days() {
+        let run_args = parse_run_args(&args("all")).unwrap();
+        assert_eq!(run_args.days, (1..=25).collect::<Vec<_>>());
+        assert_eq!(run_args.parts, vec!(1, 2));
+    }
+
+    #[test]
+    fn reject_bad_args() {
+        assert!(parse_run_args(&args("26")).is_err());
+        assert!(parse_run_args(&args("1 --part 3")).is_err());
+        assert!(parse_run_args(&args("1 2 --input foo")).is_err());
+        assert!(parse_run_args(&args("")).is_err());
+        assert!(parse_run_args(&args("1 --log loud")).is_err());
+        assert!(parse_run_args(&args("1 --budget 0")).is_err());
+        assert!(parse_run_args(&args("1 --explain")).is_err());
+        assert!(parse_run_args(&args("4 --explain yaml")).is_err());
+        assert!(parse_run_args(&args("6 --param days_1")).is_err());
+        assert!(parse_run_args(&args("6 11 --param days_1=18")).is_err());
+        assert!(parse_run_args(&args("1 --dump-parsed")).is_err());
+        assert!(parse_run_args(&args("4 --dump-parsed --explain")).is_err());
+        assert!(parse_run_args(&args("6 --count i64")).is_err());
+        assert!(parse_run_args(&args("6 --count")).is_err());
+    }
+
+    #[test]
+    fn usage_lists_days() {
+        let usage = usage();
+        assert!(usage.contains("--explain prints how days 4, 8, 10, 15, 19 and 23 came"));
+        assert!(usage.contains("Days 6 and 11 have parameters"));
+        assert!(usage.contains("Days 6, 12, 14, 16 and 21 can\ncount in a u128"));
+        assert_eq!(parse_run_args(&args("1 --count u128")), Err(String::from("Day 1 cannot count in another type, only days 6, 12, 14, 16 and 21 can")));
+        assert!(usage.contains("render draws days 5, 9, 11, 13, 20 and 25 as"));
+        assert!(usage.contains("simulation of day 11, 20, 23 or 25 for"));
+        assert_eq!(parse_run_args(&args("1 --explain")), Err(String::from("Day 1 cannot explain its answers, only days 4, 8, 10, 15, 19 and 23 can")));
+        assert_eq!(parse_step_args(&args("1")), Err(String::from("Day 1 cannot be stepped through, only days 11, 20, 23 and 25 can")));
+    }
+
+    #[test]
+    fn run_days() {
+        let dir = std::env::temp_dir().join(format!("aoc-run-{}", std::process::id()));
+        let cache = Cache::new(&dir);
+        let run_args = RunArgs {
+            days: vec!(1),
+            parts: vec!(1),
+            input: Some(String::from("../d01/test")),
+            log: Level::Warn,
+            budget: None,
+            cache: Mode::Off,
+            cache_dir: String::new(),
+            clear_cache: false,
+            explain: None,
+            dump_parsed: false,
+            params: Params::default(),
+            count: None
+        };
+        assert_eq!(run_day(1, &run_args, &cache), vec!(Ok(String::from("Day 1")), Ok(String::from("Answer part 1: 7"))));
+        assert!(!dir.exists());
+        let cached_args = RunArgs { cache: Mode::On, ..run_args.clone() };
+        run_day(1, &cached_args, &cache);
+        cache.put(&cache::Key::new(1, 1, include_str!("../../d01/test")), "8").unwrap();
+        assert_eq!(run_day(1, &cached_args, &cache)[1], Ok(String::from("Answer part 1: 8")));
+        assert_eq!(run_day(1, &run_args, &cache)[1], Ok(String::from("Answer part 1: 7")));
+        cache.clear().unwrap();
+        let run_args = RunArgs { input: Some(String::from("missing")), ..run_args };
+        assert!(matches!(&run_day(2, &run_args, &cache)[..], [Ok(_), Err(_)]));
+        let mut params = Params::default();
+        params.set("days_1=18").unwrap();
+        let run_args = RunArgs { input: Some(String::from("../d06/test")), params, ..cached_args };
+        assert_eq!(run_day(6, &run_args, &cache)[1], Ok(String::from("Answer part 1: 26")));
+        assert_eq!(run_day(6, &RunArgs { params: Params::default(), ..run_args.clone() }, &cache)[1], Ok(String::from("Answer part 1: 5934")));
+        assert!(matches!(&run_day(1, &RunArgs { input: Some(String::from("../d01/test")), ..run_args.clone() }, &cache)[..], [Ok(_), Err(e)] if e == "Day 1 has no parameters, only days 6 and 11 have"));
+        let mut params = Params::default();
+        params.set("days_1=600").unwrap();
+        let run_args = RunArgs { params, ..run_args };
+        assert_eq!(run_day(6, &run_args, &cache)[1], Err(String::from("Part 1 has no answer: the count does not fit in its type")));
+        let output = run_day(6, &RunArgs { count: Some(Counter::U128), ..run_args }, &cache);
+        assert!(output[1].as_ref().unwrap().starts_with("Answer part 1: "));
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn explain_answers() {
+        let cache = Cache::new(std::env::temp_dir().join(format!("aoc-explain-{}", std::process::id())));
+        let run_args = RunArgs {
+            days: vec!(10),
+            parts: vec!(2),
+            input: Some(String::from("../d10/test")),
+            log: Level::Warn,
+            budget: None,
+            cache: Mode::Off,
+            cache_dir: String::new(),
+            clear_cache: false,
+            explain: Some(explain::Format::Text),
+            dump_parsed: false,
+            params: Params::default(),
+            count: None
+        };
+        let output = run_day(10, &run_args, &cache);
+        assert_eq!(output[..2], [Ok(String::from("Day 10")), Ok(String::from("Answer part 2: 288957"))]);
+        assert!(output[2].as_ref().unwrap().starts_with("  incomplete:\n    - line: 1\n      completion: }}]])})]\n"));
+        let run_args = RunArgs { explain: Some(explain::Format::Json), ..run_args };
+        let output = run_day(10, &run_args, &cache);
+        assert_eq!(output.len(), 1);
+        assert!(output[0].as_ref().unwrap().starts_with("{\"day\": 10, \"part\": 2, \"answer\": \"288957\", \"explanation\": {\"incomplete\": "));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn dump_parsed() {
+        let cache = Cache::new(std::env::temp_dir().join(format!("aoc-dump-{}", std::process::id())));
+        let run_args = RunArgs {
+            days: vec!(9),
+            parts: vec!(1, 2),
+            input: Some(String::from("../d09/test")),
+            log: Level::Warn,
+            budget: None,
+            cache: Mode::Off,
+            cache_dir: String::new(),
+            clear_cache: false,
+            explain: None,
+            dump_parsed: true,
+            params: Params::default(),
+            count: None
+        };
+        let output = run_day(9, &run_args, &cache);
+        assert_eq!(output.len(), 1);
+        assert!(output[0].as_ref().unwrap().starts_with("[[2,1,9,9,9,4,3,2,1,0],[3,9,8,7,8,9,4,9,2,1],"));
+    }
+
+    #[test]
+    fn parse_bench() {
+        assert_eq!(
+            parse_bench_args(&args("14 19 --runs 3 --csv bench.csv --baseline base.csv --threshold 25")),
+            Ok(BenchArgs {
+                days: vec!(14, 19),
+                runs: 3,
+                json: None,
+                csv: Some(String::from("bench.csv")),
+                baseline: Some(String::from("base.csv")),
+                threshold: 0.25
+            })
+        );
+        assert!(parse_bench_args(&args("1 --runs 0")).is_err());
+        assert!(parse_bench_args(&args("1 --threshold")).is_err());
+        assert!(parse_bench_args(&args("--runs 2")).is_err());
+    }
+
+    #[test]
+    fn parse_verify() {
+        assert_eq!(
+            parse_verify_args(&args("--examples")),
+            Ok(VerifyArgs { days: (1..=25).collect(), manifest: String::from("answers"), examples: true })
+        );
+        assert_eq!(parse_verify_args(&args("3 --manifest m")).unwrap().days, vec!(3));
+        assert!(parse_verify_args(&args("--manifest")).is_err());
+    }
+
+    #[test]
+    fn parse_generate() {
+        assert_eq!(
+            parse_generate_args(&args("19 --seed 7 --scale 10 --answers")),
+            Ok(GenerateArgs { day: 19, seed: 7, size: None, scale: 10.0, answers: true })
+        );
+        assert_eq!(parse_generate_args(&args("5 --size 50")).unwrap().size, Some(50));
+        assert!(parse_generate_args(&args("5 6")).is_err());
+        assert!(parse_generate_args(&args("all")).is_err());
+        assert!(parse_generate_args(&args("5 --size 0")).is_err());
+    }
+
+    #[test]
+    fn parse_serve() {
+        let config = parse_serve_args(&args("--port 9000 --timeout 2.5")).unwrap();
+        assert_eq!((config.port, config.timeout), (9000, Duration::from_millis(2500)));
+        assert!(parse_serve_args(&args("--port x")).is_err());
+        assert!(parse_serve_args(&args("9000")).is_err());
+    }
+
+    #[test]
+    fn parse_batch() {
+        assert_eq!(
+            parse_batch_args(&args("inputs 1 2 --csv out.csv --budget 10")),
+            Ok(BatchArgs {
+                dir: String::from("inputs"),
+                days: vec!(1, 2),
+                json: None,
+                csv: Some(String::from("out.csv")),
+                budget: Some(Duration::from_secs(10))
+            })
+        );
+        assert_eq!(parse_batch_args(&args("inputs")).unwrap().days.len(), 25);
+        assert!(parse_batch_args(&args("")).is_err());
+        assert!(parse_batch_args(&args("--json out.json")).is_err());
+    }
+
+    #[test]
+    fn parse_render() {
+        assert_eq!(
+            parse_render_args(&args("11 --format svg --scale 10 --palette gray --frames out")),
+            Ok(RenderArgs {
+                day: 11,
+                input: None,
+                format: Format::Svg,
+                scale: 10,
+                palette: Some(Palette::Gray),
+                out: None,
+                frames: Some(String::from("out"))
+            })
+        );
+        assert_eq!(parse_render_args(&args("25 --palette #000000,ffffff")).unwrap().palette, Some(Palette::Colors(vec!([0; 3], [255; 3]))));
+        assert!(parse_render_args(&args("1")).is_err());
+        assert!(parse_render_args(&args("5 9")).is_err());
+        assert!(parse_render_args(&args("5 --format gif")).is_err());
+        assert!(parse_render_args(&args("5 --scale 0")).is_err());
+        assert!(parse_render_args(&args("5 --out a.png --frames dir")).is_err());
+    }
+
+    #[test]
+    fn parse_step() {
+        assert_eq!(
+            parse_step_args(&args("11 --part 2 --input d11/test2 --history 50")),
+            Ok(StepArgs { day: 11, part: 2, input: Some(String::from("d11/test2")), history: 50, params: Params::default() })
+        );
+        assert_eq!(parse_step_args(&args("25")).unwrap().history, 1000);
+        assert_eq!(parse_step_args(&args("11 --param steps=10")).unwrap().params.get("steps"), Some(10));
+        assert!(parse_step_args(&args("11 --param steps")).is_err());
+        assert!(parse_step_args(&args("1")).is_err());
+        assert!(parse_step_args(&args("11 20")).is_err());
+        assert!(parse_step_args(&args("11 --part 3")).is_err());
+        assert!(parse_step_args(&args("11 --history 0")).is_err());
+        assert!(parse_step_args(&args("11 --input -")).is_err());
+    }
+
+    #[test]
+    fn step_with_params() {
+        let mut step_args = parse_step_args(&args("11 --input ../d11/test2 --param steps=10")).unwrap();
+        let mut session = start_session(&step_args).unwrap();
+        session.run(&step::Command::Forward(1000));
+        assert_eq!(session.at(), 10);
+        step_args.params = Params::default();
+        let mut session = start_session(&step_args).unwrap();
+        session.run(&step::Command::Forward(1000));
+        assert_eq!(session.at(), 100);
+        let step_args = parse_step_args(&args("25 --input ../d25/test --param steps=10")).unwrap();
+        assert_eq!(start_session(&step_args).err(), Some(String::from("Day 25 has no parameters, only days 6 and 11 have")));
+    }
+}
@@ -0,0 +1,101 @@
+//! The days with parameters, with their input types erased like in `days`.
+
+use std::any::Any;
+use std::marker::PhantomData;
+use std::path::Path;
+use common::Answer;
+use common::params::{Configure, Params};
+
+pub trait Configurable: Sync {
+    /// The names of the parameters with their values in the puzzle.
+    fn params(&self) -> &'static [(&'static str, usize)];
+    /// Answers `part` of an input returned by `Puzzle::parse` of the same day.
+    fn solve(&self, input: &dyn Any, part: usize, params: &Params) -> Result<String, String>;
+}
+
+struct Day<S>(PhantomData<fn() -> S>);
+
+impl<S: Configure> Configurable for Day<S> where S::Input: 'static {
+    fn params(&self) -> &'static [(&'static str, usize)] {
+        S::PARAMS
+    }
+
+    fn solve(&self, input: &dyn Any, part: usize, params: &Params) -> Result<String, String> {
+        let input = input.downcast_ref::<S::Input>().expect("Input was parsed by another day");
+        match part {
+            1 => S::part_1_with(input, params).answer(),
+            2 => S::part_2_with(input, params).answer(),
+            _ => panic!("Invalid part {}", part)
+        }
+    }
+}
+
+static CONFIGURABLE: [(usize, &dyn Configurable); 2] = [
+    (6, &Day::<d06::Day06>(PhantomData)),
+    (11, &Day::<d11::Day11>(PhantomData)),
+];
+
+/// The days with parameters.
+pub fn days() -> Vec<usize> {
+    CONFIGURABLE.iter().map(|(day, _)| *day).collect()
+}
+
+/// The parameters of a day, if it has any.
+pub fn get(day: usize) -> Option<&'static dyn Configurable> {
+    CONFIGURABLE.iter().find(|(d, _)| *d == day).map(|(_, configurable)| *configurable)
+}
+
+/// The file with the parameters of a day.
+pub fn default_params_path(day: usize) -> String {
+    format!("d{:02}/params", day)
+}
+
+/// The parameters of a day set in `path`, if there is such a file, and
+/// then those of `overrides`. Fails if any of them is not one of the day.
+pub fn day_params(day: usize, path: &str, overrides: &Params) -> Result<Params, String> {
+    let mut params = Params::default();
+    if Path::new(path).exists() {
+        let text = crate::read_input(path)?;
+        params = Params::parse(&text).map_err(|e| format!("{}: {}", path, e.diagnostic(&text)))?;
+    }
+    params.extend(overrides.clone());
+    if params.is_empty() {
+        return Ok(params)
+    }
+    let names: Vec<_> = match get(day) {
+        Some(configurable) => configurable.params().iter().map(|(name, _)| *name).collect(),
+        None => return Err(format!("Day {} has no parameters, only days {} have", day, crate::list_days(&days(), "and")))
+    };
+    params.check(&names)?;
+    Ok(params)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn set_params() {
+        let dir = std::env::temp_dir().join(format!("aoc-params-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("params");
+        std::fs::write(&path, "days_1 = 18\ndays_2 = 18\n").unwrap();
+        let path = path.to_str().unwrap();
+        let mut overrides = Params::default();
+        overrides.set("days_2=80").unwrap();
+        let params = day_params(6, path, &overrides).unwrap();
+        assert_eq!((params.get("days_1"), params.get("days_2")), (Some(18), Some(80)));
+        let input = crate::read_input("../d06/test").unwrap();
+        let parsed = days::get(6).unwrap().parse(&input).unwrap();
+        assert_eq!(get(6).unwrap().solve(parsed.as_ref(), 1, &params), Ok(String::from("26")));
+        assert_eq!(get(6).unwrap().solve(parsed.as_ref(), 2, &params), Ok(String::from("5934")));
+        assert_eq!(day_params(11, path, &Params::default()), Err(String::from("Unknown parameter days_1, expected one of steps")));
+        assert_eq!(day_params(1, path, &Params::default()), Err(String::from("Day 1 has no parameters, only days 6 and 11 have")));
+        assert_eq!(day_params(1, "missing", &Params::default()), Ok(Params::default()));
+        std::fs::write(dir.join("params"), "days_1 18").unwrap();
+        assert!(day_params(6, path, &Params::default()).unwrap_err().contains("expected `=`"));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(days(), vec!(6, 11));
+    }
+}
@@ -0,0 +1,358 @@
+//! A small local HTTP server answering `POST /day/{n}/part/{p}` with the
+//! puzzle input as the body. Answers, timings and parse errors come back as
+//! JSON. Bodies are limited in size, and a solver that takes longer than
+//! the timeout is answered with an error and cancelled, so a slow input
+//! cannot tie up the server. A solver that does not stop when cancelled
+//! keeps its connection's place until it is done.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+use common::explain::json_string;
+use common::progress::{Progress, Stderr};
+use crate::days::{self, N_DAYS};
+
+/// Longest request line or header line that is accepted.
+const MAX_LINE: usize = 8 * 1024;
+
+/// Most header lines that are accepted.
+const MAX_HEADERS: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub port: u16,
+    /// Largest body, in bytes, that is accepted.
+    pub max_body: usize,
+    /// Time a request may take to be solved.
+    pub timeout: Duration,
+    /// Time a client may take to send its request.
+    pub read_timeout: Duration,
+    /// Requests handled, counting those whose solver still runs after they
+    /// timed out, at the same time. More are turned away.
+    pub max_connections: usize
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            port: 8080,
+            max_body: 1024 * 1024,
+            timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(5),
+            max_connections: 8
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String
+}
+
+impl Response {
+    fn new(status: u16, body: String) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::new(status, format!("{{\"error\": {}}}", json_string(message)))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            411 => "Length Required",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            431 => "Request Header Fields Too Large",
+            500 => "Internal Server Error",
+            503 => "Service Unavailable",
+            504 => "Gateway Timeout",
+            _ => ""
+        }
+    }
+
+    fn write_to(&self, stream: &mut impl Write) -> io::Result<()> {
+        write!(stream, "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status, self.reason(), self.body.len(), self.body)?;
+        stream.flush()
+    }
+}
+
+/// The day and part of a path `/day/{n}/part/{p}`.
+fn route(path: &str) -> Option<(usize, usize)> {
+    let rest = path.strip_prefix("/day/")?;
+    let (day, part) = rest.split_once("/part/")?;
+    let day: usize = day.parse().ok().filter(|day| (1..=N_DAYS).contains(day))?;
+    let part: usize = part.parse().ok().filter(|part| *part == 1 || *part == 2)?;
+    Some((day, part))
+}
+
+/// Parses and solves `input` on a thread of its own, and gives up on it
+/// after `timeout`. The solver is then cancelled, which stops the days
+/// that check for it; the others run on until they are done.
+pub fn solve(day: usize, part: usize, input: String, timeout: Duration) -> Response {
+    solve_holding(day, part, input, timeout, ())
+}
+
+/// Solves like `solve`, and drops `guard` only once the solver thread is
+/// done, even if that is long after the answer timed out.
+fn solve_holding<G: Send + 'static>(day: usize, part: usize, input: String, timeout: Duration, guard: G) -> Response {
+    let puzzle = days::get(day).unwrap();
+    let progress = Progress::new(Stderr).with_budget(timeout);
+    let (sender, receiver) = mpsc::channel();
+    let handle = progress.clone();
+    thread::spawn(move || {
+        let _guard = guard;
+        let result = panic::catch_unwind(AssertUnwindSafe(|| handle.run(|| {
+            let start = Instant::now();
+            let parsed = puzzle.parse(&input).map_err(|e| (e.clone(), e.diagnostic(&input)));
+            let parse_time = start.elapsed();
+            parsed.map(|parsed| {
+                let start = Instant::now();
+                let answer = puzzle.solve(parsed.as_ref(), part);
+                (answer, parse_time, start.elapsed())
+            })
+        })));
+        let _ = sender.send(result);
+    });
+    let cancelled = || Response::error(504, &format!("No answer within {:.2?}", timeout));
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(Ok(Ok((Ok(answer), parse_time, solve_time))))) => Response::new(200, format!(
+            "{{\"day\": {}, \"part\": {}, \"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}}}",
+            day, part, json_string(&answer), parse_time.as_nanos(), solve_time.as_nanos()
+        )),
+        Ok(Ok(Ok(Err((e, diagnostic))))) => Response::new(422, format!(
+            "{{\"error\": {}, \"line\": {}, \"column\": {}, \"expected\": {}}}",
+            json_string(&diagnostic), e.line, e.column, json_string(&e.expected)
+        )),
+        Ok(Ok(Ok(Ok((Err(e), _, _))))) => Response::error(422, &format!("Part {} has no answer: {}", part, e)),
+        Ok(Ok(Err(_))) => cancelled(),
+        Ok(Err(_)) | Err(mpsc::RecvTimeoutError::Disconnected) => Response::error(500, "The solver failed on this input"),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            progress.cancel();
+            cancelled()
+        }
+    }
+}
+
+/// Reads a line of at most `MAX_LINE` bytes, without the line break.
+fn read_line(reader: &mut impl BufRead) -> Result<String, Response> {
+    let mut line = vec!();
+    let n_read = reader.take(MAX_LINE as u64 + 1).read_until(b'\n', &mut line).map_err(read_error)?;
+    if n_read > MAX_LINE {
+        return Err(Response::error(431, "Line too long"))
+    }
+    if line.last() != Some(&b'\n') {
+        return Err(Response::error(400, "Incomplete request"))
+    }
+    String::from_utf8(line).map(|line| String::from(line.trim_end())).map_err(|_| Response::error(400, "Invalid request"))
+}
+
+fn read_error(e: io::Error) -> Response {
+    match e.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => Response::error(408, "Request not received in time"),
+        _ => Response::error(400, "Could not read the request")
+    }
+}
+
+/// Reads a request and answers it.
+pub fn handle(stream: impl Read, config: &Config) -> Response {
+    handle_holding(stream, config, ())
+}
+
+/// Answers like `handle`, with `guard` held by the solver if there is one.
+fn handle_holding<G: Send + 'static>(stream: impl Read, config: &Config, guard: G) -> Response {
+    let mut reader = BufReader::new(stream);
+    match read_request(&mut reader, config) {
+        Ok((day, part, input)) => solve_holding(day, part, input, config.timeout, guard),
+        Err(response) => response
+    }
+}
+
+fn read_request(reader: &mut impl BufRead, config: &Config) -> Result<(usize, usize, String), Response> {
+    let request_line = read_line(reader)?;
+    let mut fields = request_line.split(' ');
+    let (method, path) = match (fields.next(), fields.next(), fields.next()) {
+        (Some(method), Some(path), Some(version)) if version.starts_with("HTTP/") => (method, path),
+        _ => return Err(Response::error(400, "Invalid request line"))
+    };
+    let mut content_length = None;
+    for n_headers in 0.. {
+        let header = read_line(reader)?;
+        if header.is_empty() {
+            break
+        }
+        if n_headers == MAX_HEADERS {
+            return Err(Response::error(431, "Too many headers"))
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = Some(value.trim().parse::<usize>().map_err(|_| Response::error(400, "Invalid Content-Length"))?)
+            }
+        }
+    }
+    let (day, part) = route(path).ok_or_else(|| Response::error(404, "Expected /day/{1-25}/part/{1-2}"))?;
+    if method != "POST" {
+        return Err(Response::error(405, "Only POST is supported"))
+    }
+    let length = content_length.ok_or_else(|| Response::error(411, "Content-Length is needed"))?;
+    if length > config.max_body {
+        return Err(Response::error(413, &format!("The input is larger than {} bytes", config.max_body)))
+    }
+    let mut body = vec!(0; length);
+    reader.read_exact(&mut body).map_err(read_error)?;
+    let input = String::from_utf8(body).map_err(|_| Response::error(400, "The input is not UTF-8"))?;
+    Ok((day, part, input))
+}
+
+/// Answers a connection, which keeps `open` until it is closed and its
+/// solver is done.
+fn handle_connection(mut stream: TcpStream, config: &Config, open: Arc<Open>) {
+    let _ = stream.set_read_timeout(Some(config.read_timeout));
+    let response = match stream.try_clone() {
+        Ok(reader) => handle_holding(reader, config, open.clone()),
+        Err(_) => Response::error(500, "Could not read the request")
+    };
+    let _ = response.write_to(&mut stream);
+}
+
+/// Serves requests on `127.0.0.1` until the process is stopped, each on a
+/// thread of its own.
+pub fn serve(config: Config) -> io::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", config.port))?;
+    eprintln!("Listening on http://{}", listener.local_addr()?);
+    serve_on(listener, config)
+}
+
+/// Decrements the number of open connections when a connection and its
+/// solver are both done.
+struct Open(Arc<AtomicUsize>);
+
+impl Drop for Open {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn serve_on(listener: TcpListener, config: Config) -> io::Result<()> {
+    let config = Arc::new(config);
+    let n_open = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(_) => continue
+        };
+        if n_open.fetch_add(1, Ordering::SeqCst) >= config.max_connections {
+            n_open.fetch_sub(1, Ordering::SeqCst);
+            let _ = Response::error(503, "Too many requests at the same time").write_to(&mut stream);
+            continue
+        }
+        let open = Arc::new(Open(n_open.clone()));
+        let config = config.clone();
+        thread::spawn(move || handle_connection(stream, &config, open));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn request(path: &str, body: &str) -> String {
+        format!("POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}", path, body.len(), body)
+    }
+
+    fn answer(request: &str) -> Response {
+        handle(request.as_bytes(), &Config::default())
+    }
+
+    #[test]
+    fn routes() {
+        assert_eq!(route("/day/1/part/2"), Some((1, 2)));
+        assert_eq!(route("/day/26/part/1"), None);
+        assert_eq!(route("/day/1/part/3"), None);
+        assert_eq!(route("/day/1"), None);
+    }
+
+    #[test]
+    fn answers() {
+        let response = answer(&request("/day/1/part/1", include_str!("../../d01/test")));
+        assert_eq!(response.status, 200);
+        assert!(response.body.starts_with("{\"day\": 1, \"part\": 1, \"answer\": \"7\", \"parse_ns\": "));
+    }
+
+    #[test]
+    fn parse_error() {
+        let response = answer(&request("/day/1/part/1", "199\n2x0"));
+        assert_eq!(response.status, 422);
+        assert!(response.body.contains("\"line\": 2, \"column\": 1, \"expected\": \"a depth\""), "{}", response.body);
+    }
+
+    #[test]
+    fn no_answer() {
+        // The product of three literals of 2^60.
+        let response = answer(&request("/day/16/part/2", "0600C48C210842108421084200123084210842108421080048C210842108421084200"));
+        assert_eq!(response.status, 422);
+        assert_eq!(response.body, "{\"error\": \"Part 2 has no answer: the count does not fit in its type\"}");
+    }
+
+    #[test]
+    fn bad_requests() {
+        assert_eq!(answer(&request("/day/0/part/1", "")).status, 404);
+        assert_eq!(answer(&request("/day/1/part/1", "")[..20]).status, 400);
+        assert_eq!(answer("GET /day/1/part/1 HTTP/1.1\r\n\r\n").status, 405);
+        assert_eq!(answer("POST /day/1/part/1 HTTP/1.1\r\n\r\n").status, 411);
+        let config = Config { max_body: 10, ..Config::default() };
+        assert_eq!(handle(request("/day/1/part/1", include_str!("../../d01/test")).as_bytes(), &config).status, 413);
+    }
+
+    #[test]
+    fn timeout() {
+        let start = Instant::now();
+        let response = solve(19, 1, String::from(include_str!("../../d19/test")), Duration::from_millis(50));
+        assert_eq!(response.status, 504);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    /// Tells on which thread it was dropped.
+    struct Dropped(mpsc::Sender<thread::ThreadId>);
+
+    impl Drop for Dropped {
+        fn drop(&mut self) {
+            let _ = self.0.send(thread::current().id());
+        }
+    }
+
+    #[test]
+    fn solver_holds_guard() {
+        let (sender, receiver) = mpsc::channel();
+        let input = String::from(include_str!("../../d19/test"));
+        let response = solve_holding(19, 1, input, Duration::from_millis(50), Dropped(sender));
+        assert_eq!(response.status, 504);
+        let dropped_on = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_ne!(dropped_on, thread::current().id());
+    }
+
+    #[test]
+    fn over_tcp() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || serve_on(listener, Config::default()));
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request("/day/1/part/2", include_str!("../../d01/test")).as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\"answer\": \"5\""));
+    }
+}
@@ -0,0 +1,388 @@
+//! Steps through the days that run a simulation, forwards and back, with
+//! their states erased like the inputs in `days`.
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use common::params::Params;
+use common::simulate::Simulate;
+
+pub trait Simulation: Sync {
+    /// The first state of a part of an input returned by `Puzzle::parse` of
+    /// the same day, with the parameters of the day.
+    fn start(&self, input: &dyn Any, part: usize, params: &Params) -> Box<dyn Any>;
+    fn step(&self, state: &dyn Any) -> Option<Box<dyn Any>>;
+    fn draw(&self, state: &dyn Any) -> Vec<String>;
+    fn counters(&self, state: &dyn Any) -> Vec<(&'static str, usize)>;
+    fn origin(&self, state: &dyn Any) -> (isize, isize);
+}
+
+struct Day<S>(PhantomData<fn() -> S>);
+
+impl<S: Simulate> Day<S> {
+    fn state<'a>(&self, state: &'a dyn Any) -> &'a S::State where S::State: 'static {
+        state.downcast_ref::<S::State>().expect("State was made by another day")
+    }
+}
+
+impl<S: Simulate> Simulation for Day<S> where S::Input: 'static, S::State: 'static {
+    fn start(&self, input: &dyn Any, part: usize, params: &Params) -> Box<dyn Any> {
+        Box::new(S::start(input.downcast_ref::<S::Input>().expect("Input was parsed by another day"), part, params))
+    }
+
+    fn step(&self, state: &dyn Any) -> Option<Box<dyn Any>> {
+        S::step(self.state(state)).map(|next| Box::new(next) as Box<dyn Any>)
+    }
+
+    fn draw(&self, state: &dyn Any) -> Vec<String> {
+        S::draw(self.state(state))
+    }
+
+    fn counters(&self, state: &dyn Any) -> Vec<(&'static str, usize)> {
+        S::counters(self.state(state))
+    }
+
+    fn origin(&self, state: &dyn Any) -> (isize, isize) {
+        S::origin(self.state(state))
+    }
+}
+
+static SIMULATIONS: [(usize, &dyn Simulation); 4] = [
+    (11, &Day::<d11::Day11>(PhantomData)),
+    (20, &Day::<d20::Day20>(PhantomData)),
+    (23, &Day::<d23::Day23>(PhantomData)),
+    (25, &Day::<d25::Day25>(PhantomData)),
+];
+
+/// The days that can be stepped through.
+pub fn days() -> Vec<usize> {
+    SIMULATIONS.iter().map(|(day, _)| *day).collect()
+}
+
+/// The simulation of a day, if it has one.
+pub fn get(day: usize) -> Option<&'static dyn Simulation> {
+    SIMULATIONS.iter().find(|(d, _)| *d == day).map(|(_, simulation)| *simulation)
+}
+
+/// A run to a condition gives up after this many steps.
+const MAX_RUN: usize = 100_000;
+
+pub const HELP: &str = "\
+s [n]       step forward once, or n times
+b [n]       step back once, or n times
+g <n>       go to step n
+u <counter> <=|!=|<|<=|>|>=> <n>
+            step until a counter compares like that to n
+h           show this help
+q           quit
+An empty line steps forward once.";
+
+/// How a counter is compared in a condition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual
+}
+
+impl Comparison {
+    fn holds(&self, a: usize, b: usize) -> bool {
+        match self {
+            Comparison::Equal => a == b,
+            Comparison::NotEqual => a != b,
+            Comparison::Less => a < b,
+            Comparison::LessOrEqual => a <= b,
+            Comparison::Greater => a > b,
+            Comparison::GreaterOrEqual => a >= b
+        }
+    }
+}
+
+/// What to do next, typed at the prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Forward(usize),
+    Back(usize),
+    GoTo(usize),
+    Until(String, Comparison, usize),
+    Help,
+    Quit
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Command, String> {
+        let words: Vec<_> = s.split_whitespace().collect();
+        let number = |word: &str| word.parse::<usize>().map_err(|_| format!("Not a number: {}", word));
+        let count = || match words.get(1) {
+            Some(word) => number(word),
+            None => Ok(1)
+        };
+        match words[..] {
+            [] => Ok(Command::Forward(1)),
+            ["s" | "step", ..] if words.len() <= 2 => Ok(Command::Forward(count()?)),
+            ["b" | "back", ..] if words.len() <= 2 => Ok(Command::Back(count()?)),
+            ["g" | "goto", step] => Ok(Command::GoTo(number(step)?)),
+            ["u" | "until", counter, comparison, value] => {
+                let comparison = match comparison {
+                    "=" | "==" => Comparison::Equal,
+                    "!=" => Comparison::NotEqual,
+                    "<" => Comparison::Less,
+                    "<=" => Comparison::LessOrEqual,
+                    ">" => Comparison::Greater,
+                    ">=" => Comparison::GreaterOrEqual,
+                    _ => return Err(format!("Invalid comparison: {}", comparison))
+                };
+                Ok(Command::Until(String::from(counter), comparison, number(value)?))
+            },
+            ["h" | "help" | "?"] => Ok(Command::Help),
+            ["q" | "quit"] => Ok(Command::Quit),
+            _ => Err(format!("Invalid command: {}", s.trim()))
+        }
+    }
+}
+
+/// A simulation being stepped through, with the last states kept so that
+/// it can go back without starting over.
+pub struct Session {
+    simulation: &'static dyn Simulation,
+    history: VecDeque<Box<dyn Any>>,
+    /// The step of the oldest state kept.
+    first: usize,
+    /// The step shown.
+    at: usize,
+    capacity: usize,
+    /// Whether the newest state kept is the last one.
+    over: bool
+}
+
+impl Session {
+    /// Starts a part of a parsed input, keeping at most `capacity` states.
+    pub fn new(simulation: &'static dyn Simulation, input: &dyn Any, part: usize, params: &Params, capacity: usize) -> Session {
+        let history = VecDeque::from([simulation.start(input, part, params)]);
+        Session { simulation, history, first: 0, at: 0, capacity: capacity.max(1), over: false }
+    }
+
+    /// The step shown, counted from 0 for the start.
+    pub fn at(&self) -> usize {
+        self.at
+    }
+
+    fn last(&self) -> usize {
+        self.first + self.history.len() - 1
+    }
+
+    fn state(&self) -> &dyn Any {
+        self.history[self.at - self.first].as_ref()
+    }
+
+    pub fn counters(&self) -> Vec<(&'static str, usize)> {
+        self.simulation.counters(self.state())
+    }
+
+    /// Steps forward once, unless the simulation is over.
+    pub fn forward(&mut self) -> bool {
+        if self.at < self.last() {
+            self.at += 1;
+            return true
+        }
+        if self.over {
+            return false
+        }
+        match self.simulation.step(self.state()) {
+            Some(next) => {
+                self.history.push_back(next);
+                if self.history.len() > self.capacity {
+                    self.history.pop_front();
+                    self.first += 1;
+                }
+                self.at += 1;
+                true
+            },
+            None => {
+                self.over = true;
+                false
+            }
+        }
+    }
+
+    /// Steps back once, unless the state before is no longer kept.
+    pub fn back(&mut self) -> bool {
+        if self.at > self.first {
+            self.at -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Carries out a command, and tells how it went if that is not plain
+    /// from the screen.
+    pub fn run(&mut self, command: &Command) -> Option<String> {
+        match command {
+            Command::Forward(n) => {
+                let n_taken = (0..*n).take_while(|_| self.forward()).count();
+                (n_taken < *n).then(|| format!("The simulation is over after step {}", self.at))
+            },
+            Command::Back(n) => {
+                let n_taken = (0..*n).take_while(|_| self.back()).count();
+                (n_taken < *n).then(|| format!("Only the steps from {} are kept", self.first))
+            },
+            Command::GoTo(step) => {
+                while self.at > *step && self.back() {}
+                while self.at < *step && self.forward() {}
+                if self.at < *step {
+                    Some(format!("The simulation is over after step {}", self.at))
+                } else if self.at > *step {
+                    Some(format!("Only the steps from {} are kept", self.first))
+                } else {
+                    None
+                }
+            },
+            Command::Until(counter, comparison, value) => {
+                let holds = |session: &Session| session.counters().iter()
+                    .find(|(name, _)| name == counter)
+                    .map(|(_, count)| comparison.holds(*count, *value));
+                if holds(self).is_none() {
+                    let names: Vec<_> = self.counters().iter().map(|(name, _)| *name).collect();
+                    return Some(format!("No counter {}, only {}", counter, names.join(", ")))
+                }
+                for _ in 0..MAX_RUN {
+                    if !self.forward() {
+                        return Some(format!("The simulation is over after step {}", self.at))
+                    }
+                    if holds(self) == Some(true) {
+                        return None
+                    }
+                }
+                Some(format!("Gave up after {} steps", MAX_RUN))
+            },
+            Command::Help => Some(String::from(HELP)),
+            Command::Quit => None
+        }
+    }
+
+    /// The cells drawn in both this step and the one before that changed,
+    /// as row and column in the drawing of this step.
+    fn changed(&self) -> Vec<(usize, usize)> {
+        if self.at == self.first {
+            return vec!()
+        }
+        let before = self.history[self.at - self.first - 1].as_ref();
+        let (lines, old_lines) = (self.simulation.draw(self.state()), self.simulation.draw(before));
+        let (origin, old_origin) = (self.simulation.origin(self.state()), self.simulation.origin(before));
+        let mut changed = vec!();
+        for (row, line) in lines.iter().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                let old_row = (row as isize + origin.0 - old_origin.0) as usize;
+                let old_col = (col as isize + origin.1 - old_origin.1) as usize;
+                let old = old_lines.get(old_row).and_then(|line| line.chars().nth(old_col));
+                if old.is_some_and(|old| old != c) {
+                    changed.push((row, col))
+                }
+            }
+        }
+        changed
+    }
+
+    /// The step, the drawing and the counters. With `ansi` the screen is
+    /// cleared first and the cells that changed in the last step are shown
+    /// reversed.
+    pub fn screen(&self, ansi: bool) -> String {
+        let changed = self.changed();
+        let mut screen = String::new();
+        if ansi {
+            screen.push_str("\x1b[2J\x1b[H");
+        }
+        let over = if self.over && self.at == self.last() { ", the last" } else { "" };
+        screen.push_str(&format!("Step {}{}\n", self.at, over));
+        for (row, line) in self.simulation.draw(self.state()).iter().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                if ansi && changed.contains(&(row, col)) {
+                    screen.push_str(&format!("\x1b[7m{}\x1b[0m", c))
+                } else {
+                    screen.push(c)
+                }
+            }
+            screen.push('\n')
+        }
+        let mut counters: Vec<_> = self.counters().iter().map(|(name, count)| format!("{}: {}", name, count)).collect();
+        counters.push(format!("changed: {}", changed.len()));
+        screen.push_str(&counters.join(", "));
+        screen
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days;
+
+    fn start(day: usize, test: &str, part: usize, capacity: usize) -> Session {
+        let input = crate::read_input(&format!("../d{:02}/{}", day, test)).unwrap();
+        let parsed = days::get(day).unwrap().parse(&input).unwrap();
+        Session::new(get(day).unwrap(), parsed.as_ref(), part, &Params::default(), capacity)
+    }
+
+    #[test]
+    fn commands() {
+        assert_eq!("".parse(), Ok(Command::Forward(1)));
+        assert_eq!("s 10".parse(), Ok(Command::Forward(10)));
+        assert_eq!("back".parse(), Ok(Command::Back(1)));
+        assert_eq!("g 5".parse(), Ok(Command::GoTo(5)));
+        assert_eq!("u flashed = 100".parse(), Ok(Command::Until(String::from("flashed"), Comparison::Equal, 100)));
+        assert_eq!("q".parse(), Ok(Command::Quit));
+        assert!("s x".parse::<Command>().is_err());
+        assert!("s 1 2".parse::<Command>().is_err());
+        assert!("u moved ~ 0".parse::<Command>().is_err());
+        assert!("jump".parse::<Command>().is_err());
+        assert_eq!(days(), vec!(11, 20, 23, 25));
+        assert!(get(1).is_none());
+    }
+
+    #[test]
+    fn forward_and_back() {
+        let mut session = start(11, "test2", 2, 1000);
+        assert_eq!(session.run(&Command::Until(String::from("flashed"), Comparison::Equal, 100)), None);
+        assert_eq!(session.at(), 195);
+        assert_eq!(session.run(&Command::Forward(1)), Some(String::from("The simulation is over after step 195")));
+        assert_eq!(session.run(&Command::Back(95)), None);
+        assert_eq!(session.at(), 100);
+        assert_eq!(session.counters()[0], ("flashes", 1656));
+        assert_eq!(session.run(&Command::GoTo(195)), None);
+        assert!(session.screen(false).starts_with("Step 195, the last\n0000000000\n"));
+        assert!(session.run(&Command::Until(String::from("moved"), Comparison::Equal, 0)).unwrap().starts_with("No counter moved, only flashes, flashed"));
+    }
+
+    #[test]
+    fn keeps_last_states() {
+        let mut session = start(25, "test", 1, 10);
+        assert_eq!(session.run(&Command::Until(String::from("moved"), Comparison::Equal, 0)), None);
+        assert_eq!(session.at(), 58);
+        assert_eq!(session.run(&Command::Back(20)), Some(String::from("Only the steps from 49 are kept")));
+        assert_eq!(session.at(), 49);
+        assert_eq!(session.run(&Command::GoTo(0)), Some(String::from("Only the steps from 49 are kept")));
+        assert!(session.screen(false).starts_with("Step 49\n"));
+    }
+
+    #[test]
+    fn highlights_changes() {
+        let mut session = start(25, "test2", 1, 1000);
+        assert!(session.screen(false).ends_with("moved: 0, changed: 0"));
+        session.forward();
+        let screen = session.screen(true);
+        assert!(screen.starts_with("\x1b[2J\x1b[HStep 1\n"));
+        assert!(screen.contains("\x1b[7m"));
+        assert!(!session.screen(false).contains('\x1b'));
+        let mut session = start(20, "test", 1, 1000);
+        session.forward();
+        // The image grows, so only the cells that were drawn before count.
+        assert!(session.changed().iter().all(|(row, col)| (1..6).contains(row) && (1..6).contains(col)));
+        assert!(!session.changed().is_empty());
+    }
+}
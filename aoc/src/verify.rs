@@ -0,0 +1,243 @@
+//! Checks the answers of the days against a manifest of expected answers
+//! for the example fixtures and the real inputs.
+
+use std::any::Any;
+use std::time::{Duration, Instant};
+use common::ParseError;
+use crate::days::{self, N_DAYS};
+
+/// The manifest used unless another one is given, relative to the root of
+/// the workspace like the inputs.
+pub const DEFAULT_MANIFEST: &str = "answers";
+
+/// One line of the manifest: the answer of `part` of `day` on `file`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub day: usize,
+    pub part: usize,
+    pub file: String,
+    pub answer: String
+}
+
+impl Expected {
+    /// Whether `file` is the real input of the day rather than an example.
+    pub fn is_real_input(&self) -> bool {
+        self.file == crate::default_input_path(self.day)
+    }
+}
+
+/// Splits the next whitespace separated field off `rest`.
+fn next_field<'a>(rest: &mut &'a str, line: usize, line_text: &str, expected: &str) -> Result<&'a str, ParseError> {
+    let s = rest.trim_start();
+    if s.is_empty() {
+        return Err(ParseError::end_of_line(line, line_text, expected))
+    }
+    let end = s.find(char::is_whitespace).unwrap_or(s.len());
+    *rest = &s[end..];
+    Ok(&s[..end])
+}
+
+/// Parses a manifest with one `<day> <part> <file> <answer>` per line. An
+/// answer spanning several lines is written with `\n` between the lines.
+/// Empty lines and lines starting with `#` are skipped.
+pub fn parse_manifest(s: &str) -> Result<Vec<Expected>, ParseError> {
+    let mut manifest = vec!();
+    for (ind, l) in s.lines().enumerate() {
+        let line = ind + 1;
+        if l.trim().is_empty() || l.starts_with('#') {
+            continue
+        }
+        let mut rest = l;
+        let day_text = next_field(&mut rest, line, l, "a day")?;
+        let day: usize = common::parse::parse_at(line, l, day_text, "a day")?;
+        if day == 0 || day > N_DAYS {
+            return Err(ParseError::at(line, l, day_text, "a day"))
+        }
+        let part_text = next_field(&mut rest, line, l, "a part")?;
+        if part_text != "1" && part_text != "2" {
+            return Err(ParseError::at(line, l, part_text, "a part"))
+        }
+        let file = next_field(&mut rest, line, l, "a file")?;
+        let answer = rest.trim();
+        if answer.is_empty() {
+            return Err(ParseError::end_of_line(line, l, "an answer"))
+        }
+        manifest.push(Expected {
+            day,
+            part: part_text.parse().unwrap(),
+            file: String::from(file),
+            answer: answer.replace("\\n", "\n")
+        })
+    }
+    Ok(manifest)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    /// The answer that was found instead of the expected one.
+    Fail(String),
+    /// The file could not be read or parsed.
+    Error(String)
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub expected: Expected,
+    pub outcome: Outcome,
+    pub time: Duration
+}
+
+/// Solves every entry of `manifest`. Entries for the same file of the same
+/// day that follow each other share one parse of the file.
+pub fn verify(manifest: &[Expected]) -> Vec<Check> {
+    let mut parsed: Option<(usize, &str, Result<Box<dyn Any>, String>)> = None;
+    manifest.iter().map(|expected| {
+        let puzzle = days::get(expected.day).unwrap();
+        let start = Instant::now();
+        if !matches!(&parsed, Some((day, file, _)) if *day == expected.day && *file == expected.file) {
+            let input = crate::read_input(&expected.file).and_then(|input| {
+                puzzle.parse(&input).map_err(|e| e.diagnostic(&input))
+            });
+            parsed = Some((expected.day, &expected.file, input));
+        }
+        let outcome = match &parsed.as_ref().unwrap().2 {
+            Ok(input) => match puzzle.solve(input.as_ref(), expected.part) {
+                Ok(answer) if answer == expected.answer => Outcome::Pass,
+                Ok(answer) => Outcome::Fail(answer),
+                Err(e) => Outcome::Error(e)
+            },
+            Err(e) => Outcome::Error(e.clone())
+        };
+        Check { expected: expected.clone(), outcome, time: start.elapsed() }
+    }).collect()
+}
+
+/// A line by line diff of an expected and a found answer, marking the
+/// expected lines with `-` and the found ones with `+`.
+pub fn diff(expected: &str, found: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let found: Vec<&str> = found.lines().collect();
+    let mut lines = vec!();
+    for ind in 0..expected.len().max(found.len()) {
+        match (expected.get(ind), found.get(ind)) {
+            (Some(e), Some(f)) if e == f => lines.push(format!("  {}", e)),
+            (e, f) => {
+                lines.extend(e.map(|e| format!("- {}", e)));
+                lines.extend(f.map(|f| format!("+ {}", f)));
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+/// A table with a line per check, followed by the diff of every failed
+/// check and the error of every check that could not run.
+pub fn format_report(checks: &[Check]) -> String {
+    let width = checks.iter().map(|c| c.expected.file.len()).max().unwrap_or(0).max(4);
+    let mut report = format!("{:>3}  {:>4}  {:<width$}  {:<6} {:>10}", "Day", "Part", "File", "Result", "Time");
+    for check in checks {
+        let result = match check.outcome {
+            Outcome::Pass => "pass",
+            Outcome::Fail(_) => "FAIL",
+            Outcome::Error(_) => "ERROR"
+        };
+        report += &format!("\n{:>3}  {:>4}  {:<width$}  {:<6} {:>10}",
+            check.expected.day, check.expected.part, check.expected.file, result, format!("{:.2?}", check.time));
+    }
+    for check in checks {
+        let heading = format!("Day {} part {} on {}", check.expected.day, check.expected.part, check.expected.file);
+        match &check.outcome {
+            Outcome::Pass => (),
+            Outcome::Fail(found) => report += &format!("\n\n{}:\n{}", heading, diff(&check.expected.answer, found)),
+            Outcome::Error(e) => report += &format!("\n\n{}:\n{}", heading, e)
+        }
+    }
+    let n_passed = checks.iter().filter(|c| c.outcome == Outcome::Pass).count();
+    report += &format!("\n\n{} of {} passed", n_passed, checks.len());
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn expected(day: usize, part: usize, file: &str, answer: &str) -> Expected {
+        Expected { day, part, file: String::from(file), answer: String::from(answer) }
+    }
+
+    #[test]
+    fn parse() {
+        let manifest = "# day part file answer\n\n 1  1  d01/test  7\n13 2 d13/test #.#\\n.#.\n";
+        assert_eq!(
+            parse_manifest(manifest),
+            Ok(vec!(expected(1, 1, "d01/test", "7"), expected(13, 2, "d13/test", "#.#\n.#.")))
+        );
+        assert_eq!(parse_manifest("26 1 d26/test 1"), Err(ParseError::new(1, 1, "26", "a day")));
+        assert_eq!(parse_manifest("1 3 d01/test 1"), Err(ParseError::new(1, 3, "3", "a part")));
+        assert_eq!(parse_manifest("1 1 d01/test  "), Err(ParseError::new(1, 15, "", "an answer")));
+    }
+
+    #[test]
+    fn default_manifest() {
+        let manifest = parse_manifest(include_str!("../../answers")).unwrap();
+        for day in 1..=N_DAYS {
+            assert!(manifest.iter().any(|e| e.day == day && e.is_real_input()), "No answer for the input of day {}", day);
+        }
+        for expected in &manifest {
+            assert!(std::path::Path::new("..").join(&expected.file).is_file(), "No file {}", expected.file);
+        }
+    }
+
+    /// Files next to the days that are not inputs, or that no day can answer.
+    const NOT_INPUTS: [&str; 5] = [
+        "d14/SAMPLE",
+        "d19/rotate",
+        "d19/test_1_result",
+        "d19/test_2_result",
+        // The sea cucumbers never stop moving.
+        "d25/test2"
+    ];
+
+    #[test]
+    fn every_fixture_in_manifest() {
+        let manifest = parse_manifest(include_str!("../../answers")).unwrap();
+        for day in 1..=N_DAYS {
+            let dir = format!("d{:02}", day);
+            for entry in std::fs::read_dir(format!("../{}", dir)).unwrap() {
+                let entry = entry.unwrap();
+                let name = entry.file_name().into_string().unwrap();
+                let skipped = name.starts_with('.') || ["Cargo.toml", "Cargo.lock", "input", "params"].contains(&name.as_str());
+                if !entry.file_type().unwrap().is_file() || skipped {
+                    continue
+                }
+                let file = format!("{}/{}", dir, name);
+                if !NOT_INPUTS.contains(&file.as_str()) {
+                    assert!(manifest.iter().any(|e| e.file == file), "No answer for {}", file);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn verify_fixtures() {
+        let manifest = vec!(
+            expected(1, 1, "../d01/test", "7"),
+            expected(1, 2, "../d01/test", "6"),
+            expected(2, 1, "../d02/missing", "150")
+        );
+        let checks = verify(&manifest);
+        assert_eq!(checks[0].outcome, Outcome::Pass);
+        assert_eq!(checks[1].outcome, Outcome::Fail(String::from("5")));
+        assert!(matches!(checks[2].outcome, Outcome::Error(_)));
+        let report = format_report(&checks);
+        assert!(report.contains("Day 1 part 2 on ../d01/test:\n- 6\n+ 5"));
+        assert!(report.ends_with("1 of 3 passed"));
+    }
+
+    #[test]
+    fn diff_lines() {
+        assert_eq!(diff("#.#\n...\n#.#", "#.#\n.#.\n#.#"), "  #.#\n- ...\n+ .#.\n  #.#");
+        assert_eq!(diff("1", "1\n2"), "  1\n+ 2");
+    }
+}
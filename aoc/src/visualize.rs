@@ -0,0 +1,60 @@
+//! The days that can be drawn, with their input types erased like in `days`.
+
+use std::any::Any;
+use std::marker::PhantomData;
+use render::{Frame, Palette, Visualize};
+
+pub trait Picture: Sync {
+    fn palette(&self) -> Palette;
+    /// The frames of an input returned by `Puzzle::parse` of the same day.
+    fn frames(&self, input: &dyn Any) -> Vec<Frame>;
+}
+
+struct Day<S>(PhantomData<fn() -> S>);
+
+impl<S: Visualize> Picture for Day<S> where S::Input: 'static {
+    fn palette(&self) -> Palette {
+        S::palette()
+    }
+
+    fn frames(&self, input: &dyn Any) -> Vec<Frame> {
+        S::frames(input.downcast_ref::<S::Input>().expect("Input was parsed by another day"))
+    }
+}
+
+static PICTURES: [(usize, &dyn Picture); 6] = [
+    (5, &Day::<d05::Day05>(PhantomData)),
+    (9, &Day::<d09::Day09>(PhantomData)),
+    (11, &Day::<d11::Day11>(PhantomData)),
+    (13, &Day::<d13::Day13>(PhantomData)),
+    (20, &Day::<d20::Day20>(PhantomData)),
+    (25, &Day::<d25::Day25>(PhantomData)),
+];
+
+/// The days that can be drawn.
+pub fn days() -> Vec<usize> {
+    PICTURES.iter().map(|(day, _)| *day).collect()
+}
+
+/// The picture of a day, if it can be drawn.
+pub fn get(day: usize) -> Option<&'static dyn Picture> {
+    PICTURES.iter().find(|(d, _)| *d == day).map(|(_, picture)| *picture)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn examples_draw() {
+        for (day, test) in [(5, "test"), (9, "test"), (11, "test2"), (13, "test"), (20, "test"), (25, "test")] {
+            let input = crate::read_input(&format!("../d{:02}/{}", day, test)).unwrap();
+            let parsed = days::get(day).unwrap().parse(&input).unwrap();
+            let frames = get(day).unwrap().frames(parsed.as_ref());
+            assert!(!frames.is_empty(), "Day {} has no frames", day);
+            assert!(frames.iter().all(|f| f.n_rows() > 0 && f.n_cols() > 0));
+        }
+        assert!(get(1).is_none());
+    }
+}
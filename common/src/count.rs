@@ -0,0 +1,134 @@
+//! Counts that grow past any fixed width with larger parameters, like the
+//! lanternfish after a thousand days. The days that count such things do it
+//! in any `Count`, and report an overflow instead of wrapping around.
+//!
+//! `u32`, `u64`, `usize` and `u128` overflow at their width; with the `bigint`
+//! feature a `BigUint` never does.
+
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use crate::Solution;
+use crate::params::Params;
+
+#[cfg(feature = "bigint")]
+pub use num_bigint::BigUint;
+
+/// A count that did not fit in its type, or went below zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the count does not fit in its type")
+    }
+}
+
+impl Error for Overflow {}
+
+/// A type to count in, with arithmetic that fails rather than wraps.
+pub trait Count: Clone + Ord + Debug + Display {
+    fn from_usize(n: usize) -> Result<Self, Overflow>;
+    fn plus(&self, other: &Self) -> Result<Self, Overflow>;
+    fn minus(&self, other: &Self) -> Result<Self, Overflow>;
+    fn times(&self, other: &Self) -> Result<Self, Overflow>;
+
+    fn zero() -> Self {
+        Self::from_usize(0).unwrap()
+    }
+
+    fn one() -> Self {
+        Self::from_usize(1).unwrap()
+    }
+}
+
+macro_rules! fixed_width_count {
+    ($($t:ty),*) => {
+        $(
+            impl Count for $t {
+                fn from_usize(n: usize) -> Result<Self, Overflow> {
+                    <$t>::try_from(n).map_err(|_| Overflow)
+                }
+
+                fn plus(&self, other: &Self) -> Result<Self, Overflow> {
+                    self.checked_add(*other).ok_or(Overflow)
+                }
+
+                fn minus(&self, other: &Self) -> Result<Self, Overflow> {
+                    self.checked_sub(*other).ok_or(Overflow)
+                }
+
+                fn times(&self, other: &Self) -> Result<Self, Overflow> {
+                    self.checked_mul(*other).ok_or(Overflow)
+                }
+            }
+        )*
+    };
+}
+
+fixed_width_count!(u32, u64, usize, u128);
+
+#[cfg(feature = "bigint")]
+impl Count for BigUint {
+    fn from_usize(n: usize) -> Result<Self, Overflow> {
+        Ok(Self::from(n))
+    }
+
+    fn plus(&self, other: &Self) -> Result<Self, Overflow> {
+        Ok(self + other)
+    }
+
+    fn minus(&self, other: &Self) -> Result<Self, Overflow> {
+        if other > self {
+            return Err(Overflow)
+        }
+        Ok(self - other)
+    }
+
+    fn times(&self, other: &Self) -> Result<Self, Overflow> {
+        Ok(self * other)
+    }
+}
+
+/// The sum of `counts`, zero if there are none.
+pub fn sum<'a, C: Count + 'a>(counts: impl IntoIterator<Item = &'a C>) -> Result<C, Overflow> {
+    counts.into_iter().try_fold(C::zero(), |acc, count| acc.plus(count))
+}
+
+/// The product of `counts`, one if there are none.
+pub fn product<'a, C: Count + 'a>(counts: impl IntoIterator<Item = &'a C>) -> Result<C, Overflow> {
+    counts.into_iter().try_fold(C::one(), |acc, count| acc.times(count))
+}
+
+/// A day whose answers are counts, which can be taken in any `Count` for
+/// inputs whose answers do not fit in a `usize`. `params` are those of a day
+/// with parameters, and ignored by the others.
+pub trait Counted: Solution {
+    fn part_1_as<C: Count>(input: &Self::Input, params: &Params) -> Result<C, Overflow>;
+    fn part_2_as<C: Count>(input: &Self::Input, params: &Params) -> Result<C, Overflow>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fixed_width() {
+        assert_eq!(u64::MAX.plus(&1), Err(Overflow));
+        assert_eq!(0u64.minus(&1), Err(Overflow));
+        assert_eq!((1u64 << 32).times(&(1 << 32)), Err(Overflow));
+        assert_eq!((1u128 << 32).times(&(1 << 32)), Ok(1 << 64));
+        assert_eq!(sum(&[1u64, 2, 3]), Ok(6));
+        assert_eq!(sum::<u64>(&[]), Ok(0));
+        assert_eq!(product(&[u64::MAX, 2]), Err(Overflow));
+        assert_eq!(product::<u128>(&[]), Ok(1));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn arbitrary_precision() {
+        let big = BigUint::from(u128::MAX);
+        assert_eq!(big.plus(&BigUint::one()).unwrap().to_string(), "340282366920938463463374607431768211456");
+        assert_eq!(product(&[big.clone(), big.clone()]).unwrap(), &big * &big);
+        assert_eq!(BigUint::one().minus(&big), Err(Overflow));
+    }
+}
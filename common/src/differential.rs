@@ -0,0 +1,158 @@
+//! Differential testing: runs an optimized solver next to a simple but
+//! obviously correct reference solver on many small random cases. The first
+//! case where they disagree is shrunk to a minimal one before it is reported.
+
+use std::fmt::{self, Debug, Display};
+use crate::Rng;
+
+/// Upper bound on the shrinking steps, in case shrinking goes in circles.
+const MAX_SHRINKS: usize = 10000;
+
+/// A case where the optimized and the reference solver disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch<T, A> {
+    /// The seed of the run that found the case.
+    pub seed: u64,
+    /// The case as it was generated.
+    pub original: T,
+    /// The smallest case found by shrinking the original.
+    pub case: T,
+    pub optimized: A,
+    pub reference: A
+}
+
+impl<T: Debug, A: Debug> Display for Mismatch<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Solvers disagree with seed {} on", self.seed)?;
+        writeln!(f, "{:#?}", self.case)?;
+        writeln!(f, "optimized: {:?}", self.optimized)?;
+        writeln!(f, "reference: {:?}", self.reference)?;
+        write!(f, "shrunk from {:?}", self.original)
+    }
+}
+
+/// The solvers of a differential test and how to make and shrink cases for them.
+pub struct Differential<T, A> {
+    /// Draws a random small case.
+    pub generate: fn(&mut Rng) -> T,
+    /// Cases that are a step smaller than the given one, most promising first.
+    pub shrink: fn(&T) -> Vec<T>,
+    pub optimized: fn(&T) -> A,
+    pub reference: fn(&T) -> A
+}
+
+impl<T: Clone, A: PartialEq> Differential<T, A> {
+    /// Runs both solvers on `n_cases` cases drawn with `seed` and returns the
+    /// first disagreement, shrunk, if there is one.
+    pub fn find_mismatch(&self, seed: u64, n_cases: usize) -> Option<Mismatch<T, A>> {
+        let mut rng = Rng::new(seed);
+        let original = (0..n_cases).map(|_| (self.generate)(&mut rng)).find(|case| !self.agree(case))?;
+        let case = self.shrunk(&original);
+        Some(Mismatch { seed, optimized: (self.optimized)(&case), reference: (self.reference)(&case), original, case })
+    }
+
+    fn agree(&self, case: &T) -> bool {
+        (self.optimized)(case) == (self.reference)(case)
+    }
+
+    fn shrunk(&self, case: &T) -> T {
+        shrink_failing(case, self.shrink, |case| !self.agree(case))
+    }
+}
+
+/// Greedily replaces `case` by the first smaller one that still fails, until
+/// there is none.
+pub(crate) fn shrink_failing<T: Clone>(case: &T, shrink: fn(&T) -> Vec<T>, fails: impl Fn(&T) -> bool) -> T {
+    let mut case = case.clone();
+    for _ in 0..MAX_SHRINKS {
+        match shrink(&case).into_iter().find(|smaller| fails(smaller)) {
+            Some(smaller) => case = smaller,
+            None => break
+        }
+    }
+    case
+}
+
+impl<T: Clone + Debug, A: PartialEq + Debug> Differential<T, A> {
+    /// Like `find_mismatch` but panics with a report of the disagreement.
+    pub fn check(&self, seed: u64, n_cases: usize) {
+        if let Some(mismatch) = self.find_mismatch(seed, n_cases) {
+            panic!("{}", mismatch)
+        }
+    }
+}
+
+/// The lists left by removing one item from `items`, keeping at least `min_len`.
+pub fn remove_each<T: Clone>(items: &[T], min_len: usize) -> Vec<Vec<T>> {
+    if items.len() <= min_len {
+        return vec!()
+    }
+    (0..items.len()).map(|ind| {
+        let mut fewer = items.to_vec();
+        fewer.remove(ind);
+        fewer
+    }).collect()
+}
+
+/// The lists made by replacing one item of `items` with one of its shrinks.
+pub fn shrink_each<T: Clone>(items: &[T], shrink: impl Fn(&T) -> Vec<T>) -> Vec<Vec<T>> {
+    let mut shrunk = vec!();
+    for ind in 0..items.len() {
+        for smaller in shrink(&items[ind]) {
+            let mut items = items.to_vec();
+            items[ind] = smaller;
+            shrunk.push(items)
+        }
+    }
+    shrunk
+}
+
+/// Numbers between `min` and `n`, from `min` up, halving the distance to `n`.
+pub fn smaller(n: usize, min: usize) -> Vec<usize> {
+    let mut numbers = vec!();
+    let mut step = n.saturating_sub(min);
+    while step > 0 {
+        numbers.push(n - step);
+        step /= 2;
+    }
+    numbers
+}
+
+/// Like `smaller`, for numbers between 0 and `n` of either sign.
+pub fn towards_zero(n: isize) -> Vec<isize> {
+    smaller(n.unsigned_abs(), 0).into_iter().map(|m| m as isize * n.signum()).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn shrinks_to_minimal_case() {
+        let differential = Differential {
+            generate: |rng| (0..10).map(|_| rng.below(100)).collect::<Vec<_>>(),
+            shrink: |v| {
+                let mut shrunk = remove_each(v, 0);
+                shrunk.extend(shrink_each(v, |n| smaller(*n, 0)));
+                shrunk
+            },
+            optimized: |v| v.iter().filter(|n| **n < 50).sum::<usize>(),
+            reference: |v| v.iter().sum()
+        };
+        let mismatch = differential.find_mismatch(1, 100).unwrap();
+        assert_eq!(mismatch.case, vec!(50));
+        assert_eq!((mismatch.optimized, mismatch.reference), (0, 50));
+        assert!(mismatch.to_string().starts_with("Solvers disagree with seed 1 on"));
+        let same = Differential { reference: |v: &Vec<usize>| v.iter().filter(|n| **n < 50).sum(), ..differential };
+        assert_eq!(same.find_mismatch(1, 100), None);
+    }
+
+    #[test]
+    fn smaller_numbers() {
+        assert_eq!(smaller(10, 2), vec!(2, 6, 8, 9));
+        assert_eq!(smaller(2, 2), Vec::<usize>::new());
+        assert_eq!(towards_zero(-10), vec!(0, -5, -8, -9));
+        assert_eq!(remove_each(&[1, 2], 1), vec!(vec!(2), vec!(1)));
+        assert_eq!(remove_each(&[1], 1), Vec::<Vec<usize>>::new());
+    }
+}
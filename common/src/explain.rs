@@ -0,0 +1,183 @@
+//! Explanations of answers: the facts they follow from, like the board that
+//! wins or the path taken, so that a number does not have to be taken on
+//! trust. An explanation prints as indented text or as JSON.
+
+use crate::Solution;
+
+/// A fact about how an answer came about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fact {
+    Number(i128),
+    Text(String),
+    List(Vec<Fact>),
+    /// Named facts, printed in the order they are given.
+    Record(Vec<(String, Fact)>)
+}
+
+impl Fact {
+    pub fn list<T: Into<Fact>>(items: impl IntoIterator<Item = T>) -> Self {
+        Fact::List(items.into_iter().map(|item| item.into()).collect())
+    }
+
+    pub fn record<'a>(fields: impl IntoIterator<Item = (&'a str, Fact)>) -> Self {
+        Fact::Record(fields.into_iter().map(|(name, fact)| (String::from(name), fact)).collect())
+    }
+
+    /// A number or a single line of text.
+    fn is_scalar(&self) -> bool {
+        match self {
+            Fact::Number(_) => true,
+            Fact::Text(text) => !text.contains('\n'),
+            _ => false
+        }
+    }
+
+    /// Scalars, and lists of scalars, fit on the line of their name.
+    fn inline(&self) -> Option<String> {
+        match self {
+            Fact::Number(n) => Some(n.to_string()),
+            Fact::Text(text) if self.is_scalar() => Some(text.clone()),
+            Fact::List(items) if items.iter().all(|item| item.is_scalar()) => {
+                Some(format!("[{}]", items.iter().filter_map(|item| item.inline()).collect::<Vec<_>>().join(", ")))
+            },
+            _ => None
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        if let Some(line) = self.inline() {
+            return vec!(line)
+        }
+        let indented = |lines: Vec<String>| lines.into_iter().map(|line| format!("  {}", line)).collect::<Vec<_>>();
+        match self {
+            Fact::Text(text) => text.lines().map(String::from).collect(),
+            Fact::List(items) => items.iter().flat_map(|item| {
+                let mut lines = item.lines();
+                let rest = indented(lines.split_off(1.min(lines.len())));
+                std::iter::once(format!("- {}", lines.concat())).chain(rest)
+            }).collect(),
+            Fact::Record(fields) => fields.iter().flat_map(|(name, fact)| match fact.inline() {
+                Some(line) => vec!(format!("{}: {}", name, line)),
+                None => std::iter::once(format!("{}:", name)).chain(indented(fact.lines())).collect()
+            }).collect(),
+            Fact::Number(_) => unreachable!()
+        }
+    }
+
+    /// The fact as text, one line for each fact that does not fit on the
+    /// line of its name, indented below it.
+    pub fn to_text(&self) -> String {
+        self.lines().join("\n")
+    }
+
+    /// The fact as JSON on a single line.
+    pub fn to_json(&self) -> String {
+        match self {
+            Fact::Number(n) => n.to_string(),
+            Fact::Text(text) => json_string(text),
+            Fact::List(items) => format!("[{}]", items.iter().map(|item| item.to_json()).collect::<Vec<_>>().join(", ")),
+            Fact::Record(fields) => format!("{{{}}}", fields.iter()
+                .map(|(name, fact)| format!("{}: {}", json_string(name), fact.to_json()))
+                .collect::<Vec<_>>().join(", "))
+        }
+    }
+}
+
+impl From<usize> for Fact {
+    fn from(n: usize) -> Self {
+        Fact::Number(n as i128)
+    }
+}
+
+impl From<isize> for Fact {
+    fn from(n: isize) -> Self {
+        Fact::Number(n as i128)
+    }
+}
+
+impl From<char> for Fact {
+    fn from(c: char) -> Self {
+        Fact::Text(c.to_string())
+    }
+}
+
+impl From<&str> for Fact {
+    fn from(text: &str) -> Self {
+        Fact::Text(String::from(text))
+    }
+}
+
+impl From<String> for Fact {
+    fn from(text: String) -> Self {
+        Fact::Text(text)
+    }
+}
+
+impl<T: Into<Fact>> From<Vec<T>> for Fact {
+    fn from(items: Vec<T>) -> Self {
+        Fact::list(items)
+    }
+}
+
+/// `s` as a JSON string, with quotes.
+pub fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json += "\\\"",
+            '\\' => json += "\\\\",
+            '\n' => json += "\\n",
+            c if (c as u32) < 0x20 => json += &format!("\\u{:04x}", c as u32),
+            c => json.push(c)
+        }
+    }
+    json + "\""
+}
+
+/// Tells how the answers of a day come about, from the same work that
+/// finds them.
+pub trait Explain: Solution {
+    fn explain_1(input: &Self::Input) -> Fact;
+    fn explain_2(input: &Self::Input) -> Fact;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn example() -> Fact {
+        Fact::record([
+            ("board", Fact::from(3usize)),
+            ("draw", Fact::from("24")),
+            ("rows", Fact::list([vec!(14usize, 21), vec!(10, 16)])),
+            ("picture", Fact::from("#.\n.#")),
+            ("moves", Fact::list([Fact::record([("pod", Fact::from('A')), ("to", Fact::from(vec!(0isize, -1)))])]))
+        ])
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(example().to_text(), "\
+board: 3
+draw: 24
+rows:
+  - [14, 21]
+  - [10, 16]
+picture:
+  #.
+  .#
+moves:
+  - pod: A
+    to: [0, -1]");
+        assert_eq!(Fact::from(vec!(1usize, 2)).to_text(), "[1, 2]");
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            example().to_json(),
+            "{\"board\": 3, \"draw\": \"24\", \"rows\": [[14, 21], [10, 16]], \"picture\": \"#.\\n.#\", \"moves\": [{\"pod\": \"A\", \"to\": [0, -1]}]}"
+        );
+        assert_eq!(json_string("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+    }
+}
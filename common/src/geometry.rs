@@ -0,0 +1,360 @@
+//! Points and vectors on a plane and in space, the rotations of a cube,
+//! rigid transforms and axis-aligned boxes, for the days that work with
+//! coordinates.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A point on a plane, or the vector to it from the origin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T
+}
+
+/// A point in space, or the vector to it from the origin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// The point with `f` applied to each coordinate.
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Point2<U> {
+        Point2::new(f(self.x), f(self.y))
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// The point with `f` applied to each coordinate.
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Point3<U> {
+        Point3::new(f(self.x), f(self.y), f(self.z))
+    }
+}
+
+macro_rules! vector_ops {
+    ($point:ident, $($c:ident),*) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($c: self.$c + other.$c),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($c: self.$c - other.$c),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($c: -self.$c),* }
+            }
+        }
+
+        /// Scales the vector.
+        impl<T: Mul<Output = T> + Copy> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, factor: T) -> Self {
+                Self { $($c: self.$c * factor),* }
+            }
+        }
+
+        impl $point<isize> {
+            /// The vector with each coordinate turned into -1, 0 or 1, a
+            /// single step in its direction along a grid.
+            pub fn signum(self) -> Self {
+                Self { $($c: self.$c.signum()),* }
+            }
+
+            /// The length of the vector along a grid.
+            pub fn manhattan(self) -> usize {
+                0 $(+ self.$c.unsigned_abs())*
+            }
+
+            /// The distance to `other` along a grid.
+            pub fn manhattan_distance(self, other: Self) -> usize {
+                (self - other).manhattan()
+            }
+        }
+    };
+}
+
+vector_ops!(Point2, x, y);
+vector_ops!(Point3, x, y, z);
+
+impl Point3<isize> {
+    pub fn cross(self, other: Self) -> Self {
+        Point3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x
+        )
+    }
+}
+
+/// A rotation of space by a matrix of integers, which turns a point like
+/// the product of the matrix with the point as a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation([[isize; 3]; 3]);
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    /// How many rotations turn a cube onto itself.
+    pub const N_CUBE: usize = 24;
+
+    /// One of the rotations that turn a cube onto itself, 0 being none.
+    /// Every four in a row share where they turn the z axis.
+    pub fn cube(n: usize) -> Rotation {
+        let turn = |pt: Point3<isize>| {
+            let Point3 { x, y, z } = pt;
+            match n {
+                0 => (x, y, z),
+                1 => (-y, x, z),
+                2 => (-x, -y, z),
+                3 => (y, -x, z),
+
+                4 => (-z, y, x),
+                5 => (-y, -z, x),
+                6 => (z, -y, x),
+                7 => (y, z, x),
+
+                8 => (-x, y, -z),
+                9 => (-y, -x, -z),
+                10 => (x, -y, -z),
+                11 => (y, x, -z),
+
+                12 => (z, y, -x),
+                13 => (-y, z, -x),
+                14 => (-z, -y, -x),
+                15 => (y, -z, -x),
+
+                16 => (x, z, -y),
+                17 => (-z, x, -y),
+                18 => (-x, -z, -y),
+                19 => (z, -x, -y),
+
+                20 => (x, -z, y),
+                21 => (z, x, y),
+                22 => (-x, z, y),
+                23 => (-z, -x, y),
+                _ => panic!("Invalid turn")
+            }
+        };
+        // The columns of the matrix are where the axes are turned.
+        let axes = [Point3::new(1, 0, 0), Point3::new(0, 1, 0), Point3::new(0, 0, 1)].map(turn);
+        Rotation(std::array::from_fn(|row| std::array::from_fn(|col| {
+            let (x, y, z) = axes[col];
+            [x, y, z][row]
+        })))
+    }
+
+    pub fn apply(&self, pt: Point3<isize>) -> Point3<isize> {
+        let row = |r: usize| self.0[r][0] * pt.x + self.0[r][1] * pt.y + self.0[r][2] * pt.z;
+        Point3::new(row(0), row(1), row(2))
+    }
+
+    /// This rotation followed by `next`.
+    pub fn then(&self, next: &Rotation) -> Rotation {
+        Rotation(std::array::from_fn(|row| std::array::from_fn(|col| {
+            (0..3).map(|k| next.0[row][k] * self.0[k][col]).sum()
+        })))
+    }
+
+    /// The rotation back, the transpose of the matrix.
+    pub fn inverse(&self) -> Rotation {
+        Rotation(std::array::from_fn(|row| std::array::from_fn(|col| self.0[col][row])))
+    }
+}
+
+/// A rotation followed by a translation, which moves points without
+/// changing the distances between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Transform {
+    pub rotation: Rotation,
+    pub translation: Point3<isize>
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform { rotation: Rotation::IDENTITY, translation: Point3::new(0, 0, 0) };
+
+    pub fn new(rotation: Rotation, translation: Point3<isize>) -> Self {
+        Self { rotation, translation }
+    }
+
+    pub fn apply(&self, pt: Point3<isize>) -> Point3<isize> {
+        self.rotation.apply(pt) + self.translation
+    }
+
+    /// This transform followed by `next`.
+    pub fn then(&self, next: &Transform) -> Transform {
+        Transform::new(self.rotation.then(&next.rotation), next.apply(self.translation))
+    }
+
+    /// The transform that moves every point back.
+    pub fn inverse(&self) -> Transform {
+        let rotation = self.rotation.inverse();
+        Transform::new(rotation, -rotation.apply(self.translation))
+    }
+}
+
+/// All points from `min` to `max` in space, both included, in a box with
+/// its sides along the axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Box3 {
+    pub min: Point3<isize>,
+    pub max: Point3<isize>
+}
+
+impl Box3 {
+    pub const fn new(min: Point3<isize>, max: Point3<isize>) -> Self {
+        Self { min, max }
+    }
+
+    pub fn contains(&self, other: &Box3) -> bool {
+        other.min.x >= self.min.x && other.max.x <= self.max.x &&
+        other.min.y >= self.min.y && other.max.y <= self.max.y &&
+        other.min.z >= self.min.z && other.max.z <= self.max.z
+    }
+
+    pub fn intersects(&self, other: &Box3) -> bool {
+        self.min.x <= other.max.x && other.min.x <= self.max.x &&
+        self.min.y <= other.max.y && other.min.y <= self.max.y &&
+        self.min.z <= other.max.z && other.min.z <= self.max.z
+    }
+
+    /// The points in both boxes, if there are any.
+    pub fn intersection(&self, other: &Box3) -> Option<Box3> {
+        let min = Point3::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y), self.min.z.max(other.min.z));
+        let max = Point3::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y), self.max.z.min(other.max.z));
+        self.intersects(other).then_some(Box3::new(min, max))
+    }
+
+    /// The points of this box that are not in `other`, as boxes that do
+    /// not overlap.
+    pub fn difference(&self, other: &Box3) -> Vec<Box3> {
+        if other.contains(self) {
+            vec!()
+        } else if !other.intersects(self) {
+            vec!(*self)
+        } else {
+            self.split(other).into_iter().filter(|part| !other.contains(part)).collect()
+        }
+    }
+
+    /// This box cut into pieces by the planes of the sides of `wedge`.
+    /// Every piece is either inside `wedge` or outside it.
+    pub fn split(&self, wedge: &Box3) -> Vec<Box3> {
+        fn split_segments(omin: isize, omax: isize, wmin: isize, wmax: isize) -> Vec<(isize, isize)> {
+            if wmin > omax || wmax < omin {
+                vec!((omin, omax))
+            } else {
+                let wmin = wmin.max(omin);
+                let wmax = wmax.min(omax);
+                let mut segs = vec!();
+                if omin < wmin {
+                    segs.push((omin, wmin - 1))
+                }
+                segs.push((wmin, wmax));
+                if omax > wmax {
+                    segs.push((wmax + 1, omax))
+                }
+                segs
+            }
+        }
+        let x_segments = split_segments(self.min.x, self.max.x, wedge.min.x, wedge.max.x);
+        let y_segments = split_segments(self.min.y, self.max.y, wedge.min.y, wedge.max.y);
+        let z_segments = split_segments(self.min.z, self.max.z, wedge.min.z, wedge.max.z);
+        let mut parts = vec!();
+        for x_seg in &x_segments {
+            for y_seg in &y_segments {
+                for z_seg in &z_segments {
+                    parts.push(Box3::new(Point3::new(x_seg.0, y_seg.0, z_seg.0), Point3::new(x_seg.1, y_seg.1, z_seg.1)))
+                }
+            }
+        }
+        parts
+    }
+
+    /// The number of points in the box.
+    pub fn volume(&self) -> usize {
+        ((self.max.x - self.min.x + 1) *
+         (self.max.y - self.min.y + 1) *
+         (self.max.z - self.min.z + 1)) as usize
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn vectors() {
+        let (a, b) = (Point3::new(1, -2, 3), Point3::new(4, 5, -6));
+        assert_eq!(a + b, Point3::new(5, 3, -3));
+        assert_eq!(a - b, Point3::new(-3, -7, 9));
+        assert_eq!(-a * 2, Point3::new(-2, 4, -6));
+        assert_eq!(a.manhattan_distance(b), 19);
+        assert_eq!(a.cross(b), Point3::new(-3, 18, 13));
+        assert_eq!((Point2::new(3, 0) - Point2::new(0, 3)).signum(), Point2::new(1, -1));
+        assert_eq!(Point2::new(2usize, 7).map(|c| c as isize), Point2::new(2isize, 7));
+    }
+
+    #[test]
+    fn rotations() {
+        let pt = Point3::new(1, 2, 3);
+        assert_eq!(Rotation::cube(0), Rotation::IDENTITY);
+        assert_eq!(Rotation::cube(1).apply(pt), Point3::new(-2, 1, 3));
+        assert_eq!(Rotation::cube(21).apply(pt), Point3::new(3, 1, 2));
+        for n in 0..Rotation::N_CUBE {
+            let rotation = Rotation::cube(n);
+            assert_eq!(rotation.then(&rotation.inverse()), Rotation::IDENTITY);
+            assert_eq!(rotation.then(&Rotation::cube(1)).apply(pt), Rotation::cube(1).apply(rotation.apply(pt)));
+        }
+    }
+
+    #[test]
+    fn transforms() {
+        let first = Transform::new(Rotation::cube(5), Point3::new(68, -1246, -43));
+        let then = Transform::new(Rotation::cube(14), Point3::new(-20, 7, 1105));
+        let pt = Point3::new(-618, -824, -621);
+        assert_eq!(first.then(&then).apply(pt), then.apply(first.apply(pt)));
+        assert_eq!(first.inverse().apply(first.apply(pt)), pt);
+        assert_eq!(first.then(&first.inverse()), Transform::IDENTITY);
+        assert_eq!(Transform::IDENTITY.apply(pt), pt);
+    }
+
+    #[test]
+    fn boxes() {
+        let outer = Box3::new(Point3::new(0, 0, 0), Point3::new(2, 2, 2));
+        let inner = Box3::new(Point3::new(1, 1, 1), Point3::new(1, 1, 3));
+        assert_eq!(outer.volume(), 27);
+        assert!(outer.intersects(&inner) && !outer.contains(&inner));
+        assert_eq!(outer.intersection(&inner), Some(Box3::new(Point3::new(1, 1, 1), Point3::new(1, 1, 2))));
+        assert_eq!(outer.difference(&inner).iter().map(|part| part.volume()).sum::<usize>(), 25);
+        assert_eq!(inner.difference(&outer), vec!(Box3::new(Point3::new(1, 1, 3), Point3::new(1, 1, 3))));
+        let apart = Box3::new(Point3::new(3, 0, 0), Point3::new(3, 0, 0));
+        assert_eq!(outer.intersection(&apart), None);
+        assert_eq!(outer.difference(&apart), vec!(outer));
+    }
+}
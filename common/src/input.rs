@@ -0,0 +1,205 @@
+//! Splits a puzzle input into the pieces the days parse: lines, comma
+//! separated lists, grids of characters and sections between blank lines.
+//!
+//! Every helper normalizes the input first, so an input saved with CRLF
+//! line endings, a byte order mark or trailing newlines parses just like
+//! the original. Line numbers in errors are those of the input as given.
+
+use std::borrow::Cow;
+use std::str::FromStr;
+use crate::parse::{parse_at, ParseError};
+
+/// `input` without a byte order mark and with CRLF and CR line endings
+/// turned into LF, so that its lines are those the parsers count.
+pub fn unify_line_endings(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    if input.contains('\r') {
+        Cow::Owned(input.replace("\r\n", "\n").replace('\r', "\n"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+/// `input` with unified line endings and without blank lines or a newline
+/// at the end. No line moves.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let mut normalized = unify_line_endings(input);
+    let mut end = 0;
+    let mut start = 0;
+    for line in normalized.split('\n') {
+        if !line.trim().is_empty() {
+            end = start + line.len()
+        }
+        start += line.len() + 1;
+    }
+    match &mut normalized {
+        Cow::Borrowed(s) => *s = &s[..end],
+        Cow::Owned(s) => s.truncate(end)
+    }
+    normalized
+}
+
+/// Parses every line of `input` with `parse`, which is given the number of
+/// the line, counting from 1, and its text. An empty input is a single
+/// empty line.
+pub fn parse_lines<T>(input: &str, mut parse: impl FnMut(usize, &str) -> Result<T, ParseError>) -> Result<Vec<T>, ParseError> {
+    normalize(input).split('\n').enumerate().map(|(ind, l)| parse(ind + 1, l)).collect()
+}
+
+/// Parses an input that is a single line.
+pub fn parse_one_line<T>(input: &str, parse: impl FnOnce(&str) -> Result<T, ParseError>) -> Result<T, ParseError> {
+    let input = normalize(input);
+    let mut lines = input.split('\n');
+    let value = parse(lines.next().unwrap())?;
+    match lines.next() {
+        Some(extra) => Err(ParseError::at(2, extra, extra, "end of input")),
+        None => Ok(value)
+    }
+}
+
+/// Parses the items of `text`, a slice of `line_text`, the text of line
+/// `line`, between each `separator`.
+pub fn parse_list<T: FromStr>(line: usize, line_text: &str, text: &str, separator: &str, expected: &str) -> Result<Vec<T>, ParseError> {
+    text.split(separator).map(|item| parse_at(line, line_text, item, expected)).collect()
+}
+
+/// Error for an input that ends before `expected` was found, pointing at
+/// the end of its last line.
+pub fn end_of_input(input: &str, expected: &str) -> ParseError {
+    let input = normalize(input);
+    let n_lines = input.split('\n').count();
+    ParseError::end_of_line(n_lines, input.rsplit('\n').next().unwrap(), expected)
+}
+
+/// Lines of the input between blank lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// Line of the input the section starts on, counting from 1.
+    pub first_line: usize,
+    pub text: String
+}
+
+impl Section {
+    /// Parses the section with `parse`, which counts lines from the start of
+    /// the section. Errors are moved to their line in the whole input.
+    pub fn parse<T>(&self, parse: impl FnOnce(&str) -> Result<T, ParseError>) -> Result<T, ParseError> {
+        parse(&self.text).map_err(|e| e.offset_lines(self.first_line - 1))
+    }
+}
+
+/// The sections of `input`, separated by one or more blank lines.
+pub fn sections(input: &str) -> Vec<Section> {
+    let mut sections: Vec<Section> = vec!();
+    let mut in_section = false;
+    for (ind, l) in normalize(input).split('\n').enumerate() {
+        if l.trim().is_empty() {
+            in_section = false
+        } else if in_section {
+            let section = sections.last_mut().unwrap();
+            section.text.push('\n');
+            section.text.push_str(l)
+        } else {
+            sections.push(Section { first_line: ind + 1, text: String::from(l) });
+            in_section = true
+        }
+    }
+    sections
+}
+
+/// Error for a section after the last one there should be.
+pub fn unexpected_section(section: &Section) -> ParseError {
+    ParseError::new(section.first_line, 1, section.text.split('\n').next().unwrap(), "end of input")
+}
+
+/// Parses a rectangle of characters from `allowed`, one row per line.
+pub fn parse_char_rows(s: &str, allowed: &str, expected: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let mut rows: Vec<Vec<char>> = vec!();
+    for (ind, l) in normalize(s).lines().enumerate() {
+        let row: Vec<char> = l.chars().collect();
+        if let Some(col) = row.iter().position(|c| !allowed.contains(*c)) {
+            return Err(ParseError::new(ind + 1, col + 1, &row[col].to_string(), expected))
+        }
+        match rows.first() {
+            Some(first) if first.len() != row.len() => {
+                let expected = format!("a row of {} characters", first.len());
+                return Err(ParseError::at(ind + 1, l, l, &expected))
+            },
+            None if row.is_empty() => return Err(ParseError::end_of_line(ind + 1, l, expected)),
+            _ => rows.push(row)
+        }
+    }
+    if rows.is_empty() {
+        return Err(ParseError::new(1, 1, "", expected))
+    }
+    Ok(rows)
+}
+
+/// Parses a rectangle of single digits, one row per line.
+pub fn parse_digit_rows(s: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    let rows = parse_char_rows(s, "0123456789", "a digit")?;
+    Ok(rows.iter().map(|row| row.iter().map(|c| c.to_digit(10).unwrap()).collect()).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn line_endings() {
+        assert_eq!(normalize("1\n2"), "1\n2");
+        assert!(matches!(normalize("1\n2"), Cow::Borrowed(_)));
+        assert_eq!(normalize("1\r\n2\r\n"), "1\n2");
+        assert_eq!(normalize("1\r2\r"), "1\n2");
+        assert_eq!(normalize("\u{feff}1\n2"), "1\n2");
+        assert_eq!(normalize("1\n2\n\n \n\t\n"), "1\n2");
+        assert_eq!(normalize("\n\n1"), "\n\n1");
+        assert_eq!(normalize("  #\n"), "  #");
+        assert_eq!(normalize(""), "");
+        assert_eq!(normalize("\n\r\n"), "");
+    }
+
+    #[test]
+    fn lines() {
+        let parse = |input| parse_lines(input, |line, l| parse_at::<usize>(line, l, l, "a number"));
+        assert_eq!(parse("1\n2"), Ok(vec!(1, 2)));
+        assert_eq!(parse("1\r\n2\r\n\r\n"), Ok(vec!(1, 2)));
+        assert_eq!(parse("\u{feff}1\n2\n"), Ok(vec!(1, 2)));
+        assert_eq!(parse("1\n\n2").unwrap_err(), ParseError::new(2, 1, "", "a number"));
+        assert_eq!(parse("").unwrap_err(), ParseError::new(1, 1, "", "a number"));
+    }
+
+    #[test]
+    fn one_line_lists() {
+        let parse = |input| parse_one_line(input, |l| parse_list::<usize>(1, l, l, ",", "a number"));
+        assert_eq!(parse("3,4,3\n"), Ok(vec!(3, 4, 3)));
+        assert_eq!(parse("3,4,3\r\n"), Ok(vec!(3, 4, 3)));
+        assert_eq!(parse("3,x,3").unwrap_err(), ParseError::new(1, 3, "x", "a number"));
+        assert_eq!(parse("3,4\n5").unwrap_err(), ParseError::new(2, 1, "5", "end of input"));
+    }
+
+    #[test]
+    fn blank_line_sections() {
+        let input = "a\nb\n\n\nc\r\n \r\nd\ne\n\n";
+        let found = sections(input);
+        assert_eq!(found, vec!(
+            Section { first_line: 1, text: String::from("a\nb") },
+            Section { first_line: 5, text: String::from("c") },
+            Section { first_line: 7, text: String::from("d\ne") }
+        ));
+        let err = found[2].parse(|text| parse_lines(text, |line, l| parse_at::<usize>(line, l, l, "a number"))).unwrap_err();
+        assert_eq!(err, ParseError::new(7, 1, "d", "a number"));
+        assert_eq!(end_of_input(input, "more"), ParseError::new(8, 2, "", "more"));
+        assert_eq!(end_of_input("", "more"), ParseError::new(1, 1, "", "more"));
+        assert!(sections("\n\n").is_empty());
+        assert_eq!(unexpected_section(&found[1]), ParseError::new(5, 1, "c", "end of input"));
+    }
+
+    #[test]
+    fn digit_rows() {
+        assert_eq!(parse_digit_rows("12\n34"), Ok(vec!(vec!(1, 2), vec!(3, 4))));
+        assert_eq!(parse_digit_rows("12\n3x").unwrap_err(), ParseError::new(2, 2, "x", "a digit"));
+        assert_eq!(parse_digit_rows("12\n345").unwrap_err(), ParseError::new(2, 1, "345", "a row of 2 characters"));
+        assert_eq!(parse_digit_rows("").unwrap_err(), ParseError::new(1, 1, "", "a digit"));
+        assert_eq!(parse_digit_rows("\u{feff}12\r\n34\r\n\r\n"), Ok(vec!(vec!(1, 2), vec!(3, 4))));
+    }
+}
@@ -0,0 +1,157 @@
+//! Code shared by the solutions of all days.
+
+pub mod count;
+pub mod differential;
+pub mod explain;
+pub mod geometry;
+pub mod input;
+pub mod params;
+pub mod parse;
+pub mod progress;
+pub mod property;
+pub mod rng;
+pub mod simulate;
+
+use std::fmt::{self, Display};
+
+pub use parse::ParseError;
+pub use rng::Rng;
+
+/// The solution of one day: how to parse the puzzle input and how to
+/// answer each of its two parts from the parsed input.
+pub trait Solution {
+    type Input;
+    type Output1: Answer;
+    type Output2: Answer;
+
+    /// Bumped whenever a change could give other answers, so that answers
+    /// cached from an older version are not used.
+    const VERSION: u32 = 1;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_1(input: &Self::Input) -> Self::Output1;
+    fn part_2(input: &Self::Input) -> Self::Output2;
+}
+
+/// What a part gives: its answer as printed, or why it has none, like a
+/// count that does not fit in its type.
+pub trait Answer {
+    fn answer(&self) -> Result<String, String>;
+}
+
+macro_rules! display_answer {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn answer(&self) -> Result<String, String> {
+                    Ok(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+display_answer!(usize, isize, u32, u64, String, NoAnswer);
+
+impl<T: Display> Answer for Result<T, count::Overflow> {
+    fn answer(&self) -> Result<String, String> {
+        self.as_ref().map(|answer| answer.to_string()).map_err(|e| e.to_string())
+    }
+}
+
+/// A synthetic puzzle input, with the answers of the parts that are known
+/// from how it was built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>
+}
+
+impl Generated {
+    pub fn new(input: String) -> Self {
+        Self { input, part_1: None, part_2: None }
+    }
+
+    pub fn with_answers(input: String, part_1: impl Display, part_2: impl Display) -> Self {
+        Self { input, part_1: Some(part_1.to_string()), part_2: Some(part_2.to_string()) }
+    }
+}
+
+/// Builds valid inputs of any size for a day, to test it on more than the
+/// single real input and to find out how it scales.
+pub trait Generate: Solution {
+    /// The `size` that gives an input about as large as the real one.
+    const INPUT_SIZE: usize;
+
+    /// An input of `size`, counted in a unit that suits the day, like lines
+    /// or the side of a grid. Days with a fixed input size ignore it.
+    fn generate(rng: &mut Rng, size: usize) -> Generated;
+}
+
+/// Answer of a part that has no puzzle, i.e. the second part of the last day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoAnswer;
+
+impl Display for NoAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no puzzle")
+    }
+}
+
+/// Formats an answer the way all binaries print it. Answers spanning
+/// several lines, like the letters of day 13, start on a line of their own.
+pub fn format_answer(part: usize, answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("Answer part {}:\n{}", part, answer)
+    } else {
+        format!("Answer part {}: {}", part, answer)
+    }
+}
+
+/// Parses `input` and prints the answers of both parts. Exits with an
+/// error code after printing where the input is broken if it cannot be
+/// parsed, or why a part has no answer.
+pub fn run<S: Solution>(input: &str) {
+    let input = match S::parse(input) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e.diagnostic(input));
+            std::process::exit(1)
+        }
+    };
+    let mut ok = true;
+    for (part, answer) in [(1, S::part_1(&input).answer()), (2, S::part_2(&input).answer())] {
+        match answer {
+            Ok(answer) => println!("{}", format_answer(part, &answer)),
+            Err(e) => {
+                eprintln!("Part {}: {}", part, e);
+                ok = false
+            }
+        }
+    }
+    if !ok {
+        std::process::exit(1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn single_line_answer() {
+        assert_eq!(format_answer(1, "42"), "Answer part 1: 42")
+    }
+
+    #[test]
+    fn multi_line_answer() {
+        assert_eq!(format_answer(2, "#.\n.#"), "Answer part 2:\n#.\n.#")
+    }
+
+    #[test]
+    fn overflow_answer() {
+        assert_eq!(Ok::<usize, count::Overflow>(42).answer(), Ok(String::from("42")));
+        assert_eq!(Err::<usize, _>(count::Overflow).answer(), Err(String::from("the count does not fit in its type")));
+    }
+}
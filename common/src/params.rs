@@ -0,0 +1,92 @@
+//! Numbers of a puzzle that are not in its input, like how many days the
+//! lanternfish grow for. They keep the values of the puzzle unless they are
+//! set by `name = value` lines in a file or on the command line.
+
+use crate::{ParseError, Solution};
+use crate::input::parse_lines;
+use crate::parse::{parse_at, split_at};
+
+/// Parameters that are set, in the order they were set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(Vec<(String, usize)>);
+
+impl Params {
+    /// Reads `name = value` lines. Empty lines and lines starting with `#`
+    /// are skipped.
+    pub fn parse(s: &str) -> Result<Params, ParseError> {
+        let lines = parse_lines(s, |n_line, line| {
+            if line.trim().is_empty() || line.starts_with('#') {
+                return Ok(None)
+            }
+            let (name, value) = split_at(n_line, line, line, "=")?;
+            Ok(Some((String::from(name.trim()), parse_at(n_line, line, value.trim(), "a number")?)))
+        })?;
+        Ok(Params(lines.into_iter().flatten().collect()))
+    }
+
+    /// Sets a parameter from a `name=value` argument.
+    pub fn set(&mut self, assignment: &str) -> Result<(), String> {
+        let mut set = Params::parse(assignment).map_err(|_| format!("Invalid parameter: {}", assignment))?;
+        self.0.append(&mut set.0);
+        Ok(())
+    }
+
+    /// Sets all parameters of `other`, overriding those set here.
+    pub fn extend(&mut self, other: Params) {
+        self.0.extend(other.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The value a parameter was last set to, if it was set.
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.0.iter().rev().find(|(n, _)| n == name).map(|(_, value)| *value)
+    }
+
+    /// Fails for the first parameter that is set but not one of `names`.
+    pub fn check(&self, names: &[&str]) -> Result<(), String> {
+        match self.0.iter().find(|(name, _)| !names.contains(&name.as_str())) {
+            Some((name, _)) => Err(format!("Unknown parameter {}, expected one of {}", name, names.join(", "))),
+            None => Ok(())
+        }
+    }
+}
+
+/// A day whose answers depend on numbers that are not in its input.
+pub trait Configure: Solution {
+    /// The names of the parameters with their values in the puzzle.
+    const PARAMS: &'static [(&'static str, usize)];
+
+    fn part_1_with(input: &Self::Input, params: &Params) -> Self::Output1;
+    fn part_2_with(input: &Self::Input, params: &Params) -> Self::Output2;
+
+    /// The value of a parameter, as it was set or else as in the puzzle.
+    fn param(params: &Params, name: &str) -> usize {
+        params.get(name).unwrap_or_else(|| {
+            Self::PARAMS.iter().find(|(n, _)| *n == name).expect("Not a parameter of the day").1
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_and_set() {
+        let mut params = Params::parse("# Lanternfish\ndays_1 = 18\n\ndays_2=80\n").unwrap();
+        assert_eq!(params.get("days_1"), Some(18));
+        assert_eq!(params.get("steps"), None);
+        params.set("days_1=10").unwrap();
+        assert_eq!(params.get("days_1"), Some(10));
+        assert!(params.set("days_1").is_err());
+        assert!(params.set("days_1=-1").is_err());
+        assert_eq!(Params::parse("days 18").unwrap_err(), ParseError::new(1, 1, "days 18", "`=`"));
+        assert_eq!(Params::parse("a = 1\nb = x").unwrap_err(), ParseError::new(2, 5, "x", "a number"));
+        assert_eq!(params.check(&["days_1", "days_2"]), Ok(()));
+        assert_eq!(params.check(&["steps"]), Err(String::from("Unknown parameter days_1, expected one of steps")));
+        assert!(Params::default().is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use crate::input::unify_line_endings;
+
+/// Error from parsing a puzzle input. Points at the offending text and
+/// says what was expected there instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the input, counting from 1.
+    pub line: usize,
+    /// Column of the line, counting characters from 1.
+    pub column: usize,
+    /// The offending text, empty if the line or input ended too early.
+    pub text: String,
+    pub expected: String
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, expected: &str) -> Self {
+        Self { line, column, text: String::from(text), expected: String::from(expected) }
+    }
+
+    /// Error for `text`, which must be a slice of `line_text`, the text of
+    /// line `line`. The column is taken from where `text` sits in the line.
+    pub fn at(line: usize, line_text: &str, text: &str, expected: &str) -> Self {
+        let offset = (text.as_ptr() as usize).wrapping_sub(line_text.as_ptr() as usize);
+        let column = if offset <= line_text.len() && line_text.is_char_boundary(offset) {
+            line_text[..offset].chars().count() + 1
+        } else {
+            1
+        };
+        Self::new(line, column, text, expected)
+    }
+
+    /// Error for a line that ends before `expected` was found.
+    pub fn end_of_line(line: usize, line_text: &str, expected: &str) -> Self {
+        Self::new(line, line_text.chars().count() + 1, "", expected)
+    }
+
+    /// Moves the error down by `n_lines`, for errors from parsing a section
+    /// that starts further down in the input.
+    pub fn offset_lines(mut self, n_lines: usize) -> Self {
+        self.line += n_lines;
+        self
+    }
+
+    /// Renders the error together with the offending line of `input`, with
+    /// a marker under the column, the way it is printed to the user.
+    pub fn diagnostic(&self, input: &str) -> String {
+        let mut diagnostic = format!("error: {}", self);
+        // The lines are counted like the parsers count them, where a lone CR
+        // also ends a line.
+        if let Some(line_text) = unify_line_endings(input).lines().nth(self.line.wrapping_sub(1)) {
+            let number = self.line.to_string();
+            let padding = " ".repeat(number.len());
+            let marker = "^".repeat(self.text.chars().count().clamp(1, 40));
+            diagnostic += &format!("\n{} |\n{} | {}\n{} | {}{}",
+                padding, number, line_text, padding, " ".repeat(self.column - 1), marker);
+        }
+        diagnostic
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let found = if self.text.is_empty() { String::from("end of line") } else { format!("`{}`", self.text) };
+        write!(f, "line {}, column {}: expected {}, found {}", self.line, self.column, self.expected, found)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `text`, a slice of `line_text`, the text of line `line`.
+pub fn parse_at<T: FromStr>(line: usize, line_text: &str, text: &str, expected: &str) -> Result<T, ParseError> {
+    text.parse().map_err(|_| ParseError::at(line, line_text, text, expected))
+}
+
+/// Takes the next field of a line from `fields`, failing at the end of the line.
+pub fn next_field<'a>(fields: &mut impl Iterator<Item = &'a str>, line: usize, line_text: &str, expected: &str) -> Result<&'a str, ParseError> {
+    fields.next().ok_or_else(|| ParseError::end_of_line(line, line_text, expected))
+}
+
+/// Splits `text`, a slice of `line_text`, at the first `separator`.
+pub fn split_at<'a>(line: usize, line_text: &str, text: &'a str, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+    text.split_once(separator).ok_or_else(|| {
+        ParseError::at(line, line_text, text, &format!("`{}`", separator))
+    })
+}
+
+/// Fails with what was expected if `line_text` has text after `rest`.
+pub fn expect_end(line: usize, line_text: &str, rest: &str) -> Result<(), ParseError> {
+    if rest.is_empty() {
+        Ok(())
+    } else {
+        Err(ParseError::at(line, line_text, rest, "end of line"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::input::parse_lines;
+
+    #[test]
+    fn column_of_slice() {
+        let line = "12,x4";
+        let err = parse_at::<usize>(3, line, &line[3..], "a number").unwrap_err();
+        assert_eq!(err, ParseError::new(3, 4, "x4", "a number"));
+        assert_eq!(err.to_string(), "line 3, column 4: expected a number, found `x4`");
+    }
+
+    #[test]
+    fn end_of_line() {
+        let line = "forward";
+        let err = next_field(&mut line.split(' ').skip(1), 1, line, "a distance").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 8: expected a distance, found end of line");
+    }
+
+    #[test]
+    fn split() {
+        let line = "0,9 -> 5,9";
+        assert_eq!(split_at(1, line, line, " -> "), Ok(("0,9", "5,9")));
+        assert_eq!(split_at(1, line, &line[7..], ",").unwrap(), ("5", "9"));
+        assert_eq!(split_at(1, line, line, " => ").unwrap_err().column, 1);
+    }
+
+    #[test]
+    fn diagnostic() {
+        let input = "199\n2x0\n208";
+        let line = input.lines().nth(1).unwrap();
+        let err = parse_at::<usize>(2, line, line, "a depth").unwrap_err();
+        assert_eq!(
+            err.diagnostic(input),
+            "error: line 2, column 1: expected a depth, found `2x0`\n  |\n2 | 2x0\n  | ^^^"
+        );
+        let input = "199\r2x0\r\n208";
+        let err = parse_lines(input, |n_line, line| parse_at::<usize>(n_line, line, line, "a depth")).unwrap_err();
+        assert_eq!(
+            err.diagnostic(input),
+            "error: line 2, column 1: expected a depth, found `2x0`\n  |\n2 | 2x0\n  | ^^^"
+        )
+    }
+}
@@ -0,0 +1,360 @@
+//! Progress reporting and cancellation for the slow solvers.
+//!
+//! A runner wraps a solver in `Progress::run`. While it runs, the solver can
+//! log messages and report metrics through the free functions of this
+//! module, which go to the observer of that `Progress`, and should call
+//! `checkpoint` every now and then. Once the progress is cancelled or its
+//! time budget is spent, the next `checkpoint` unwinds out of the solver
+//! and `run` returns `Err(Cancelled)`. Outside of `run` logging does
+//! nothing and nothing is ever cancelled.
+
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How often metrics are passed on to the observer at most.
+pub const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace
+}
+
+impl Level {
+    pub const ALL: [Level; 5] = [Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace];
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Level::Error => write!(f, "error"),
+            Level::Warn => write!(f, "warn"),
+            Level::Info => write!(f, "info"),
+            Level::Debug => write!(f, "debug"),
+            Level::Trace => write!(f, "trace")
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Level, String> {
+        Level::ALL.into_iter().find(|level| level.to_string() == s).ok_or_else(|| format!("Invalid log level: {}", s))
+    }
+}
+
+/// How far a solver has come. Fields a solver does not know are left out.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Metrics {
+    /// States, candidates or the like looked at so far.
+    pub explored: usize,
+    /// States or candidates waiting to be looked at.
+    pub open: Option<usize>,
+    /// The best bound of the answer found so far.
+    pub best: Option<usize>,
+    /// The fraction of the work that is done, from 0 to 1.
+    pub done: Option<f64>
+}
+
+impl Display for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "explored {}", self.explored)?;
+        if let Some(open) = self.open {
+            write!(f, ", open {}", open)?;
+        }
+        if let Some(best) = self.best {
+            write!(f, ", best {}", best)?;
+        }
+        if let Some(done) = self.done {
+            write!(f, ", {:.1}% done", 100.0 * done)?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives what a solver reports.
+pub trait Observer: Send + Sync {
+    fn log(&self, level: Level, message: &str);
+
+    /// Metrics along with the time spent so far and, if the solver knows how
+    /// much is done, an estimate of the time left.
+    fn report(&self, metrics: &Metrics, elapsed: Duration, eta: Option<Duration>) {
+        let mut message = format!("{} after {:.1?}", metrics, elapsed);
+        if let Some(eta) = eta {
+            message += &format!(", {:.0?} left", eta);
+        }
+        self.log(Level::Info, &message)
+    }
+}
+
+/// Writes every message to stderr, so that it does not mix with the answers.
+pub struct Stderr;
+
+impl Observer for Stderr {
+    fn log(&self, level: Level, message: &str) {
+        eprintln!("[{}] {}", level, message)
+    }
+}
+
+/// The solver was stopped before it was done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cancelled")
+    }
+}
+
+struct Shared {
+    observer: Box<dyn Observer>,
+    level: Level,
+    budget: Option<Duration>,
+    start: Instant,
+    cancelled: AtomicBool,
+    last_report: Mutex<Option<Instant>>
+}
+
+/// The observer, log level, time budget and cancellation of one run of a
+/// solver. Clones share all of it, so a clone can cancel from another thread.
+#[derive(Clone)]
+pub struct Progress {
+    shared: Arc<Shared>
+}
+
+impl Progress {
+    /// Passes messages up to `Level::Warn` to `observer`, with no time budget.
+    pub fn new(observer: impl Observer + 'static) -> Self {
+        Self { shared: Arc::new(Shared {
+            observer: Box::new(observer),
+            level: Level::Warn,
+            budget: None,
+            start: Instant::now(),
+            cancelled: AtomicBool::new(false),
+            last_report: Mutex::new(None)
+        }) }
+    }
+
+    fn shared_mut(&mut self) -> &mut Shared {
+        Arc::get_mut(&mut self.shared).expect("Progress is already shared")
+    }
+
+    /// Passes on messages up to and including `level`. Metrics are reported
+    /// from `Level::Info` on.
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.shared_mut().level = level;
+        self
+    }
+
+    /// Cancels the solver once `budget` has passed since the progress was made.
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.shared_mut().budget = Some(budget);
+        self
+    }
+
+    pub fn cancel(&self) {
+        self.shared.cancelled.store(true, Ordering::Relaxed)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.shared.cancelled.load(Ordering::Relaxed) || self.over_budget()
+    }
+
+    /// Whether the time budget is spent.
+    pub fn over_budget(&self) -> bool {
+        self.shared.budget.is_some_and(|budget| self.elapsed() > budget)
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.shared.start.elapsed()
+    }
+
+    pub fn enabled(&self, level: Level) -> bool {
+        level <= self.shared.level
+    }
+
+    fn log(&self, level: Level, message: &str) {
+        if self.enabled(level) {
+            self.shared.observer.log(level, message)
+        }
+    }
+
+    fn report(&self, metrics: &Metrics) {
+        if !self.enabled(Level::Info) {
+            return
+        }
+        let mut last_report = self.shared.last_report.lock().unwrap();
+        if last_report.is_some_and(|last| last.elapsed() < REPORT_INTERVAL) {
+            return
+        }
+        *last_report = Some(Instant::now());
+        let elapsed = self.elapsed();
+        let eta = metrics.done.filter(|done| *done > 0.0).map(|done| elapsed.mul_f64((1.0 - done).max(0.0) / done));
+        self.shared.observer.report(metrics, elapsed, eta)
+    }
+
+    /// Runs `solve` with this as the progress the free functions of the
+    /// module report to, on this thread. Fails if the solver was stopped at
+    /// a `checkpoint`.
+    pub fn run<T>(&self, solve: impl FnOnce() -> T) -> Result<T, Cancelled> {
+        let previous = CURRENT.with(|current| current.replace(Some(self.clone())));
+        let result = panic::catch_unwind(AssertUnwindSafe(solve));
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+        match result {
+            Ok(value) => Ok(value),
+            Err(payload) if payload.is::<Cancelled>() => Err(Cancelled),
+            Err(payload) => panic::resume_unwind(payload)
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Progress>> = const { RefCell::new(None) };
+}
+
+fn with_current(f: impl FnOnce(&Progress)) {
+    CURRENT.with(|current| {
+        if let Some(progress) = current.borrow().as_ref() {
+            f(progress)
+        }
+    })
+}
+
+/// The progress the solver on this thread reports to, if any, to hand on
+/// to other threads.
+pub fn current() -> Option<Progress> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Runs `f` with `progress` as the progress of this thread, for work handed
+/// over to other threads. Unlike `Progress::run`, a cancellation unwinds on
+/// out of `f`, so that it reaches the `run` on the thread that handed the
+/// work over.
+pub fn within<T>(progress: &Option<Progress>, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<Progress>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT.with(|current| *current.borrow_mut() = self.0.take())
+        }
+    }
+
+    let _restore = Restore(CURRENT.with(|current| current.replace(progress.clone())));
+    f()
+}
+
+/// Logs the message made by `message`, which is only called if the level is enabled.
+pub fn log<M: Display>(level: Level, message: impl FnOnce() -> M) {
+    with_current(|progress| {
+        if progress.enabled(level) {
+            progress.log(level, &message().to_string())
+        }
+    })
+}
+
+/// Reports metrics, at most once every `REPORT_INTERVAL`.
+pub fn report(metrics: impl FnOnce() -> Metrics) {
+    with_current(|progress| {
+        if progress.enabled(Level::Info) {
+            progress.report(&metrics())
+        }
+    })
+}
+
+/// Stops the solver, by unwinding out of it, if its run is cancelled or
+/// over budget.
+pub fn checkpoint() {
+    let mut cancelled = false;
+    with_current(|progress| cancelled = progress.is_cancelled());
+    if cancelled {
+        panic::resume_unwind(Box::new(Cancelled))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Keeps everything it is told.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl Observer for Recorder {
+        fn log(&self, level: Level, message: &str) {
+            self.0.lock().unwrap().push(format!("{}: {}", level, message))
+        }
+    }
+
+    #[test]
+    fn levels() {
+        let recorder = Recorder::default();
+        let progress = Progress::new(recorder.clone()).with_level(Level::Info);
+        let answer = progress.run(|| {
+            log(Level::Info, || "starting");
+            log(Level::Debug, || -> String { panic!("Not enabled, so never made") });
+            report(|| Metrics { explored: 10, best: Some(3), ..Metrics::default() });
+            report(|| Metrics { explored: 20, ..Metrics::default() });
+            42
+        });
+        assert_eq!(answer, Ok(42));
+        let messages = recorder.0.lock().unwrap().clone();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0], "info: starting");
+        assert!(messages[1].starts_with("info: explored 10, best 3 after"));
+        assert_eq!("debug".parse(), Ok(Level::Debug));
+        assert!("loud".parse::<Level>().is_err());
+    }
+
+    #[test]
+    fn cancel() {
+        let progress = Progress::new(Recorder::default());
+        let handle = progress.clone();
+        let result = progress.run(|| {
+            for n in 0.. {
+                if n == 100 {
+                    handle.cancel()
+                }
+                checkpoint()
+            }
+        });
+        assert_eq!(result, Err(Cancelled));
+        checkpoint();
+    }
+
+    #[test]
+    fn budget() {
+        let progress = Progress::new(Recorder::default()).with_budget(Duration::from_millis(10));
+        assert_eq!(progress.run(|| loop { checkpoint() }), Err::<(), _>(Cancelled));
+        assert!(progress.over_budget());
+    }
+
+    #[test]
+    fn cancel_other_thread() {
+        let progress = Progress::new(Recorder::default()).with_budget(Duration::from_millis(10));
+        let result = progress.run(|| {
+            let current = current();
+            std::thread::scope(|scope| {
+                let worker = scope.spawn(|| within(&current, || loop { checkpoint() }));
+                if let Err(payload) = worker.join() {
+                    panic::resume_unwind(payload)
+                }
+            })
+        });
+        assert_eq!(result, Err(Cancelled));
+    }
+
+    #[test]
+    #[should_panic(expected = "Not a cancellation")]
+    fn other_panics_pass() {
+        let _ = Progress::new(Recorder::default()).run(|| panic!("Not a cancellation"));
+    }
+}
@@ -0,0 +1,89 @@
+//! Property-based testing: checks that a law a solver relies on holds on many
+//! small random cases. The first case it fails on is shrunk to a minimal one
+//! before it is reported. The shrinking helpers of `differential` make the
+//! smaller cases.
+
+use std::fmt::{self, Debug, Display};
+use crate::differential::shrink_failing;
+use crate::Rng;
+
+/// A case a law does not hold for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counterexample<T> {
+    /// The seed of the run that found the case.
+    pub seed: u64,
+    /// The case as it was generated.
+    pub original: T,
+    /// The smallest case found by shrinking the original.
+    pub case: T,
+    /// How the law fails for the smallest case.
+    pub failure: String
+}
+
+impl<T: Debug> Display for Counterexample<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Law does not hold with seed {} for", self.seed)?;
+        writeln!(f, "{:#?}", self.case)?;
+        writeln!(f, "{}", self.failure)?;
+        write!(f, "shrunk from {:?}", self.original)
+    }
+}
+
+/// A law and how to make and shrink cases for it.
+pub struct Property<T> {
+    /// Draws a random small case.
+    pub generate: fn(&mut Rng) -> T,
+    /// Cases that are a step smaller than the given one, most promising first.
+    pub shrink: fn(&T) -> Vec<T>,
+    /// Nothing if the law holds for a case, or how it fails.
+    pub law: fn(&T) -> Result<(), String>
+}
+
+impl<T: Clone> Property<T> {
+    /// Checks the law on `n_cases` cases drawn with `seed` and returns the
+    /// first case it fails for, shrunk, if there is one.
+    pub fn find_counterexample(&self, seed: u64, n_cases: usize) -> Option<Counterexample<T>> {
+        let mut rng = Rng::new(seed);
+        let original = (0..n_cases).map(|_| (self.generate)(&mut rng)).find(|case| (self.law)(case).is_err())?;
+        let case = shrink_failing(&original, self.shrink, |case| (self.law)(case).is_err());
+        let failure = (self.law)(&case).unwrap_err();
+        Some(Counterexample { seed, original, case, failure })
+    }
+}
+
+impl<T: Clone + Debug> Property<T> {
+    /// Like `find_counterexample` but panics with a report of the case.
+    pub fn check(&self, seed: u64, n_cases: usize) {
+        if let Some(counterexample) = self.find_counterexample(seed, n_cases) {
+            panic!("{}", counterexample)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::differential::{remove_each, shrink_each, smaller};
+    use super::*;
+
+    #[test]
+    fn shrinks_to_minimal_case() {
+        let property = Property {
+            generate: |rng| (0..10).map(|_| rng.below(100)).collect::<Vec<_>>(),
+            shrink: |v| {
+                let mut shrunk = remove_each(v, 0);
+                shrunk.extend(shrink_each(v, |n| smaller(*n, 0)));
+                shrunk
+            },
+            law: |v| match v.iter().find(|n| **n >= 50) {
+                Some(n) => Err(format!("{} is not below 50", n)),
+                None => Ok(())
+            }
+        };
+        let counterexample = property.find_counterexample(1, 100).unwrap();
+        assert_eq!(counterexample.case, vec!(50));
+        assert_eq!(counterexample.failure, "50 is not below 50");
+        assert!(counterexample.to_string().starts_with("Law does not hold with seed 1 for"));
+        let holds = Property { law: |_: &Vec<usize>| Ok(()), ..property };
+        assert_eq!(holds.find_counterexample(1, 100), None);
+    }
+}
@@ -0,0 +1,74 @@
+//! A small seeded random number generator for the input generators. It is
+//! SplitMix64, so the same seed gives the same input on every platform.
+
+pub struct Rng {
+    state: u64
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from 0 up to but not including `n`, which must not be 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Empty range");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number from `min` to `max`, both included.
+    pub fn range(&mut self, min: isize, max: isize) -> isize {
+        assert!(min <= max, "Empty range {}..={}", min, max);
+        min + (self.next_u64() % ((max - min) as u64 + 1)) as isize
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for ind in (1..items.len()).rev() {
+            items.swap(ind, self.below(ind + 1))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let first: Vec<_> = (0..5).scan(Rng::new(7), |rng, _| Some(rng.next_u64())).collect();
+        let second: Vec<_> = (0..5).scan(Rng::new(7), |rng, _| Some(rng.next_u64())).collect();
+        assert_eq!(first, second);
+        assert_ne!(first[0], Rng::new(8).next_u64());
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            assert!(rng.below(3) < 3);
+            assert!((-2..=2).contains(&rng.range(-2, 2)));
+        }
+        assert!((0..1000).all(|_| !rng.chance(0.0) && rng.chance(1.0)));
+        let mut items: Vec<_> = (0..10).collect();
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,25 @@
+//! Days whose answers come from a simulation that plays out one step at a
+//! time, so that it can be stepped through and watched.
+
+use crate::Solution;
+use crate::params::Params;
+
+/// A simulation a day runs to answer a part, one step at a time.
+pub trait Simulate: Solution {
+    type State: Clone;
+
+    /// The state before the first step. Days that run the same simulation
+    /// for both parts ignore `part`, and days without parameters `params`.
+    fn start(input: &Self::Input, part: usize, params: &Params) -> Self::State;
+    /// The state after one more step, or `None` once the simulation is over.
+    fn step(state: &Self::State) -> Option<Self::State>;
+    /// The state as lines of one character per cell.
+    fn draw(state: &Self::State) -> Vec<String>;
+    /// What is counted so far, like the flashes of the octopuses.
+    fn counters(state: &Self::State) -> Vec<(&'static str, usize)>;
+    /// Where the top left cell of the drawing was at the start, for days
+    /// whose drawing grows, so that cells can be compared between steps.
+    fn origin(_state: &Self::State) -> (isize, isize) {
+        (0, 0)
+    }
+}
@@ -0,0 +1,16 @@
+use common::{Generate, Generated, Rng};
+use crate::Day01;
+
+impl Generate for Day01 {
+    const INPUT_SIZE: usize = 2000;
+
+    /// `size` depths of a sea floor that mostly slopes down.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut depth = rng.range(100, 200);
+        let depths: Vec<String> = (0..size).map(|_| {
+            depth = (depth + rng.range(-6, 10)).max(0);
+            depth.to_string()
+        }).collect();
+        Generated::new(depths.join("\n"))
+    }
+}
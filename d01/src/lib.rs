@@ -0,0 +1,75 @@
+mod generate;
+
+use common::{ParseError, Solution};
+use common::input::parse_lines;
+use common::parse::parse_at;
+
+pub fn parse_input(s: &str) -> Result<Vec<usize>, ParseError> {
+    parse_lines(s, |line, l| parse_at(line, l, l, "a depth"))
+}
+
+pub fn count_increases(meas: &Vec<usize>) -> usize {
+    (1..meas.len()).filter(|i| meas[*i] > meas[*i-1]).count()
+}
+
+pub fn count_sliding(meas: &Vec<usize>) -> usize {
+    (3..meas.len()).filter(
+        |i| {
+            let r_lo = (i - 3)..=(i-1);
+            let r_up = (i - 2)..=*i;
+            let sum_lo =  r_lo.map(|ind| meas[ind]).sum::<usize>();
+            let sum_up =  r_up.map(|ind| meas[ind]).sum::<usize>();
+            sum_up > sum_lo
+        } 
+    ).count()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<usize>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(meas: &Vec<usize>) -> usize {
+        count_increases(meas)
+    }
+
+    fn part_2(meas: &Vec<usize>) -> usize {
+        count_sliding(meas)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn test_count() {
+        let meas = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            count_increases(&meas),
+            7
+        )
+    }
+
+    #[test]
+    fn test_sliding() {
+        let meas = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            count_sliding(&meas),
+            5
+        )
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse_input("199\n200\n2O8").unwrap_err(),
+            ParseError::new(3, 1, "2O8", "a depth")
+        )
+    }
+}
\ No newline at end of file
@@ -0,0 +1,46 @@
+use common::{Generate, Generated, Rng};
+use crate::Day02;
+
+impl Generate for Day02 {
+    const INPUT_SIZE: usize = 1000;
+
+    /// `size` instructions that never take the submarine above the surface,
+    /// with the answers worked out along the way.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        // The depth of the first part is the aim of the second.
+        let (mut x, mut depth, mut depth_2) = (0, 0, 0);
+        let instructions: Vec<String> = (0..size).map(|_| {
+            let dist = rng.range(1, 9) as usize;
+            match rng.below(3) {
+                0 => {
+                    x += dist;
+                    depth_2 += depth * dist;
+                    format!("forward {}", dist)
+                },
+                1 if dist <= depth => {
+                    depth -= dist;
+                    format!("up {}", dist)
+                },
+                _ => {
+                    depth += dist;
+                    format!("down {}", dist)
+                }
+            }
+        }).collect();
+        Generated::with_answers(instructions.join("\n"), x * depth, x * depth_2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Solution;
+    use super::*;
+
+    #[test]
+    fn test_generated_answers() {
+        let generated = Day02::generate(&mut Rng::new(2), 200);
+        let instructions = Day02::parse(&generated.input).unwrap();
+        assert_eq!(generated.part_1, Some(Day02::part_1(&instructions).to_string()));
+        assert_eq!(generated.part_2, Some(Day02::part_2(&instructions).to_string()));
+    }
+}
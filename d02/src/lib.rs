@@ -0,0 +1,115 @@
+mod generate;
+
+use common::{ParseError, Solution};
+use common::input::parse_lines;
+use common::parse::{expect_end, next_field, parse_at};
+
+pub enum Direction {
+    Forward,
+    Up,
+    Down
+}
+
+pub struct Instruction {
+    pub dir: Direction,
+    pub dist: usize
+}
+
+impl Instruction {
+    fn parse(line: usize, s: &str) -> Result<Self, ParseError> {
+        let mut it = s.split(' ');
+        let dir = next_field(&mut it, line, s, "a direction")?;
+        let dir = match dir {
+            "forward" => Direction::Forward,
+            "up" => Direction::Up,
+            "down" => Direction::Down,
+            _ => return Err(ParseError::at(line, s, dir, "forward, up or down"))
+        };
+        let dist = parse_at(line, s, next_field(&mut it, line, s, "a distance")?, "a distance")?;
+        expect_end(line, s, it.next().unwrap_or(""))?;
+        Ok(Self { dir, dist})
+    }
+}
+
+pub fn calc_pos(ins: &Vec<Instruction>) -> (usize, usize) {
+    ins.iter().fold((0, 0), 
+        |(x, y), ins| {
+            match ins.dir {
+                Direction::Forward => (x + ins.dist, y),
+                Direction::Up => (x, y - ins.dist),
+                Direction::Down => (x, y + ins.dist),
+            }
+        }
+    )
+}
+
+pub fn calc_pos_2(ins: &Vec<Instruction>) -> (usize, usize, usize) {
+    ins.iter().fold((0, 0, 0), 
+        |(x, y, aim), ins| {
+            match ins.dir {
+                Direction::Forward => (x + ins.dist, y + aim * ins.dist, aim),
+                Direction::Up => (x, y, aim - ins.dist),
+                Direction::Down => (x, y, aim + ins.dist),
+            }
+        }
+    )
+}
+
+pub fn parse_instructions(s: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines(s, Instruction::parse)
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Instruction>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+        parse_instructions(input)
+    }
+
+    fn part_1(instructions: &Vec<Instruction>) -> usize {
+        let pos = calc_pos(instructions);
+        pos.0 * pos.1
+    }
+
+    fn part_2(instructions: &Vec<Instruction>) -> usize {
+        let pos = calc_pos_2(instructions);
+        pos.0 * pos.1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn  test_part_1() {
+        let instructions = parse_instructions(include_str!("../test")).unwrap();
+        assert_eq!(
+            calc_pos(&instructions),
+            (15, 10)
+        );
+    }
+
+    #[test]
+    fn  test_part_2() {
+        let instructions = parse_instructions(include_str!("../test")).unwrap();
+        assert_eq!(
+            calc_pos_2(&instructions),
+            (15, 60, 10)
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_instructions("forward 5\nbackward 3").err().unwrap();
+        assert_eq!(err, ParseError::new(2, 1, "backward", "forward, up or down"));
+        let err = parse_instructions("forward 5\ndown x").err().unwrap();
+        assert_eq!(err, ParseError::new(2, 6, "x", "a distance"));
+        let err = parse_instructions("up").err().unwrap();
+        assert_eq!(err, ParseError::new(1, 3, "", "a distance"));
+    }
+}
\ No newline at end of file
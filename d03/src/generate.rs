@@ -0,0 +1,22 @@
+use std::collections::HashSet;
+use common::{Generate, Generated, Rng};
+use crate::Day03;
+
+impl Generate for Day03 {
+    const INPUT_SIZE: usize = 1000;
+
+    /// `size` distinct diagnostic codes of at least 12 bits. The codes have
+    /// to be distinct for the oxygen and CO2 ratings to end up with one code.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let n_bits = 12.max(usize::BITS - size.leading_zeros() + 1) as usize;
+        let mut seen = HashSet::new();
+        let mut codes = vec!();
+        while codes.len() < size {
+            let code = rng.next_u64() & ((1 << n_bits) - 1);
+            if seen.insert(code) {
+                codes.push(format!("{:0width$b}", code, width = n_bits))
+            }
+        }
+        Generated::new(codes.join("\n"))
+    }
+}
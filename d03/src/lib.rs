@@ -0,0 +1,165 @@
+mod generate;
+
+use common::{ParseError, Solution};
+use common::input::parse_lines;
+
+pub fn parse_input(s:  &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let mut code_length = None;
+    parse_lines(s, |line, s| {
+        let code_length = *code_length.get_or_insert(s.len());
+        if let Some((col, c)) = s.chars().enumerate().find(|(_, c)| *c != '0' && *c != '1') {
+            return Err(ParseError::new(line, col + 1, &c.to_string(), "a binary digit"))
+        }
+        if s.is_empty() || s.len() != code_length {
+            let expected = format!("a code of {} digits", code_length);
+            return Err(ParseError::new(line, 1, s, &expected))
+        }
+        Ok(s.chars().collect::<Vec<char>>())
+    })
+}
+
+pub fn calc_rates(diag_codes: &Vec<Vec<char>>) -> (usize, usize) {
+    let mut counts: Vec<usize> = vec![0; diag_codes[0].len()];
+    let code_length = diag_codes[0].len();
+    for pos in 0..code_length {
+        for code in diag_codes {
+            if code[pos] == '1' {
+                counts[pos] += 1
+            }
+        }    
+    }
+    let n_codes = diag_codes.len();
+    let mut gamma: Vec<char> = vec!();
+    let mut epsilon: Vec<char> = vec!();
+    for pos in 0..code_length {
+        if counts[pos]*2 > n_codes {
+            gamma.push('1');
+            epsilon.push('0');
+        } else {
+            gamma.push('0');
+            epsilon.push('1');
+        }
+    }
+    let gamma_str: String = gamma.iter().collect();
+    let epsilon_str: String = epsilon.iter().collect();
+    let gamma = usize::from_str_radix(&gamma_str, 2).unwrap();
+    let epsilon = usize::from_str_radix(&epsilon_str, 2).unwrap();
+    (gamma, epsilon)
+}
+
+pub fn calc_ox_co2(diag_codes: &Vec<Vec<char>>) -> (usize, usize) {
+    (calc_ox(diag_codes), calc_co2(diag_codes))
+}
+
+fn get_most_common(diag_codes: &Vec<Vec<char>>, pos: usize) -> char {
+    let n_ones = diag_codes.iter().fold(0, |acc, code| if code[pos] == '1'{ acc + 1} else { acc });
+    if n_ones * 2 >= diag_codes.len() {
+        '1'
+    } else {
+        '0'
+    }
+}
+
+fn get_least_common(diag_codes: &Vec<Vec<char>>, pos: usize) -> char {
+    let n_ones = diag_codes.iter().fold(0, |acc, code| if code[pos] == '1'{ acc + 1} else { acc });
+    if n_ones * 2 < diag_codes.len() {
+        '1'
+    } else {
+        '0'
+    }
+}
+
+fn filter_all(remaining: Vec<Vec<char>>, pos: usize, to_keep: char) -> Vec<Vec<char>> {
+    remaining.into_iter().filter(|code| code[pos] == to_keep).collect()
+}
+
+fn calc_ox(diag_codes: &Vec<Vec<char>>) -> usize {
+    let mut pos = 0;
+    let mut remaining = diag_codes.clone();
+    while remaining.len() > 1 {
+        let most_common = get_most_common(&remaining, pos);
+        remaining = filter_all(remaining, pos, most_common);
+        pos +=1;
+    }
+    let ox: String = remaining[0].iter().collect();
+    usize::from_str_radix(&ox, 2).unwrap()
+}
+
+fn calc_co2(diag_codes: &Vec<Vec<char>>) -> usize {
+    let mut pos = 0;
+    let mut remaining = diag_codes.clone();
+    while remaining.len() > 1 {
+        let least_common = get_least_common(&remaining, pos);
+        remaining = filter_all(remaining, pos, least_common);
+        pos +=1;
+    }
+    let co2: String = remaining[0].iter().collect();
+    usize::from_str_radix(&co2, 2).unwrap()
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Vec<char>>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(diag_codes: &Vec<Vec<char>>) -> usize {
+        let (gamma, epsilon) = calc_rates(diag_codes);
+        gamma * epsilon
+    }
+
+    fn part_2(diag_codes: &Vec<Vec<char>>) -> usize {
+        let (ox, co2) = calc_ox_co2(diag_codes);
+        ox * co2
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        let diag_codes = parse_input(include_str!("../test")).unwrap();
+        let (gamma, epsilon) = calc_rates(&diag_codes);
+        assert_eq!(
+            gamma * epsilon,
+            198
+        )
+    }
+
+    #[test]
+    fn test_ox() {
+        let diag_codes = parse_input(include_str!("../test")).unwrap();
+        let ox = calc_ox(&diag_codes);
+        assert_eq!(ox, 23)
+    }
+
+    #[test]
+    fn test_part_2() {
+        let diag_codes = parse_input(include_str!("../test")).unwrap();
+        let (ox, co2) = calc_ox_co2(&diag_codes);
+        assert_eq!(
+            (ox, co2),
+            (23, 10)
+        )
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse_input("00100\n11120").unwrap_err(),
+            ParseError::new(2, 4, "2", "a binary digit")
+        );
+        assert_eq!(
+            parse_input("00100\n1110").unwrap_err(),
+            ParseError::new(2, 1, "1110", "a code of 5 digits")
+        )
+    }
+
+}
\ No newline at end of file
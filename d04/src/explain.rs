@@ -0,0 +1,69 @@
+use common::explain::{Explain, Fact};
+use crate::{first_win, last_win, Board, Day04, Win};
+
+/// The first complete row or column of a board, counted from 1.
+fn complete_line(board: &Board) -> String {
+    if let Some(row) = board.rows.iter().position(|r| r.iter().all(|c| c.drawn)) {
+        return format!("row {}", row + 1)
+    }
+    let col = (0..5).find(|col| board.rows.iter().all(|r| r[*col].drawn)).expect("A winning board has a complete line");
+    format!("column {}", col + 1)
+}
+
+/// The board, counted from 1, the number that completed it and the line it
+/// completed, and the unmarked numbers that make up the score.
+fn explain(win: &Win) -> Fact {
+    let unmarked: Vec<usize> = win.marked.rows.iter().flatten().filter(|c| !c.drawn).map(|c| c.num).collect();
+    Fact::record([
+        ("board", Fact::from(win.board + 1)),
+        ("draw", Fact::from(win.pick)),
+        ("numbers_drawn", Fact::from(win.n_drawn)),
+        ("line", Fact::from(complete_line(&win.marked))),
+        ("rows", Fact::list(win.marked.rows.iter().map(|r| Fact::list(r.iter().map(|c| c.num))))),
+        ("unmarked_sum", Fact::from(unmarked.iter().sum::<usize>())),
+        ("unmarked", Fact::from(unmarked)),
+        ("score", Fact::from(win.score()))
+    ])
+}
+
+impl Explain for Day04 {
+    /// The board that wins first.
+    fn explain_1(input: &(Vec<usize>, Vec<Board>)) -> Fact {
+        let (nums, boards) = input;
+        explain(&first_win(nums, boards))
+    }
+
+    /// The board that wins last.
+    fn explain_2(input: &(Vec<usize>, Vec<Board>)) -> Fact {
+        let (nums, boards) = input;
+        explain(&last_win(nums, boards))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn winning_boards() {
+        let input = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(Day04::explain_1(&input).to_text(), "\
+board: 3
+draw: 24
+numbers_drawn: 12
+line: row 1
+rows:
+  - [14, 21, 17, 24, 4]
+  - [10, 16, 15, 9, 19]
+  - [18, 8, 23, 26, 20]
+  - [22, 11, 13, 6, 5]
+  - [2, 0, 12, 3, 7]
+unmarked_sum: 188
+unmarked: [10, 16, 15, 19, 18, 8, 26, 20, 22, 13, 6, 12, 3]
+score: 4512");
+        let last = Day04::explain_2(&input).to_json();
+        assert!(last.starts_with("{\"board\": 2, \"draw\": 13, \"numbers_drawn\": 15, \"line\": \"column 3\""), "{}", last);
+        assert!(last.ends_with("\"unmarked_sum\": 148, \"unmarked\": [3, 15, 22, 18, 19, 8, 25, 20, 12, 6], \"score\": 1924}"), "{}", last);
+    }
+}
@@ -0,0 +1,25 @@
+use common::{Generate, Generated, Rng};
+use crate::Day04;
+
+impl Generate for Day04 {
+    const INPUT_SIZE: usize = 100;
+
+    /// `size` boards. All numbers on the boards are drawn, so every board
+    /// wins in the end.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let n_numbers = 100.max(size);
+        let mut drawn: Vec<usize> = (0..n_numbers).collect();
+        rng.shuffle(&mut drawn);
+        let mut input = drawn.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(",");
+        for _ in 0..size {
+            let mut numbers: Vec<usize> = (0..n_numbers).collect();
+            rng.shuffle(&mut numbers);
+            input += "\n";
+            for row in numbers[..25].chunks(5) {
+                input += "\n";
+                input += &row.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>().join(" ");
+            }
+        }
+        Generated::new(input)
+    }
+}
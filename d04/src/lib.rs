@@ -0,0 +1,204 @@
+mod explain;
+mod generate;
+
+use common::{ParseError, Solution};
+use common::input::{parse_lines, parse_list, parse_one_line, sections};
+use common::parse::parse_at;
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cell {
+    pub num: usize,
+    pub drawn: bool
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Board {
+    pub rows: Vec<Vec<Cell>>
+}
+
+impl Board {
+    pub fn mark(&mut self, num: usize) {
+        let rows = self.rows.clone();
+        self.rows = rows.into_iter().map(|r| r.into_iter().map(|mut c| { if c.num == num { c.drawn = true} c}).collect()).collect();
+    }
+
+    pub fn is_winner(&self) -> bool {
+        for row in &self.rows {
+            if row.iter().all(|c| c.drawn) {
+                return true
+            }
+        }
+        for col in 0..5 {
+            let mut all_drawn = true;
+            for row in &self.rows {
+                if !row[col].drawn {
+                    all_drawn = false;
+                    break
+                }
+            }
+            if all_drawn {
+                return true
+            }
+        }
+        false
+    }
+
+    pub fn score(&self, last_pick: usize) -> usize {
+        self.rows.iter().flatten().fold(0, |acc, cell| if cell.drawn { acc } else { acc + cell.num}) * last_pick
+    }
+}
+
+fn parse_board(s: &str) -> Result<Board, ParseError> {
+    let rows = parse_lines(s, |line, row| {
+        if line > 5 {
+            return Err(ParseError::at(line, row, row, "an empty line"))
+        }
+        let cells: Vec<Cell> = row.split_whitespace()
+            .map(|n| parse_at(line, row, n, "a number").map(|num| Cell {num, drawn: false}))
+            .collect::<Result<_, _>>()?;
+        if cells.len() != 5 {
+            return Err(ParseError::at(line, row, row, "a row of 5 numbers"))
+        }
+        Ok(cells)
+    })?;
+    if rows.len() < 5 {
+        return Err(ParseError::new(rows.len() + 1, 1, "", "a row of 5 numbers"))
+    }
+    Ok(Board{rows})
+}
+
+pub fn parse_input(s: &str) -> Result<(Vec<usize>, Vec<Board>), ParseError> {
+    let sections = sections(s);
+    let nums = match sections.first() {
+        Some(section) => section.parse(|s| parse_one_line(s, |l| parse_list(1, l, l, ",", "a drawn number")))?,
+        None => return Err(ParseError::new(1, 1, "", "a drawn number"))
+    };
+    let boards = sections.iter().skip(1).map(|section| section.parse(parse_board)).collect::<Result<_, _>>()?;
+    Ok((nums, boards))
+}
+
+/// A board that has won: which one, counted from 0, the number that
+/// completed it, how many numbers had been drawn then, and the board as it
+/// was marked at that point.
+pub struct Win {
+    pub board: usize,
+    pub pick: usize,
+    pub n_drawn: usize,
+    pub marked: Board
+}
+
+impl Win {
+    pub fn score(&self) -> usize {
+        self.marked.score(self.pick)
+    }
+}
+
+pub fn first_win(nums: &Vec<usize>, boards: &Vec<Board>) -> Win {
+    let mut boards = boards.clone();
+    let mut nums = nums.iter().enumerate();
+    loop {
+        if let Some((ind, pick)) = nums.next() {
+            for board in &mut boards {
+                board.mark(*pick)
+            }
+            if let Some(board) = boards.iter().position(|b| b.is_winner())  {
+                return Win { board, pick: *pick, n_drawn: ind + 1, marked: boards[board].clone() }
+            }
+        } else {
+            panic!("Out of numbers.")
+        }
+    }
+}
+
+pub fn last_win(nums: &Vec<usize>, boards: &Vec<Board>) -> Win {
+    let mut boards = boards.clone();
+    let mut nums = nums.iter().enumerate();
+    loop {
+        if let Some((ind, pick)) = nums.next() {
+            let non_winner = boards.iter().position(|b| !b.is_winner()).unwrap();
+            for board in &mut boards {
+                board.mark(*pick)
+            }
+            if boards.iter().all(|b| b.is_winner())  {
+                return Win { board: non_winner, pick: *pick, n_drawn: ind + 1, marked: boards[non_winner].clone() }
+            }
+        } else {
+            panic!("Out of numbers.")
+        }
+    }
+}
+
+pub fn part_1(nums: &Vec<usize>, boards: &Vec<Board>) -> usize {
+    first_win(nums, boards).score()
+}
+
+pub fn part_2(nums: &Vec<usize>, boards: &Vec<Board>) -> usize {
+    last_win(nums, boards).score()
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = (Vec<usize>, Vec<Board>);
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<(Vec<usize>, Vec<Board>), ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &(Vec<usize>, Vec<Board>)) -> usize {
+        let (nums, boards) = input;
+        part_1(nums, boards)
+    }
+
+    fn part_2(input: &(Vec<usize>, Vec<Board>)) -> usize {
+        let (nums, boards) = input;
+        part_2(nums, boards)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        let (nums, boards) = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            part_1(&nums, &boards),
+            4512
+        )
+    }
+
+    #[test]
+    fn test_part_2() {
+        let (nums, boards) = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            part_2(&nums, &boards),
+            1924
+        )
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = include_str!("../test").replacen("14 16  7", "14 1b  7", 1);
+        assert_eq!(parse_input(&input).err(), Some(ParseError::new(5, 10, "1b", "a number")));
+        let input = include_str!("../test").replacen(" 6 10  3 18  5", " 6 10  3 18", 1);
+        assert_eq!(parse_input(&input).err(), Some(ParseError::new(6, 1, " 6 10  3 18", "a row of 5 numbers")));
+        let input = "7,4,9\n\n1 2 3 4 5";
+        assert_eq!(parse_input(input).err(), Some(ParseError::new(4, 1, "", "a row of 5 numbers")));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let input = Day04::parse(include_str!("../test")).unwrap();
+        let json = serde_json::to_string(&input).unwrap();
+        let read: (Vec<usize>, Vec<Board>) = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&read).unwrap(), json);
+        assert_eq!(Day04::part_1(&read), 4512);
+    }
+}
@@ -0,0 +1,26 @@
+use common::{Generate, Generated, Rng};
+use crate::Day05;
+
+impl Generate for Day05 {
+    const INPUT_SIZE: usize = 500;
+
+    /// `size` horizontal, vertical and diagonal lines on a 1000 by 1000 floor.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let lines: Vec<String> = (0..size).map(|_| {
+            let (x1, y1) = (rng.range(0, 999), rng.range(0, 999));
+            let len = rng.range(-999, 999);
+            let (x2, y2) = match rng.below(3) {
+                0 => ((x1 + len).clamp(0, 999), y1),
+                1 => (x1, (y1 + len).clamp(0, 999)),
+                _ => {
+                    let dir_y = if rng.chance(0.5) { 1 } else { -1 };
+                    let len = len.clamp(-x1, 999 - x1);
+                    let len = (len * dir_y).clamp(-y1, 999 - y1) * dir_y;
+                    (x1 + len, y1 + len * dir_y)
+                }
+            };
+            format!("{},{} -> {},{}", x1, y1, x2, y2)
+        }).collect();
+        Generated::new(lines.join("\n"))
+    }
+}
@@ -0,0 +1,124 @@
+mod generate;
+mod visualize;
+
+use common::{ParseError, Solution};
+use common::geometry::Point2;
+use common::input;
+use common::parse::{parse_at, split_at};
+
+#[derive(Debug)]
+pub struct Line {
+    pub from: Point2<usize>,
+    pub to: Point2<usize>
+}
+
+fn parse_point(line: usize, l: &str, point: &str) -> Result<Point2<usize>, ParseError> {
+    let (x, y) = split_at(line, l, point, ",")?;
+    Ok(Point2::new(parse_at(line, l, x, "a coordinate")?, parse_at(line, l, y, "a coordinate")?))
+}
+
+pub fn parse_lines(s: &str) -> Result<Vec<Line>, ParseError> {
+    input::parse_lines(s, |line, l|
+        {
+            let (from, to) = split_at(line, l, l, " -> ")?;
+            let from = parse_point(line, l, from)?;
+            let to = parse_point(line, l, to)?;
+            if from.x != to.x && from.y != to.y && from.x.abs_diff(to.x) != from.y.abs_diff(to.y) {
+                return Err(ParseError::at(line, l, l, "a horizontal, vertical or diagonal line"))
+            }
+            Ok(Line { from, to })
+        })
+}
+
+pub fn mk_grid(lines: &Vec<Line>, part: usize) -> Vec<Vec<usize>> {
+    let orto_lines: Vec<_> = if part == 1 {
+        lines.iter().filter(|l| l.from.x == l.to.x || l.from.y == l.to.y).collect()
+    } else {
+        lines.iter().collect()
+    };
+    let max_x = orto_lines.iter().map(|l| l.from.x.max(l.to.x)).max().unwrap();
+    let max_y = orto_lines.iter().map(|l| l.from.y.max(l.to.y)).max().unwrap();
+    let first_line: Vec<usize> = vec![0; max_x + 1];
+    let mut grid: Vec<Vec<usize>> = vec!();
+    grid.resize(max_y + 1, first_line);
+    for line in orto_lines {
+        let to = line.to.map(|c| c as isize);
+        let mut pt = line.from.map(|c| c as isize);
+        let step = (to - pt).signum();
+        loop {
+            grid[pt.y as usize][pt.x as usize] +=1;
+            if pt == to {
+                break
+            }
+            pt = pt + step;
+        }
+    }
+    grid
+}
+
+pub fn count_severe_danger(grid: &Vec<Vec<usize>>) -> usize {
+    grid.iter().flatten().fold(0, |acc, n| if *n >= 2 { acc + 1 } else { acc } )
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<Line>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Line>, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part_1(lines: &Vec<Line>) -> usize {
+        count_severe_danger(&mk_grid(lines, 1))
+    }
+
+    fn part_2(lines: &Vec<Line>) -> usize {
+        count_severe_danger(&mk_grid(lines, 2))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        let lines = parse_lines(include_str!("../test")).unwrap();
+        assert_eq!(
+            count_severe_danger(&mk_grid(&lines, 1)),
+            5
+        )
+    }
+
+    #[test]
+    fn test_part_2() {
+        let lines = parse_lines(include_str!("../test")).unwrap();
+        assert_eq!(
+            count_severe_danger(&mk_grid(&lines, 2)),
+            12
+        )
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse_lines("0,9 -> 5,9\n8,0 -> 0,8\n9,4 ->3,4").unwrap_err(),
+            ParseError::new(3, 1, "9,4 ->3,4", "` -> `")
+        );
+        assert_eq!(
+            parse_lines("0,9 -> 5,9\n8,0 -> 0;8").unwrap_err(),
+            ParseError::new(2, 8, "0;8", "`,`")
+        );
+        assert_eq!(
+            parse_lines("0,9 -> 5,-9").unwrap_err(),
+            ParseError::new(1, 10, "-9", "a coordinate")
+        );
+        assert_eq!(
+            parse_lines("0,0 -> 5,2").unwrap_err(),
+            ParseError::new(1, 1, "0,0 -> 5,2", "a horizontal, vertical or diagonal line")
+        )
+    }
+}
@@ -0,0 +1,23 @@
+use render::{Frame, Visualize};
+use crate::{mk_grid, Day05, Line};
+
+/// Cells crossed by three or more lines share the top level.
+const LEVELS: u8 = 4;
+
+impl Visualize for Day05 {
+    /// How many lines cross each point, first of the horizontal and vertical
+    /// lines only, then of all lines.
+    fn frames(lines: &Vec<Line>) -> Vec<Frame> {
+        let all = mk_grid(lines, 2);
+        let (n_rows, n_cols) = (all.len(), all[0].len());
+        [mk_grid(lines, 1), all].iter().map(|grid| {
+            let mut frame = Frame::new(n_rows, n_cols, LEVELS);
+            for (y, row) in grid.iter().enumerate() {
+                for (x, n) in row.iter().enumerate() {
+                    frame.set(y, x, (*n).min(LEVELS as usize - 1) as u8)
+                }
+            }
+            frame
+        }).collect()
+    }
+}
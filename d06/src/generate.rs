@@ -0,0 +1,12 @@
+use common::{Generate, Generated, Rng};
+use crate::Day06;
+
+impl Generate for Day06 {
+    const INPUT_SIZE: usize = 300;
+
+    /// `size` lanternfish with timers from 1 to 5, like in the real input.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let timers: Vec<String> = (0..size).map(|_| rng.range(1, 5).to_string()).collect();
+        Generated::new(timers.join(","))
+    }
+}
@@ -0,0 +1,189 @@
+mod generate;
+mod params;
+
+use std::collections::HashMap;
+use common::{ParseError, Solution};
+use common::count::{self, Count, Counted, Overflow};
+use common::input::{parse_list, parse_one_line};
+use common::params::{Configure, Params};
+
+pub fn parse_input(s: &str) -> Result<HashMap<usize, usize>, ParseError> {
+    let mut fish: HashMap<usize, usize> = HashMap::new();
+    let fishv: Vec<usize> = parse_one_line(s, |s| {
+        let fishv: Vec<usize> = parse_list(1, s, s, ",", "a timer from 0 to 8")?;
+        if let Some(f) = s.split(',').zip(&fishv).find(|(_, timer)| **timer > 8) {
+            return Err(ParseError::at(1, s, f.0, "a timer from 0 to 8"))
+        }
+        Ok(fishv)
+    })?;
+    for f in fishv {
+        if fish.contains_key(&f) {
+            let n = fish.get(&f).unwrap();
+            fish.insert(f, n + 1);
+        } else {
+            fish.insert(f, 1);
+        }
+    }
+    Ok(fish)
+}
+
+pub fn count_after(fish: &HashMap<usize, usize>, n_days: usize) -> Result<usize, Overflow> {
+    count_after_as(fish, n_days)
+}
+
+/// The number of fish after `n_days`, counted in `C`.
+pub fn count_after_as<C: Count>(fish: &HashMap<usize, usize>, n_days: usize) -> Result<C, Overflow> {
+    // The number of fish with each timer, from 0 to 8.
+    let mut timers = (0..9).map(|t| C::from_usize(*fish.get(&t).unwrap_or(&0))).collect::<Result<Vec<C>, _>>()?;
+    for _ in 0..n_days {
+        timers.rotate_left(1);
+        timers[6] = timers[6].plus(&timers[8])?;
+    }
+    count::sum(&timers)
+}
+
+pub fn count_fish(fish: HashMap<usize, usize>) -> usize {
+    fish.values().sum()
+}
+
+pub fn spawn(fish: HashMap<usize, usize>) -> HashMap<usize, usize> {
+    let n_new = if let Some(n) = fish.get(&0) {
+        *n
+    } else {
+        0
+    };
+    let the_fish: Vec<(usize, usize)> = fish.into_iter().map(
+        |(clock, n_fish)| {
+            if clock == 0 {
+                (6, n_fish)
+            } else {
+                (clock - 1, n_fish)
+            }
+        }
+    ).collect();
+    let mut new_fish: HashMap<usize, usize> = HashMap::new();
+    for f in the_fish {
+        if new_fish.contains_key(&f.0) {
+            let n = new_fish.get(&f.0).unwrap();
+            new_fish.insert(f.0, n + f.1);
+        } else {
+            new_fish.insert(f.0, f.1);
+        }
+
+    }
+    if n_new > 0 {
+        new_fish.insert(8, n_new);
+    }
+    new_fish
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = HashMap<usize, usize>;
+    type Output1 = Result<usize, Overflow>;
+    type Output2 = Result<usize, Overflow>;
+
+    fn parse(input: &str) -> Result<HashMap<usize, usize>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(fish: &HashMap<usize, usize>) -> Result<usize, Overflow> {
+        Self::part_1_with(fish, &Params::default())
+    }
+
+    fn part_2(fish: &HashMap<usize, usize>) -> Result<usize, Overflow> {
+        Self::part_2_with(fish, &Params::default())
+    }
+}
+
+impl Counted for Day06 {
+    fn part_1_as<C: Count>(fish: &HashMap<usize, usize>, params: &Params) -> Result<C, Overflow> {
+        count_after_as(fish, Self::param(params, "days_1"))
+    }
+
+    fn part_2_as<C: Count>(fish: &HashMap<usize, usize>, params: &Params) -> Result<C, Overflow> {
+        count_after_as(fish, Self::param(params, "days_2"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::differential::{remove_each, shrink_each, smaller, Differential};
+    use super::*;
+
+    /// Counts the fish by keeping every single one of them in a list.
+    fn count_one_by_one(timers: &[usize], n_days: usize) -> usize {
+        let mut fish = timers.to_vec();
+        for _ in 0..n_days {
+            let n_new = fish.iter().filter(|t| **t == 0).count();
+            fish = fish.into_iter().map(|t| if t == 0 { 6 } else { t - 1 }).collect();
+            fish.extend(std::iter::repeat_n(8, n_new));
+        }
+        fish.len()
+    }
+
+    #[test]
+    fn test_1_1() {
+        let mut fish = parse_input(include_str!("../test")).unwrap();
+        for _ in 0..18 {
+            fish = spawn(fish);
+            println!("{:?}", fish);
+        }
+        assert_eq!(
+            count_fish(fish),
+            26
+        )
+    }
+
+    #[test]
+    fn test_1_2() {
+        let mut fish = parse_input(include_str!("../test")).unwrap();
+        for _ in 0..256 {
+            fish = spawn(fish)
+        }
+        assert_eq!(
+            count_fish(fish),
+            26984457539
+        )
+    }
+
+    #[test]
+    fn many_days() {
+        let fish = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(count_after_as::<u64>(&fish, 256), Ok(26984457539));
+        assert_eq!(Day06::part_2_as::<u64>(&fish, &Params::default()), Ok(26984457539));
+        let mut params = Params::default();
+        params.set("days_2=1000").unwrap();
+        assert_eq!(Day06::part_2(&fish), Ok(26984457539));
+        assert_eq!(Day06::part_2_with(&fish, &params), Err(Overflow));
+        assert_eq!(count_after_as::<u64>(&fish, 1000), Err(Overflow));
+        assert_eq!(count_after_as::<u128>(&fish, 1000), Err(Overflow));
+        let fish_1000 = count_after_as::<count::BigUint>(&fish, 1000).unwrap();
+        assert_eq!(fish_1000.to_string(), "379589061144698259131825683795505058481");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(parse_input("3,4,3,x,2").unwrap_err(), ParseError::new(1, 7, "x", "a timer from 0 to 8"));
+        assert_eq!(parse_input("3,4,9").unwrap_err(), ParseError::new(1, 5, "9", "a timer from 0 to 8"));
+    }
+
+    #[test]
+    fn same_as_fish_list() {
+        Differential {
+            generate: |rng| ((0..1 + rng.below(8)).map(|_| rng.below(9)).collect::<Vec<_>>(), rng.below(60)),
+            shrink: |(timers, n_days)| {
+                let mut shrunk: Vec<_> = remove_each(timers, 1).into_iter().map(|t| (t, *n_days)).collect();
+                shrunk.extend(shrink_each(timers, |t| smaller(*t, 0)).into_iter().map(|t| (t, *n_days)));
+                shrunk.extend(smaller(*n_days, 0).into_iter().map(|n| (timers.clone(), n)));
+                shrunk
+            },
+            optimized: |(timers, n_days)| {
+                let input = timers.iter().map(|t| t.to_string()).collect::<Vec<_>>().join(",");
+                count_after(&parse_input(&input).unwrap(), *n_days).unwrap()
+            },
+            reference: |(timers, n_days)| count_one_by_one(timers, *n_days)
+        }.check(6, 500)
+    }
+}
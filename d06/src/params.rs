@@ -0,0 +1,33 @@
+use std::collections::HashMap;
+use common::count::Overflow;
+use common::params::{Configure, Params};
+use crate::{count_after, Day06};
+
+impl Configure for Day06 {
+    /// The days the fish grow for in each part.
+    const PARAMS: &'static [(&'static str, usize)] = &[("days_1", 80), ("days_2", 256)];
+
+    fn part_1_with(fish: &HashMap<usize, usize>, params: &Params) -> Result<usize, Overflow> {
+        count_after(fish, Self::param(params, "days_1"))
+    }
+
+    fn part_2_with(fish: &HashMap<usize, usize>, params: &Params) -> Result<usize, Overflow> {
+        count_after(fish, Self::param(params, "days_2"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn days() {
+        let fish = parse_input(include_str!("../test")).unwrap();
+        let mut params = Params::default();
+        assert_eq!(Day06::part_1_with(&fish, &params), Ok(5934));
+        params.set("days_1=18").unwrap();
+        assert_eq!(Day06::part_1_with(&fish, &params), Ok(26));
+        assert_eq!(Day06::part_2_with(&fish, &params), Ok(26984457539));
+    }
+}
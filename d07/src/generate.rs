@@ -0,0 +1,16 @@
+use common::{Generate, Generated, Rng};
+use crate::Day07;
+
+impl Generate for Day07 {
+    const INPUT_SIZE: usize = 1000;
+
+    /// `size` crabs, most of them in the lower part of a range twice as
+    /// wide as there are crabs.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let width = 2 * size as isize;
+        let positions: Vec<String> = (0..size).map(|_| {
+            rng.range(0, width - 1).min(rng.range(0, width - 1)).to_string()
+        }).collect();
+        Generated::new(positions.join(","))
+    }
+}
@@ -0,0 +1,101 @@
+mod generate;
+
+use common::{ParseError, Solution};
+use common::input::{parse_list, parse_one_line};
+
+pub fn parse_input(s: &str) -> Result<Vec<usize>, ParseError> {
+    parse_one_line(s, |s| parse_list(1, s, s, ",", "a crab position"))
+}
+
+pub fn part_1(crab_pos: &Vec<usize>) -> usize {
+    let first_pos = *crab_pos.iter().min().unwrap();
+    let last_pos = *crab_pos.iter().max().unwrap();
+    let test_positions: Vec<_> = (first_pos..=last_pos).collect();
+    
+    let mut fuel_costs_by_ind: Vec<_> = test_positions.iter().map(
+        |pos|
+        (pos,
+        crab_pos.iter().fold(0,
+            |acc, cp| acc + cp.abs_diff(*pos))
+        )
+    ).collect();
+    fuel_costs_by_ind.sort_by_key(|(_, cost)| *cost);
+    fuel_costs_by_ind[0].1
+}
+
+pub fn part_2(crab_pos: &Vec<usize>) -> usize {
+    let first_pos = *crab_pos.iter().min().unwrap();
+    let last_pos = *crab_pos.iter().max().unwrap();
+    let mut dist_cost: Vec<usize> = vec!();
+    let mut acc = 0;
+    for delta in 0..=(last_pos - first_pos) {
+        acc += delta;
+        dist_cost.push(acc)
+    }
+
+    let test_positions: Vec<_> = (first_pos..=last_pos).collect();
+    
+    let mut fuel_costs_by_ind: Vec<_> = test_positions.iter().map(
+        |pos|
+        (pos,
+        crab_pos.iter().fold(0,
+            |acc, cp| {
+                if cp > pos {
+                    dist_cost[cp - pos] + acc
+                } else {
+                    dist_cost[pos - cp] + acc
+                }
+            })
+        )
+    ).collect();
+    fuel_costs_by_ind.sort_by_key(|(_, cost)| *cost);
+    fuel_costs_by_ind[0].1
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<usize>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(crab_pos: &Vec<usize>) -> usize {
+        part_1(crab_pos)
+    }
+
+    fn part_2(crab_pos: &Vec<usize>) -> usize {
+        part_2(crab_pos)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        let crab_pos = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            part_1(&crab_pos),
+            37
+        )
+    }
+
+    #[test]
+    fn test_part_2() {
+        let crab_pos = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            part_2(&crab_pos),
+            168
+        )
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(parse_input("16,1,,0").unwrap_err(), ParseError::new(1, 6, "", "a crab position"))
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+use common::explain::{Explain, Fact};
+use crate::{find_wiring, part_1, part_2, read_output, Day08};
+
+/// The digits that can be told by the number of segments they light.
+fn easy_digit(pattern: &HashSet<char>) -> Option<char> {
+    match pattern.len() {
+        2 => Some('1'),
+        3 => Some('7'),
+        4 => Some('4'),
+        7 => Some('8'),
+        _ => None
+    }
+}
+
+impl Explain for Day08 {
+    /// The output of each display, counted from 1, with the digits that are
+    /// told apart by their number of segments and `?` for the others.
+    fn explain_1(observed: &Vec<(Vec<HashSet<char>>, Vec<HashSet<char>>)>) -> Fact {
+        let displays = observed.iter().enumerate().map(|(ind, (_, output))| Fact::record([
+            ("display", Fact::from(ind + 1)),
+            ("output", Fact::from(output.iter().map(|d| easy_digit(d).unwrap_or('?')).collect::<String>())),
+            ("easy_digits", Fact::from(output.iter().filter_map(easy_digit).count()))
+        ]));
+        Fact::record([
+            ("displays", Fact::list(displays)),
+            ("easy_digits", Fact::from(part_1(observed)))
+        ])
+    }
+
+    /// The wiring of each display, as the segments that wires a to g light,
+    /// and the output it reads.
+    fn explain_2(observed: &Vec<(Vec<HashSet<char>>, Vec<HashSet<char>>)>) -> Fact {
+        let displays = observed.iter().enumerate().map(|(ind, line)| {
+            let wiring = find_wiring(line);
+            Fact::record([
+                ("display", Fact::from(ind + 1)),
+                ("wiring", Fact::from(('a'..='g').map(|wire| wiring[&wire]).collect::<String>())),
+                ("output", Fact::from(read_output(line, &wiring)))
+            ])
+        });
+        Fact::record([
+            ("displays", Fact::list(displays)),
+            ("sum", Fact::from(part_2(observed)))
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn decoded_wiring() {
+        let input = parse_input("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf").unwrap();
+        assert_eq!(Day08::explain_1(&input).to_text(), "\
+displays:
+  - display: 1
+    output: ????
+    easy_digits: 0
+easy_digits: 0");
+        // The wiring of the example in the puzzle text, read the other way round.
+        assert_eq!(Day08::explain_2(&input).to_text(), "\
+displays:
+  - display: 1
+    wiring: cfgabde
+    output: 5353
+sum: 5353");
+        let input = parse_input(include_str!("../test2")).unwrap();
+        assert!(Day08::explain_1(&input).to_text().contains("display: 1\n    output: 8??4\n    easy_digits: 2"));
+    }
+}
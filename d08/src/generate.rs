@@ -0,0 +1,49 @@
+use common::{Generate, Generated, Rng};
+use crate::Day08;
+
+const DIGIT_SEGMENTS: [&str; 10] = ["abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg"];
+
+/// The wires lit for `digit`, in random order.
+fn scramble(rng: &mut Rng, wiring: &[char], digit: usize) -> String {
+    let mut wires: Vec<char> = DIGIT_SEGMENTS[digit].chars().map(|c| wiring[c as usize - 'a' as usize]).collect();
+    rng.shuffle(&mut wires);
+    wires.into_iter().collect()
+}
+
+impl Generate for Day08 {
+    const INPUT_SIZE: usize = 200;
+
+    /// `size` displays, each wired up in its own random way. The shown
+    /// numbers are picked first, so both answers are known.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut n_easy = 0;
+        let mut sum = 0;
+        let displays: Vec<String> = (0..size).map(|_| {
+            let mut wiring: Vec<char> = ('a'..='g').collect();
+            rng.shuffle(&mut wiring);
+            let mut order: Vec<usize> = (0..10).collect();
+            rng.shuffle(&mut order);
+            let patterns: Vec<String> = order.iter().map(|digit| scramble(rng, &wiring, *digit)).collect();
+            let shown: Vec<usize> = (0..4).map(|_| rng.below(10)).collect();
+            n_easy += shown.iter().filter(|digit| [1, 4, 7, 8].contains(*digit)).count();
+            sum += shown.iter().fold(0, |acc, digit| acc * 10 + digit);
+            let output: Vec<String> = shown.iter().map(|digit| scramble(rng, &wiring, *digit)).collect();
+            format!("{} | {}", patterns.join(" "), output.join(" "))
+        }).collect();
+        Generated::with_answers(displays.join("\n"), n_easy, sum)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Solution;
+    use super::*;
+
+    #[test]
+    fn test_generated_answers() {
+        let generated = Day08::generate(&mut Rng::new(8), 20);
+        let displays = Day08::parse(&generated.input).unwrap();
+        assert_eq!(generated.part_1, Some(Day08::part_1(&displays).to_string()));
+        assert_eq!(generated.part_2, Some(Day08::part_2(&displays).to_string()));
+    }
+}
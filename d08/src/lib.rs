@@ -0,0 +1,169 @@
+mod explain;
+mod generate;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use itertools::Itertools;
+use common::{ParseError, Solution};
+use common::input::parse_lines;
+use common::parse::split_at;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+fn parse_patterns(line: usize, l: &str, s: &str, n_patterns: usize) -> Result<Vec<HashSet<char>>, ParseError> {
+    let expected = format!("{} patterns of segments a to g", n_patterns);
+    let patterns: Vec<&str> = s.split_whitespace().collect();
+    if let Some(pattern) = patterns.iter().find(|p| p.chars().any(|c| !('a'..='g').contains(&c))) {
+        return Err(ParseError::at(line, l, pattern, &expected))
+    }
+    if patterns.len() != n_patterns {
+        return Err(ParseError::at(line, l, s, &expected))
+    }
+    Ok(patterns.iter().map(|p| p.chars().collect()).collect())
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<(Vec<HashSet<char>>, Vec<HashSet<char>>)>, ParseError> {
+    parse_lines(s,
+        |line, l| {
+            let (p1, p2) = split_at(line, l, l, " | ")?;
+            Ok((
+                parse_patterns(line, l, p1, 10)?,
+                parse_patterns(line, l, p2, 4)?,
+            ))
+        }
+    )
+}
+
+pub fn part_1(observed: &Vec<(Vec<HashSet<char>>, Vec<HashSet<char>>)>) -> usize {
+    let valid_lengths = [2, 3, 4, 7];
+    observed.iter().fold(0,
+        |acc, (_, digs)|
+            acc + digs.iter().filter(|d| valid_lengths.contains(&d.len())).count()
+    )
+}
+
+/// The segments lit by each digit, in the order of the digits.
+fn segs_by_dig() -> Vec<(HashSet<char>, char)> {
+    [
+        ("abcefg".chars().collect(), '0'),
+        ("cf".chars().collect(), '1'),
+        ("acdeg".chars().collect(), '2'),
+        ("acdfg".chars().collect(), '3'),
+        ("bcdf".chars().collect(), '4'),
+        ("abdfg".chars().collect(), '5'),
+        ("abdefg".chars().collect(), '6'),
+        ("acf".chars().collect(), '7'),
+        ("abcdefg".chars().collect(), '8'),
+        ("abcdfg".chars().collect(), '9'),
+        ].into_iter()
+    .collect()
+}
+
+/// The segment each wire lights, found by trying all wirings until every
+/// observed pattern is a digit.
+pub fn find_wiring(line: &(Vec<HashSet<char>>, Vec<HashSet<char>>)) -> HashMap<char, char> {
+    let valid_segs: Vec<_> = segs_by_dig().into_iter().map(|(set, _)| set).collect();
+    let (obs, _) = line;
+    let straight = ['a', 'b', 'c', 'd', 'e', 'f', 'g'];
+    for perm in vec!('a', 'b', 'c', 'd', 'e', 'f', 'g').into_iter().permutations(7) {
+        let translation: HashMap<char, char> = perm.into_iter().zip(straight).collect();
+        let translated_obs: Vec<_> = obs.iter().map(|o| o.iter().map(|c| *translation.get(c).unwrap()).collect::<HashSet<_>>()).collect();
+        if translated_obs.iter().all(|to| valid_segs.contains(to)) {
+            return translation
+        }
+    }
+    panic!("No translation found!!")
+}
+
+/// The four digits of the output, read with `wiring`.
+pub fn read_output(line: &(Vec<HashSet<char>>, Vec<HashSet<char>>), wiring: &HashMap<char, char>) -> String {
+    let segs_by_dig = segs_by_dig();
+    let (_, scrambled) = line;
+    scrambled.iter().map(
+        |sc|
+        {
+            let translated: HashSet<_> = sc.iter().map(|c| *wiring.get(c).unwrap()).collect();
+            segs_by_dig.iter().find(|(seg, _)| translated.len() == seg.len() && translated.difference(seg).count() == 0).unwrap().1
+        }
+    ).collect()
+}
+
+fn find_num(line: &(Vec<HashSet<char>>, Vec<HashSet<char>>)) -> usize {
+    read_output(line, &find_wiring(line)).parse().unwrap()
+}
+
+pub fn part_2(observed: &Vec<(Vec<HashSet<char>>, Vec<HashSet<char>>)>) -> usize {
+    #[cfg(feature = "parallel")]
+    return observed.par_iter().map(find_num).sum();
+    #[cfg(not(feature = "parallel"))]
+    observed.iter().map(find_num).sum()
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<(Vec<HashSet<char>>, Vec<HashSet<char>>)>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<(Vec<HashSet<char>>, Vec<HashSet<char>>)>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(observed: &Vec<(Vec<HashSet<char>>, Vec<HashSet<char>>)>) -> usize {
+        part_1(observed)
+    }
+
+    fn part_2(observed: &Vec<(Vec<HashSet<char>>, Vec<HashSet<char>>)>) -> usize {
+        part_2(observed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        let input = parse_input(include_str!("../test2")).unwrap();
+        assert_eq!(
+            part_1(&input),
+            26
+        );
+    }
+
+    #[test]
+    fn test_2_0() {
+        let input = parse_input("be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe").unwrap();
+        assert_eq!(
+            part_2(&input),
+            8394
+        );
+    }
+
+    #[test]
+    fn test_2_1() {
+        let input = parse_input(include_str!("../test1")).unwrap();
+        assert_eq!(
+            part_2(&input),
+            5353
+        );
+    }
+
+    #[test]
+    fn test_2_2() {
+        let input = parse_input(include_str!("../test2")).unwrap();
+        assert_eq!(
+            part_2(&input),
+            61229
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgh gcbe").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 76, "cefbgh", "4 patterns of segments a to g"));
+        let err = parse_input("be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb fdgacbe cefdb cefbgd gcbe").unwrap_err();
+        assert_eq!(err.expected, "` | `");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+use common::{Generate, Generated, Rng};
+use crate::Day09;
+
+impl Generate for Day09 {
+    const INPUT_SIZE: usize = 100;
+
+    /// A `size` by `size` height map of basins walled in by ridges of 9s.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let rows: Vec<String> = (0..size).map(|_| {
+            (0..size).map(|_| {
+                if rng.chance(0.3) { '9' } else { char::from_digit(rng.below(9) as u32, 10).unwrap() }
+            }).collect()
+        }).collect();
+        Generated::new(rows.join("\n"))
+    }
+}
@@ -0,0 +1,108 @@
+mod generate;
+mod visualize;
+
+use std::collections::HashSet;
+use common::{ParseError, Solution};
+use grid::{Coord, Grid};
+
+pub fn parse_input(s: &str) -> Result<Grid<u32>, ParseError> {
+    Grid::parse_digits(s)
+}
+
+pub fn is_low_point(grid: &Grid<u32>, pos: Coord) -> bool {
+    grid.neighbours_4(pos).all(|adj| grid[adj] > grid[pos])
+}
+
+pub fn get_low_points(grid: &Grid<u32>) -> Vec<Coord> {
+    grid.coords().filter(|pos| is_low_point(grid, *pos)).collect()
+}
+
+pub fn risk_level(grid: &Grid<u32>, pos: Coord) -> u32 {
+    grid[pos] + 1
+}
+
+/// The low point at `pos` and every point that flows down to it.
+pub fn basin(grid: &Grid<u32>, pos: Coord) -> HashSet<Coord> {
+    let mut basin_members: HashSet<Coord> = [pos].into_iter().collect();
+    let mut newly_added: HashSet<Coord> = [pos].into_iter().collect();
+    while !newly_added.is_empty() {
+        let mut new_newly_added: HashSet<Coord> = HashSet::new();
+        for member in &newly_added {
+            let new_adjacent: HashSet<Coord> = grid.neighbours_4(*member).filter(|adj| !basin_members.contains(adj) && grid[*adj] < 9).collect();
+            new_newly_added = new_newly_added.union(&new_adjacent).copied().collect()
+        }
+        newly_added = new_newly_added;
+        basin_members = basin_members.union(&newly_added).copied().collect()
+    }
+    basin_members
+}
+
+pub fn basin_size(grid: &Grid<u32>, pos: Coord) -> usize {
+    basin(grid, pos).len()
+}
+
+pub fn part_1(grid: &Grid<u32>) -> u32 {
+    get_low_points(grid).iter().map(|pos| risk_level(grid, *pos)).sum()
+}
+
+pub fn part_2(grid: &Grid<u32>) -> usize {
+    let low_points = get_low_points(grid);
+    let mut basin_sizes: Vec<_> = low_points.iter().map(|pos| basin_size(grid, *pos)).collect();
+    basin_sizes.sort();
+    basin_sizes.reverse();
+    basin_sizes.iter().take(3).product()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Grid<u32>;
+    type Output1 = u32;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<u32>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(grid: &Grid<u32>) -> u32 {
+        part_1(grid)
+    }
+
+    fn part_2(grid: &Grid<u32>) -> usize {
+        part_2(grid)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn test_part_1() {
+        let grid = parse_input(include_str!("../test")).unwrap();
+        let low_points = get_low_points(&grid);
+        assert_eq!(
+            low_points.iter().map(|pos| risk_level(&grid, *pos)).sum::<u32>(),
+            15
+        )
+    }
+
+    #[test]
+    fn test_part_2() {
+        let grid = parse_input(include_str!("../test")).unwrap();
+        let a2 = part_2(&grid);
+        assert_eq!(
+            a2,
+            1134
+        )
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let grid = parse_input(include_str!("../test")).unwrap();
+        let json = serde_json::to_string(&grid).unwrap();
+        assert!(json.starts_with("[[2,1,9,9,9,4,3,2,1,0],"));
+        let read: Grid<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, grid);
+    }
+}
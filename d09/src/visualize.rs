@@ -0,0 +1,41 @@
+use grid::Grid;
+use render::{Frame, Palette, Visualize};
+use crate::{basin, get_low_points, Day09};
+
+const WALL: u8 = 0;
+const N_BASIN_COLORS: u8 = 6;
+const LARGEST: u8 = N_BASIN_COLORS + 1;
+
+impl Visualize for Day09 {
+    fn palette() -> Palette {
+        Palette::Colors(vec!(
+            [0x20, 0x20, 0x20],
+            [0x1f, 0x4e, 0x79], [0x2e, 0x75, 0xb6], [0x38, 0x57, 0x23],
+            [0x54, 0x82, 0x35], [0x7f, 0x60, 0x00], [0x5b, 0x3a, 0x7a],
+            [0xff, 0xd9, 0x66]
+        ))
+    }
+
+    /// The basins in turns of a few colours, apart from the three largest,
+    /// whose sizes make the answer of part 2, which stand out. The points of
+    /// height 9 between the basins are dark.
+    fn frames(grid: &Grid<u32>) -> Vec<Frame> {
+        let basins: Vec<_> = get_low_points(grid).into_iter().map(|pos| basin(grid, pos)).collect();
+        let mut sizes: Vec<_> = basins.iter().map(|b| b.len()).collect();
+        sizes.sort();
+        let min_largest = sizes.len().checked_sub(3).map_or(0, |ind| sizes[ind]);
+        let mut frame = Frame::new(grid.n_rows(), grid.n_cols(), LARGEST + 1);
+        for (ind, members) in basins.iter().enumerate() {
+            let level = if members.len() >= min_largest { LARGEST } else { 1 + ind as u8 % N_BASIN_COLORS };
+            for pos in members {
+                frame.set(pos.row, pos.col, level)
+            }
+        }
+        for (pos, height) in grid.iter() {
+            if *height == 9 {
+                frame.set(pos.row, pos.col, WALL)
+            }
+        }
+        vec!(frame)
+    }
+}
@@ -0,0 +1,71 @@
+use common::explain::{Explain, Fact};
+use crate::{check_syntax, completion_score, error_score, part_1, part_2, Day10, Syntax};
+
+impl Explain for Day10 {
+    /// The first illegal character of each corrupted line, with its line and
+    /// column, and the closing character that was expected there if any.
+    fn explain_1(lines: &Vec<String>) -> Fact {
+        let corrupted = lines.iter().enumerate().filter_map(|(ind, line)| match check_syntax(line) {
+            Syntax::Corrupted { col, found, expected } => {
+                let mut fields = vec!(
+                    ("line", Fact::from(ind + 1)),
+                    ("column", Fact::from(col)),
+                    ("found", Fact::from(found))
+                );
+                if let Some(expected) = expected {
+                    fields.push(("expected", Fact::from(expected)))
+                }
+                fields.push(("score", Fact::from(error_score(line).unwrap())));
+                Some(Fact::record(fields))
+            },
+            Syntax::Incomplete { .. } => None
+        });
+        Fact::record([
+            ("corrupted", Fact::list(corrupted)),
+            ("total_score", Fact::from(part_1(lines)))
+        ])
+    }
+
+    /// The characters that complete each incomplete line, and the score in
+    /// the middle.
+    fn explain_2(lines: &Vec<String>) -> Fact {
+        let incomplete = lines.iter().enumerate().filter_map(|(ind, line)| match check_syntax(line) {
+            Syntax::Incomplete { completion } => Some(Fact::record([
+                ("line", Fact::from(ind + 1)),
+                ("completion", Fact::from(completion)),
+                ("score", Fact::from(completion_score(line).unwrap()))
+            ])),
+            Syntax::Corrupted { .. } => None
+        });
+        Fact::record([
+            ("incomplete", Fact::list(incomplete)),
+            ("middle_score", Fact::from(part_2(lines)))
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn illegal_characters() {
+        let lines = parse_input(include_str!("../test")).unwrap();
+        let explained = Day10::explain_1(&lines).to_text();
+        assert!(explained.starts_with("\
+corrupted:
+  - line: 3
+    column: 13
+    found: }
+    expected: ]
+    score: 1197
+"), "{}", explained);
+        assert!(explained.ends_with("total_score: 26397"));
+        assert_eq!(Day10::explain_1(&vec!(String::from(")"))).to_json(), "\
+{\"corrupted\": [{\"line\": 1, \"column\": 1, \"found\": \")\", \"score\": 3}], \"total_score\": 3}");
+        let explained = Day10::explain_2(&lines).to_json();
+        assert!(explained.starts_with("{\"incomplete\": [{\"line\": 1, \"completion\": \"}}]])})]\", \"score\": 288957}"), "{}", explained);
+        assert!(explained.ends_with("\"middle_score\": 288957}"));
+    }
+}
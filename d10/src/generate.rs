@@ -0,0 +1,77 @@
+use common::{Generate, Generated, Rng};
+use crate::Day10;
+
+const PAIRS: [(char, char, usize, usize); 4] = [('(', ')', 3, 1), ('[', ']', 57, 2), ('{', '}', 1197, 3), ('<', '>', 25137, 4)];
+
+/// Opens and closes random chunks for about `len` characters, never nesting
+/// deeper than 20 so that completion scores fit. Returns the open chunks.
+fn chunks(rng: &mut Rng, line: &mut String, len: usize) -> Vec<usize> {
+    let mut stack = vec!();
+    for _ in 0..len {
+        if stack.is_empty() || (stack.len() < 20 && rng.chance(0.55)) {
+            let pair = rng.below(4);
+            line.push(PAIRS[pair].0);
+            stack.push(pair)
+        } else {
+            line.push(PAIRS[stack.pop().unwrap()].1)
+        }
+    }
+    stack
+}
+
+impl Generate for Day10 {
+    const INPUT_SIZE: usize = 94;
+
+    /// `size` lines, about half of them corrupted and an odd number of them
+    /// incomplete. The first wrong bracket and the missing brackets are
+    /// known, so both answers are.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let size = size.max(1);
+        let mut incomplete: Vec<bool> = (0..size).map(|ind| ind < (size / 2) | 1).collect();
+        rng.shuffle(&mut incomplete);
+        let mut error_score = 0;
+        let mut completion_scores = vec!();
+        let lines: Vec<String> = incomplete.iter().map(|incomplete| {
+            let mut line = String::new();
+            let len = rng.range(80, 110) as usize;
+            let mut stack = chunks(rng, &mut line, len);
+            if *incomplete {
+                if stack.is_empty() {
+                    line.push('(');
+                    stack.push(0)
+                }
+                completion_scores.push(stack.iter().rev().fold(0, |acc, pair| acc * 5 + PAIRS[*pair].3));
+            } else {
+                let expected = stack.last().copied();
+                let wrong = loop {
+                    let pair = rng.below(4);
+                    if Some(pair) != expected {
+                        break pair
+                    }
+                };
+                line.push(PAIRS[wrong].1);
+                error_score += PAIRS[wrong].2;
+                let len = rng.range(0, 10) as usize;
+                chunks(rng, &mut line, len);
+            }
+            line
+        }).collect();
+        completion_scores.sort();
+        let completion_score = completion_scores[completion_scores.len() / 2];
+        Generated::with_answers(lines.join("\n"), error_score, completion_score)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Solution;
+    use super::*;
+
+    #[test]
+    fn test_generated_answers() {
+        let generated = Day10::generate(&mut Rng::new(10), 30);
+        let lines = Day10::parse(&generated.input).unwrap();
+        assert_eq!(generated.part_1, Some(Day10::part_1(&lines).to_string()));
+        assert_eq!(generated.part_2, Some(Day10::part_2(&lines).to_string()));
+    }
+}
@@ -0,0 +1,148 @@
+mod explain;
+mod generate;
+
+use common::{ParseError, Solution};
+use common::input::parse_lines;
+
+pub fn parse_input(s: &str) -> Result<Vec<String>, ParseError> {
+    parse_lines(s, |line, l| {
+        match l.chars().enumerate().find(|(_, c)| !"()[]{}<>".contains(*c)) {
+            Some((col, c)) => Err(ParseError::new(line, col + 1, &c.to_string(), "a bracket")),
+            None => Ok(String::from(l))
+        }
+    })
+}
+
+/// What is wrong with a line: the first character, counted from 1, that
+/// does not close the last open chunk, or the characters that would close
+/// all chunks left open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Syntax {
+    Corrupted { col: usize, found: char, expected: Option<char> },
+    Incomplete { completion: String }
+}
+
+fn closing(c: char) -> char {
+    match c {
+        '(' => ')',
+        '[' => ']',
+        '{' => '}',
+        '<' => '>',
+        _ => panic!("Invalid character in stack {}", c)
+    }
+}
+
+pub fn check_syntax(s: &str) -> Syntax {
+    let mut stack: Vec<char> = vec!();
+    for (col, c) in s.chars().enumerate() {
+        match c {
+            '(' | '[' | '{' | '<' => stack.push(c),
+            ')' | ']' | '}' | '>' => {
+                let expected = stack.pop().map(closing);
+                if expected != Some(c) {
+                    return Syntax::Corrupted { col: col + 1, found: c, expected }
+                }
+            },
+            _ => panic!("Invalid character in input {}", c as u8)
+        }
+    }
+    Syntax::Incomplete { completion: stack.into_iter().rev().map(closing).collect() }
+}
+
+pub fn error_score(s: &str) -> Option<usize> {
+    match check_syntax(s) {
+        Syntax::Corrupted { found, .. } => Some(match found {
+            ')' => 3,
+            ']' => 57,
+            '}' => 1197,
+            _ => 25137
+        }),
+        Syntax::Incomplete { .. } => None
+    }
+}
+
+pub fn completion_score(s: &str) -> Option<usize> {
+    let Syntax::Incomplete { completion } = check_syntax(s) else {
+        return None
+    };
+    let completion_score = completion.chars().fold(0,
+        |acc, c| {
+            acc * 5 +
+            match c {
+                ')' => 1,
+                ']' => 2,
+                '}' => 3,
+                '>' => 4,
+                _ => panic!("Invalid character in completion {}", c)
+            }
+        }
+    );
+    Some(completion_score)
+}
+
+pub fn part_2(input: &Vec<String>) -> usize {
+    let mut scores: Vec<_> = input.iter().map(|s| completion_score(s)).filter(|score| score.is_some()).map(|score| if let Some(s) = score {s} else {panic!("Should not happen")}).collect();
+    scores.sort();
+    scores[scores.len()/2]
+}
+
+pub fn part_1(input: &Vec<String>) -> usize {
+    input.iter().map(|s| error_score(s)).fold(0, |acc, score| if let Some(score) = score { acc + score } else { acc })
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<String>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(lines: &Vec<String>) -> usize {
+        part_1(lines)
+    }
+
+    fn part_2(lines: &Vec<String>) -> usize {
+        part_2(lines)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn test_part_1() {
+        let inp = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            part_1(&inp),
+            26397
+        )   
+
+    }
+    #[test]
+    fn test_part_2() {
+        let inp = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            part_2(&inp),
+            288957
+        )   
+
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse_input("[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<[]>>( ]").unwrap_err(),
+            ParseError::new(2, 23, " ", "a bracket")
+        )
+    }
+
+    #[test]
+    fn test_unopened() {
+        assert_eq!(error_score(")"), Some(3));
+        assert_eq!(completion_score(">"), None)
+    }
+}
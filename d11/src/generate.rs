@@ -0,0 +1,34 @@
+use common::{Generate, Generated, Rng};
+use crate::{parse_input, tick, Day11};
+
+/// Steps within which a generated grid should flash all at once.
+const MAX_STEPS: usize = 1000;
+
+/// Grids drawn before settling for one that may never flash all at once.
+const MAX_TRIES: usize = 10;
+
+impl Generate for Day11 {
+    const INPUT_SIZE: usize = 10;
+
+    /// A `size` by `size` grid of random energy levels. Not every grid ever
+    /// flashes all at once, so grids are drawn until one does within a
+    /// thousand steps, which makes the second answer known. Large grids
+    /// hardly ever do, and after ten tries the last one is used as it is,
+    /// so part 2 of a large grid may never finish.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut tries = 0;
+        loop {
+            let rows: Vec<String> = (0..size).map(|_| {
+                (0..size).map(|_| char::from_digit(rng.below(10) as u32, 10).unwrap()).collect()
+            }).collect();
+            let input = rows.join("\n");
+            let mut grid = parse_input(&input).unwrap();
+            tries += 1;
+            if let Some(step) = (1..=MAX_STEPS).find(|_| tick(&mut grid) == grid.n_cells()) {
+                return Generated { input, part_1: None, part_2: Some(step.to_string()) }
+            } else if tries == MAX_TRIES {
+                return Generated::new(input)
+            }
+        }
+    }
+}
\ No newline at end of file
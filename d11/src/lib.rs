@@ -0,0 +1,120 @@
+mod generate;
+mod params;
+mod simulate;
+mod visualize;
+
+use common::{ParseError, Solution};
+use common::params::{Configure, Params};
+use grid::Grid;
+
+/// Energy level of each octopus, and whether it has flashed in this step.
+pub fn parse_input(s: &str) -> Result<Grid<(u32, bool)>, ParseError> {
+    Ok(Grid::parse_digits(s)?.map(|level| (*level, false)))
+}
+
+pub fn tick(grid: &mut Grid<(u32, bool)>) -> usize {
+    for pos in grid.coords() {
+        grid[pos].0 += 1
+    }
+    loop {
+        let mut flashed = false;
+        for pos in grid.coords() {
+            if grid[pos].0 > 9 && !grid[pos].1 {
+                grid[pos].1 = true;
+                flashed = true;
+                let adjacent: Vec<_> = grid.neighbours_8(pos).collect();
+                for adj in adjacent {
+                    grid[adj].0 += 1;
+                }
+            }
+        }
+        if !flashed {
+            break
+        }
+    }
+
+    let n_flash = grid.values().filter(|(_, flashed)| *flashed).count();
+
+    for pos in grid.coords() {
+        if grid[pos].1 {
+            grid[pos] = (0, false)
+        }
+    }
+    n_flash
+}
+
+pub fn part_1(grid: &Grid<(u32, bool)>, n_steps: usize) -> usize{
+    let mut grid = grid.clone();
+    (0..n_steps).map(|_| tick(&mut grid)).sum()
+}
+
+pub fn part_2(grid: &Grid<(u32, bool)>) -> usize{
+    let mut grid = grid.clone();
+    let n_cells = grid.n_cells();
+    for n_tick in 1.. {
+        if tick(&mut grid) == n_cells {
+            return n_tick
+        }
+    }
+    0
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Grid<(u32, bool)>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<(u32, bool)>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(grid: &Grid<(u32, bool)>) -> usize {
+        Self::part_1_with(grid, &Params::default())
+    }
+
+    fn part_2(grid: &Grid<(u32, bool)>) -> usize {
+        Self::part_2_with(grid, &Params::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part_1_1() {
+        assert_eq!(
+            part_1(&parse_input(include_str!("../test1")).unwrap(), 1),
+            9
+        )
+    }
+
+    #[test]
+    fn test_part_1_2() {
+        assert_eq!(
+            part_1(&parse_input(include_str!("../test2")).unwrap(), 100),
+            1656
+        )
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(
+            part_2(&parse_input(include_str!("../test2")).unwrap()),
+            195
+        )
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let grid = parse_input(include_str!("../test2")).unwrap();
+        let json = serde_json::to_string(&grid).unwrap();
+        assert!(json.starts_with("[[[5,false],[4,false],"));
+        let read: Grid<(u32, bool)> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, grid);
+        assert_eq!(Day11::part_2(&read), 195);
+    }
+}
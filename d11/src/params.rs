@@ -0,0 +1,31 @@
+use common::params::{Configure, Params};
+use grid::Grid;
+use crate::{part_1, part_2, Day11};
+
+impl Configure for Day11 {
+    /// The steps counted in part 1. Part 2 runs until all octopuses flash.
+    const PARAMS: &'static [(&'static str, usize)] = &[("steps", 100)];
+
+    fn part_1_with(grid: &Grid<(u32, bool)>, params: &Params) -> usize {
+        part_1(grid, Self::param(params, "steps"))
+    }
+
+    fn part_2_with(grid: &Grid<(u32, bool)>, _params: &Params) -> usize {
+        part_2(grid)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn steps() {
+        let grid = parse_input(include_str!("../test2")).unwrap();
+        let mut params = Params::default();
+        assert_eq!(Day11::part_1_with(&grid, &params), 1656);
+        params.set("steps=10").unwrap();
+        assert_eq!(Day11::part_1_with(&grid, &params), 204);
+    }
+}
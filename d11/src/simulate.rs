@@ -0,0 +1,91 @@
+use common::params::{Configure, Params};
+use common::simulate::Simulate;
+use grid::Grid;
+use crate::{tick, Day11};
+
+/// The octopuses after some steps, with how often they flashed.
+#[derive(Clone)]
+pub struct Octopuses {
+    grid: Grid<(u32, bool)>,
+    part: usize,
+    /// The steps part 1 runs for.
+    part_1_steps: usize,
+    steps: usize,
+    flashes: usize,
+    flashed: usize
+}
+
+impl Simulate for Day11 {
+    type State = Octopuses;
+
+    /// Part 1 runs for the steps of `params`, 100 by default, and part 2
+    /// until all octopuses flash at once.
+    fn start(grid: &Grid<(u32, bool)>, part: usize, params: &Params) -> Octopuses {
+        Octopuses { grid: grid.clone(), part, part_1_steps: Self::param(params, "steps"), steps: 0, flashes: 0, flashed: 0 }
+    }
+
+    fn step(octopuses: &Octopuses) -> Option<Octopuses> {
+        let over = match octopuses.part {
+            1 => octopuses.steps == octopuses.part_1_steps,
+            _ => octopuses.flashed == octopuses.grid.n_cells()
+        };
+        if over {
+            return None
+        }
+        let mut grid = octopuses.grid.clone();
+        let flashed = tick(&mut grid);
+        Some(Octopuses { grid, steps: octopuses.steps + 1, flashes: octopuses.flashes + flashed, flashed, ..*octopuses })
+    }
+
+    fn draw(octopuses: &Octopuses) -> Vec<String> {
+        octopuses.grid.rows().map(|row| row.iter().map(|(energy, _)| char::from_digit(*energy, 10).unwrap()).collect()).collect()
+    }
+
+    /// All flashes so far and those of the last step.
+    fn counters(octopuses: &Octopuses) -> Vec<(&'static str, usize)> {
+        vec!(("flashes", octopuses.flashes), ("flashed", octopuses.flashed))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse_input, part_1, part_2};
+
+    #[test]
+    fn steps() {
+        let grid = parse_input(include_str!("../test2")).unwrap();
+        let mut octopuses = Day11::start(&grid, 1, &Params::default());
+        assert_eq!(Day11::draw(&octopuses)[0], "5483143223");
+        let mut n_steps = 0;
+        while let Some(next) = Day11::step(&octopuses) {
+            octopuses = next;
+            n_steps += 1;
+        }
+        assert_eq!(n_steps, 100);
+        assert_eq!(Day11::counters(&octopuses)[0], ("flashes", part_1(&grid, 100)));
+        let mut octopuses = Day11::start(&grid, 2, &Params::default());
+        let mut n_steps = 0;
+        while let Some(next) = Day11::step(&octopuses) {
+            octopuses = next;
+            n_steps += 1;
+        }
+        assert_eq!(n_steps, part_2(&grid));
+        assert_eq!(Day11::draw(&octopuses)[9], "0000000000");
+    }
+
+    #[test]
+    fn steps_of_params() {
+        let grid = parse_input(include_str!("../test2")).unwrap();
+        let mut params = Params::default();
+        params.set("steps=10").unwrap();
+        let mut octopuses = Day11::start(&grid, 1, &params);
+        let mut n_steps = 0;
+        while let Some(next) = Day11::step(&octopuses) {
+            octopuses = next;
+            n_steps += 1;
+        }
+        assert_eq!(n_steps, 10);
+        assert_eq!(Day11::counters(&octopuses)[0], ("flashes", 204));
+    }
+}
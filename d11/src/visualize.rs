@@ -0,0 +1,41 @@
+use grid::Grid;
+use render::{Frame, Visualize};
+use crate::{tick, Day11};
+
+/// Octopuses that never all flash at once are only followed this far.
+const MAX_TICKS: usize = 1000;
+
+impl Visualize for Day11 {
+    /// The energy of the octopuses before the first step and after each
+    /// `tick` until all of them flash at once. Those that just flashed, and
+    /// so are at 0, are brightest; the others are the brighter the more
+    /// energy they have.
+    fn frames(grid: &Grid<(u32, bool)>) -> Vec<Frame> {
+        let to_frame = |grid: &Grid<(u32, bool)>| Frame::from_grid(grid, 10, |(energy, _)| ((energy + 9) % 10) as u8);
+        let mut grid = grid.clone();
+        let mut frames = vec!(to_frame(&grid));
+        for _ in 0..MAX_TICKS {
+            let n_flash = tick(&mut grid);
+            frames.push(to_frame(&grid));
+            if n_flash == grid.n_cells() {
+                break
+            }
+        }
+        frames
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse_input, part_2};
+
+    #[test]
+    fn frames() {
+        let grid = parse_input(include_str!("../test2")).unwrap();
+        let frames = Day11::frames(&grid);
+        assert_eq!(frames.len(), part_2(&grid) + 1);
+        let last = frames.last().unwrap();
+        assert!((0..last.n_rows()).all(|row| (0..last.n_cols()).all(|col| last.get(row, col) == 9)));
+    }
+}
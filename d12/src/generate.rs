@@ -0,0 +1,44 @@
+use common::{Generate, Generated, Rng};
+use crate::Day12;
+
+/// A distinct name of two or more letters for cave `ind`.
+fn cave_name(ind: usize) -> String {
+    let mut name = String::new();
+    let mut n = ind + 26;
+    while n > 0 {
+        name.insert(0, (b'a' + (n % 26) as u8) as char);
+        n /= 26
+    }
+    name
+}
+
+impl Generate for Day12 {
+    const INPUT_SIZE: usize = 12;
+
+    /// A cave system of `size` caves besides start and end, a third of them
+    /// large. Large caves are never connected to each other, as there would
+    /// be endless routes between them.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let n_large = size / 3;
+        let caves: Vec<String> = (0..size).map(|ind| {
+            if ind < n_large { cave_name(ind).to_uppercase() } else { cave_name(ind) }
+        }).collect();
+        let mut connections = vec!();
+        for ind in 0..size {
+            for _ in 0..2 {
+                let other = rng.below(size);
+                if other != ind && (ind >= n_large || other >= n_large) {
+                    connections.push(format!("{}-{}", caves[ind], caves[other]))
+                }
+            }
+        }
+        for end in ["start", "end"] {
+            for _ in 0..rng.range(1, 3) {
+                let cave = rng.choose(&caves);
+                connections.push(if rng.chance(0.5) { format!("{}-{}", end, cave) } else { format!("{}-{}", cave, end) })
+            }
+        }
+        rng.shuffle(&mut connections);
+        Generated::new(connections.join("\n"))
+    }
+}
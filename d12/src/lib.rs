@@ -0,0 +1,238 @@
+mod generate;
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use common::{ParseError, Solution};
+use common::count::{Count, Counted, Overflow};
+use common::input::parse_lines;
+use common::params::Params;
+use common::parse::split_at;
+use search::{all_paths, count_paths, count_paths_as, SearchState};
+
+pub fn parse_input(s: &str) -> Result<HashMap<String, HashSet<String>>, ParseError> {
+    fn insert_path(a: &String, b: &String, path_colls: &mut HashMap<String, HashSet<String>>) {
+        if let Some(hs) = path_colls.get_mut(a) {
+            hs.insert(b.clone());
+        } else {
+            let mut new_set: HashSet<String> = HashSet::new();
+            new_set.insert(b.clone());
+            path_colls.insert(a.clone(), new_set);
+        }
+    }
+
+    let paths = parse_lines(s, |n_line, line| {
+        let (a, b) = split_at(n_line, line, line, "-")?;
+        for cave in [a, b] {
+            if cave.is_empty() || !cave.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(ParseError::at(n_line, line, cave, "a cave name"))
+            }
+        }
+        Ok((String::from(a), String::from(b)))
+    })?;
+    let mut path_colls: HashMap<String, HashSet<String>> = HashMap::new();
+    for (a, b) in paths {
+        if &a != "end" && &b != "start" {
+            insert_path(&a, &b, &mut path_colls);
+        }
+        if &a != "start" && &b != "end" {
+            insert_path(&b, &a, &mut path_colls);
+        }
+    }
+    Ok(path_colls)
+}
+
+fn is_large_cave(c: &str) -> bool {
+    c.chars().next().unwrap().is_uppercase()
+}
+
+/// A route through the caves so far: the cave it has reached, the small caves
+/// it has been to, and whether it may still visit one of those a second time.
+#[derive(Clone)]
+struct Route<'a> {
+    path_colls: &'a HashMap<String, HashSet<String>>,
+    cave: &'a str,
+    visited_small: BTreeSet<&'a str>,
+    may_revisit: bool
+}
+
+impl<'a> Route<'a> {
+    fn start(path_colls: &'a HashMap<String, HashSet<String>>, allow_multiple: bool) -> Self {
+        if !path_colls.contains_key("start") {
+            panic!("Could not find start.")
+        }
+        Route { path_colls, cave: "start", visited_small: BTreeSet::new(), may_revisit: allow_multiple }
+    }
+}
+
+impl<'a> SearchState for Route<'a> {
+    type Key = (&'a str, BTreeSet<&'a str>, bool);
+
+    fn key(&self) -> Self::Key {
+        (self.cave, self.visited_small.clone(), self.may_revisit)
+    }
+
+    fn neighbours(&self) -> Vec<(Self, usize)> {
+        let Some(destinations) = self.path_colls.get(self.cave) else {
+            return vec!()
+        };
+        destinations.iter().filter_map(|dest| {
+            let mut next = Route { cave: dest, ..self.clone() };
+            if !is_large_cave(dest) && !next.visited_small.insert(dest) {
+                if !self.may_revisit {
+                    return None
+                }
+                next.may_revisit = false;
+            }
+            Some((next, 1))
+        }).collect()
+    }
+
+    fn is_goal(&self) -> bool {
+        self.cave == "end"
+    }
+}
+
+/// All routes from start to end. With `allow_multiple` a single small cave
+/// may be visited twice.
+pub fn find_routes(path_colls: &HashMap<String, HashSet<String>>, allow_multiple: bool) -> Vec<Vec<String>> {
+    all_paths(Route::start(path_colls, allow_multiple)).into_iter().map(
+        |route| route.iter().map(|r| String::from(r.cave)).collect()
+    ).collect()
+}
+
+/// Number of routes `find_routes` would find, without listing them.
+pub fn count_routes(path_colls: &HashMap<String, HashSet<String>>, allow_multiple: bool) -> Result<usize, Overflow> {
+    count_paths(Route::start(path_colls, allow_multiple))
+}
+
+/// Like `count_routes`, counting in `C`.
+pub fn count_routes_as<C: Count>(path_colls: &HashMap<String, HashSet<String>>, allow_multiple: bool) -> Result<C, Overflow> {
+    count_paths_as(Route::start(path_colls, allow_multiple))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = HashMap<String, HashSet<String>>;
+    type Output1 = Result<usize, Overflow>;
+    type Output2 = Result<usize, Overflow>;
+
+    fn parse(input: &str) -> Result<HashMap<String, HashSet<String>>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(path_colls: &HashMap<String, HashSet<String>>) -> Result<usize, Overflow> {
+        count_routes(path_colls, false)
+    }
+
+    fn part_2(path_colls: &HashMap<String, HashSet<String>>) -> Result<usize, Overflow> {
+        count_routes(path_colls, true)
+    }
+}
+
+impl Counted for Day12 {
+    fn part_1_as<C: Count>(path_colls: &HashMap<String, HashSet<String>>, _params: &Params) -> Result<C, Overflow> {
+        count_routes_as(path_colls, false)
+    }
+
+    fn part_2_as<C: Count>(path_colls: &HashMap<String, HashSet<String>>, _params: &Params) -> Result<C, Overflow> {
+        count_routes_as(path_colls, true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_p1_1() {
+        let path_colls = parse_input(include_str!("../test1")).unwrap();
+        let paths = find_routes(&path_colls, false);
+        assert_eq!(
+            paths.len(),
+            10
+        )
+    }
+
+    #[test]
+    fn test_p1_2() {
+        let path_colls = parse_input(include_str!("../test2")).unwrap();
+        let paths = find_routes(&path_colls, false);
+        assert_eq!(
+            paths.len(),
+            19
+        )
+    }
+
+    #[test]
+    fn test_p1_3() {
+        let path_colls = parse_input(include_str!("../test3")).unwrap();
+        let paths = find_routes(&path_colls, false);
+        assert_eq!(
+            paths.len(),
+            226
+        )
+    }
+
+    #[test]
+    fn test_p2_1() {
+        let path_colls = parse_input(include_str!("../test1")).unwrap();
+        let paths = find_routes(&path_colls, true);
+        assert_eq!(
+            paths.len(),
+            36
+        )
+    }
+
+    #[test]
+    fn test_p2_2() {
+        let path_colls = parse_input(include_str!("../test2")).unwrap();
+        let paths = find_routes(&path_colls, true);
+        assert_eq!(
+            paths.len(),
+            103
+        )
+    }
+
+    #[test]
+    fn test_p2_3() {
+        let path_colls = parse_input(include_str!("../test3")).unwrap();
+        let paths = find_routes(&path_colls, true);
+        assert_eq!(
+            paths.len(),
+            3509
+        )
+    }
+
+    #[test]
+    fn test_count_routes() {
+        let path_colls = parse_input(include_str!("../test3")).unwrap();
+        assert_eq!(count_routes(&path_colls, false), Ok(226));
+        assert_eq!(count_routes(&path_colls, true), Ok(3509));
+    }
+
+    #[test]
+    fn many_routes() {
+        // A chain of small caves, each reached from the one before through
+        // either of two large caves, doubles the routes with every link.
+        let name = |ind: usize| format!("{}{}", (b'a' + (ind / 26) as u8) as char, (b'a' + (ind % 26) as u8) as char);
+        let mut lines = vec!();
+        let mut from = String::from("start");
+        for ind in 0..70 {
+            for large in ["X", "Y"] {
+                let large = format!("{}{}", large, name(ind).to_uppercase());
+                lines.push(format!("{}-{}", from, large));
+                lines.push(format!("{}-{}", large, name(ind)));
+            }
+            from = name(ind);
+        }
+        lines.push(format!("{}-end", from));
+        let path_colls = parse_input(&lines.join("\n")).unwrap();
+        assert_eq!(count_routes_as::<u64>(&path_colls, false), Err(Overflow));
+        assert_eq!(Day12::part_1(&path_colls), Err(Overflow));
+        assert_eq!(count_routes_as::<u128>(&path_colls, false), Ok(1 << 70));
+        assert_eq!(Day12::part_1_as::<u128>(&path_colls, &Params::default()), Ok(1 << 70));
+        assert!(count_routes_as::<u128>(&path_colls, true).unwrap() > 1 << 70);
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::BTreeSet;
+use common::{Generate, Generated, Rng};
+use common::geometry::Point2;
+use crate::Day13;
+
+/// Draws `points` the way part 2 shows them.
+fn render(points: &BTreeSet<Point2<usize>>) -> String {
+    let max_x = points.iter().map(|pt| pt.x).max().unwrap();
+    let max_y = points.iter().map(|pt| pt.y).max().unwrap();
+    (0..=max_y).map(|y| {
+        (0..=max_x).map(|x| if points.contains(&Point2::new(x, y)) { '#' } else { '.' }).collect::<String>()
+    }).collect::<Vec<_>>().join("\n")
+}
+
+impl Generate for Day13 {
+    const INPUT_SIZE: usize = 1000;
+
+    /// About `size` points that twelve folds turn into a random 40 by 6
+    /// code. The input is built by unfolding the code, so the points left
+    /// after the first fold and the code itself are both known.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let n_folds = 12;
+        let mut points: BTreeSet<Point2<usize>> = BTreeSet::new();
+        while points.is_empty() {
+            points = (0..40).flat_map(|x| (0..6).map(move |y| Point2::new(x, y))).filter(|_| rng.chance(0.4)).collect();
+        }
+        let code = render(&points);
+        // Each unfolding keeps, mirrors or doubles every point, so this
+        // chance of doubling makes the points grow to about `size`.
+        let growth = (size as f64 / points.len() as f64).powf(1.0 / n_folds as f64);
+        let p_both = (growth - 1.0).clamp(0.0, 1.0);
+        let mut folds = vec!();
+        let mut n_after_first = 0;
+        for _ in 0..n_folds {
+            n_after_first = points.len();
+            let along_x = rng.chance(0.5);
+            let extent = points.iter().map(|pt| if along_x { pt.x } else { pt.y }).max().unwrap() + 1;
+            let coord = extent + rng.below(2);
+            let mirror = |pt: Point2<usize>| if along_x { Point2::new(2 * coord - pt.x, pt.y) } else { Point2::new(pt.x, 2 * coord - pt.y) };
+            points = points.into_iter().flat_map(|pt| {
+                if rng.chance(p_both) {
+                    vec!(pt, mirror(pt))
+                } else if rng.chance(0.5) {
+                    vec!(mirror(pt))
+                } else {
+                    vec!(pt)
+                }
+            }).collect();
+            folds.push(format!("fold along {}={}", if along_x { 'x' } else { 'y' }, coord));
+        }
+        folds.reverse();
+        let mut lines: Vec<String> = points.iter().map(|pt| format!("{},{}", pt.x, pt.y)).collect();
+        rng.shuffle(&mut lines);
+        lines.push(String::new());
+        lines.append(&mut folds);
+        Generated::with_answers(lines.join("\n"), n_after_first, code)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Solution;
+    use super::*;
+
+    #[test]
+    fn test_generated_answers() {
+        let generated = Day13::generate(&mut Rng::new(13), 500);
+        let input = Day13::parse(&generated.input).unwrap();
+        assert_eq!(generated.part_1, Some(Day13::part_1(&input).to_string()));
+        assert_eq!(generated.part_2, Some(Day13::part_2(&input)));
+    }
+}
@@ -0,0 +1,148 @@
+mod generate;
+mod visualize;
+
+use std::collections::HashSet;
+use common::{ParseError, Solution};
+use common::geometry::Point2;
+use common::input::{end_of_input, parse_lines, sections, unexpected_section};
+use common::parse::{parse_at, split_at};
+
+pub fn parse_input(s: &str) -> Result<(HashSet<Point2<usize>>, Vec<(char, usize)>), ParseError> {
+    fn parse_point(line: usize, l: &str) -> Result<Point2<usize>, ParseError> {
+        let (x, y) = split_at(line, l, l, ",")?;
+        Ok(Point2::new(parse_at(line, l, x, "a coordinate")?, parse_at(line, l, y, "a coordinate")?))
+    }
+
+    fn parse_fold(line: usize, l: &str) -> Result<(char, usize), ParseError> {
+        let fold = l.strip_prefix("fold along ").ok_or_else(|| ParseError::at(line, l, l, "`fold along`"))?;
+        let (axis, coord) = split_at(line, l, fold, "=")?;
+        let axis = match axis {
+            "x" => 'x',
+            "y" => 'y',
+            _ => return Err(ParseError::at(line, l, axis, "x or y"))
+        };
+        Ok((axis, parse_at(line, l, coord, "a coordinate")?))
+    }
+
+    let sections = sections(s);
+    let points = match sections.first() {
+        Some(section) => section.parse(|s| parse_lines(s, parse_point))?.into_iter().collect(),
+        None => return Err(ParseError::new(1, 1, "", "a point"))
+    };
+    let folds = match sections.get(1) {
+        Some(section) => section.parse(|s| parse_lines(s, parse_fold))?,
+        None => return Err(end_of_input(s, "an empty line and folds"))
+    };
+    if let Some(extra) = sections.get(2) {
+        return Err(unexpected_section(extra))
+    }
+    Ok((points, folds))
+}
+
+pub fn render_points(points: &HashSet<Point2<usize>>) -> String {
+    let max_x = points.iter().map(|pt| pt.x).max().unwrap();
+    let max_y = points.iter().map(|pt| pt.y).max().unwrap();
+    let mut p_row: Vec<char> = vec!();
+    p_row.resize(max_x + 1, '.');
+    let mut p_vec: Vec<Vec<char>> = vec!();
+    p_vec.resize(max_y + 1, p_row);
+    for pt in points {
+        p_vec[pt.y][pt.x] = '#'
+    } 
+    p_vec.into_iter().map(|row| row.into_iter().collect::<String>()).collect::<Vec<_>>().join("\n")
+}
+
+pub fn fold(points: &HashSet<Point2<usize>>, axis: char, coord: usize) -> HashSet<Point2<usize>> {
+    let mut new_points: HashSet<Point2<usize>> = HashSet::new();
+    for pt in points {
+        match axis {
+            'x' => {
+                if pt.x < coord {
+                    new_points.insert(*pt);
+                } else {
+                    let new_x = 2 * coord - pt.x;
+                    new_points.insert(Point2::new(new_x, pt.y));
+                }
+            }
+            ,
+            'y' => {
+                if pt.y < coord {
+                    new_points.insert(*pt);
+                } else {
+                    let new_y = 2 * coord - pt.y;
+                    new_points.insert(Point2::new(pt.x, new_y));
+                }
+            },
+            _ => panic!("Illegal axis {}", axis)
+        }
+    }
+    new_points
+}
+
+pub fn part_1(points: &HashSet<Point2<usize>>, folds: &Vec<(char, usize)>) -> usize {
+    let new_points = fold(points, folds[0].0, folds[0].1);
+    new_points.len()
+}
+
+pub fn part_2(points: &HashSet<Point2<usize>>, folds: &Vec<(char, usize)>) -> String {
+    let mut points = points.clone();
+    for f in folds {
+        points = fold(&points, f.0, f.1);
+    }
+    render_points(&points)
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = (HashSet<Point2<usize>>, Vec<(char, usize)>);
+    type Output1 = usize;
+    type Output2 = String;
+
+    fn parse(input: &str) -> Result<(HashSet<Point2<usize>>, Vec<(char, usize)>), ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &(HashSet<Point2<usize>>, Vec<(char, usize)>)) -> usize {
+        let (points, folds) = input;
+        part_1(points, folds)
+    }
+
+    fn part_2(input: &(HashSet<Point2<usize>>, Vec<(char, usize)>)) -> String {
+        let (points, folds) = input;
+        part_2(points, folds)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        let (points, folds) = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            part_1(&points, &folds),
+            17
+        )
+    }
+
+    #[test]
+    fn test_part_2() {
+        let (points, folds) = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            part_2(&points, &folds),
+            "#####\n#...#\n#...#\n#...#\n#####"
+        )
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("6,10\n0,14\n9 10\n\nfold along y=7").unwrap_err();
+        assert_eq!(err, ParseError::new(3, 1, "9 10", "`,`"));
+        let err = parse_input("6,10\n0,14\n\nfold along y=7\nfold along z=5").unwrap_err();
+        assert_eq!(err, ParseError::new(5, 12, "z", "x or y"));
+        let err = parse_input("6,10\n\nfold y=7").unwrap_err();
+        assert_eq!(err, ParseError::new(3, 1, "fold y=7", "`fold along`"));
+    }
+}
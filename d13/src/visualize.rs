@@ -0,0 +1,58 @@
+use std::collections::HashSet;
+use common::geometry::Point2;
+use render::{Frame, Palette, Visualize};
+use crate::{fold, Day13};
+
+fn to_frame(points: &HashSet<Point2<usize>>, width: usize, height: usize) -> Frame {
+    let mut frame = Frame::new(height, width, 2);
+    for pt in points {
+        frame.set(pt.y, pt.x, 1)
+    }
+    frame
+}
+
+impl Visualize for Day13 {
+    fn palette() -> Palette {
+        Palette::Gray
+    }
+
+    /// The dots on the paper before folding and after each fold. The paper
+    /// shrinks to the side kept by the fold.
+    fn frames(input: &(HashSet<Point2<usize>>, Vec<(char, usize)>)) -> Vec<Frame> {
+        let (points, folds) = input;
+        let mut points = points.clone();
+        let mut width = points.iter().map(|pt| pt.x + 1).max().unwrap();
+        let mut height = points.iter().map(|pt| pt.y + 1).max().unwrap();
+        let mut frames = vec!(to_frame(&points, width, height));
+        for (axis, coord) in folds {
+            points = fold(&points, *axis, *coord);
+            match axis {
+                'x' => width = *coord,
+                _ => height = *coord
+            }
+            frames.push(to_frame(&points, width, height))
+        }
+        frames
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse_input, part_2};
+
+    #[test]
+    fn frames() {
+        let input = parse_input(include_str!("../test")).unwrap();
+        let frames = Day13::frames(&input);
+        assert_eq!(frames.len(), input.1.len() + 1);
+        let last = frames.last().unwrap();
+        let drawn: Vec<String> = (0..last.n_rows()).map(|row|
+            (0..last.n_cols()).map(|col| if last.get(row, col) == 1 { '#' } else { '.' }).collect()
+        ).collect();
+        for (drawn, expected) in drawn.iter().zip(part_2(&input.0, &input.1).lines()) {
+            assert!(drawn.starts_with(expected))
+        }
+        assert_eq!((frames[0].n_rows(), frames[0].n_cols()), (15, 11));
+    }
+}
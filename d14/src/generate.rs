@@ -0,0 +1,22 @@
+use common::{Generate, Generated, Rng};
+use crate::Day14;
+
+impl Generate for Day14 {
+    const INPUT_SIZE: usize = 20;
+
+    /// A template of `size` elements out of ten, with a rule for every pair
+    /// of those elements.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut letters: Vec<char> = ('A'..='Z').collect();
+        rng.shuffle(&mut letters);
+        let elements = &letters[..10];
+        let template: String = (0..size).map(|_| *rng.choose(elements)).collect();
+        let mut lines = vec!(template, String::new());
+        for first in elements {
+            for second in elements {
+                lines.push(format!("{}{} -> {}", first, second, rng.choose(elements)))
+            }
+        }
+        Generated::new(lines.join("\n"))
+    }
+}
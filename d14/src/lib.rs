@@ -0,0 +1,318 @@
+mod generate;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use itertools::Itertools;
+use common::{ParseError, Solution};
+use common::count::{Count, Counted, Overflow};
+use common::params::Params;
+use common::input::{parse_lines, parse_one_line, sections, unexpected_section};
+use common::parse::split_at;
+
+fn is_element(s: &str, n_elements: usize) -> bool {
+    s.chars().count() == n_elements && s.chars().all(|c| c.is_ascii_uppercase())
+}
+
+fn parse_template(poly: &str) -> Result<String, ParseError> {
+    if poly.is_empty() || !poly.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(ParseError::at(1, poly, poly, "a polymer template"))
+    }
+    Ok(String::from(poly))
+}
+
+fn parse_insertion(line: usize, s: &str) -> Result<((char, char), char), ParseError> {
+    let (from, to) = split_at(line, s, s, " -> ")?;
+    if !is_element(from, 2) {
+        return Err(ParseError::at(line, s, from, "a pair of elements"))
+    }
+    if !is_element(to, 1) {
+        return Err(ParseError::at(line, s, to, "an element"))
+    }
+    let mut from = from.chars();
+    Ok(((from.next().unwrap(), from.next().unwrap()), to.chars().next().unwrap()))
+}
+
+pub fn parse_input(s: &str) -> Result<(String, HashMap<(char, char), char>), ParseError> {
+    let sections = sections(s);
+    let poly = match sections.first() {
+        Some(section) => section.parse(|s| parse_one_line(s, parse_template))?,
+        None => return Err(ParseError::new(1, 1, "", "a polymer template"))
+    };
+    let insertions = match sections.get(1) {
+        Some(section) => section.parse(|s| parse_lines(s, parse_insertion))?.into_iter().collect(),
+        None => HashMap::new()
+    };
+    if let Some(extra) = sections.get(2) {
+        return Err(unexpected_section(extra))
+    }
+    Ok((poly, insertions))
+}
+
+fn insert(poly: &String, insertions: &HashMap<(char, char), char>) -> String {
+    let mut new_poly: Vec<char> = vec!();
+    let mut it = poly.chars().peekable();
+    while let Some(c) = it.next() {
+        new_poly.push(c);
+        if let Some(next_c) = it.peek() {
+            new_poly.push(*insertions.get(&(c, *next_c)).unwrap());
+        }
+    }
+    new_poly.into_iter().collect()
+}
+
+fn insert_n(poly: &String, insertions: &HashMap<(char, char), char>, n: usize) -> String {
+    let mut poly = poly.clone();
+    for _ in 0..n {
+        poly = insert(&poly, insertions);
+    }
+    String::from(&poly[1..(poly.len()-1)])
+}
+
+fn pairify(s: &str) -> Vec<(char, char)> {
+    let mut pairs: Vec<(char, char)> = vec!();
+    let mut it = s.chars().peekable();
+    while let Some(c) = it.next() {
+        if let Some(next_c) = it.peek() {
+            pairs.push((c, *next_c));
+        }
+    }
+    pairs
+}
+
+fn get_expanded_counts_recursive(poly: &String, expansions: &HashMap<(char, char), (String, HashMap<char, usize>)>) -> HashMap<char, usize> {
+    let poly_pairs = pairify(poly);
+    let mut all_pairs: Vec<(char, char)> = vec!();
+    for this_pair in poly_pairs  {
+        let (sub_str, _) = expansions.get(&this_pair).unwrap();
+        let mut sub_pairs = pairify(sub_str);
+        all_pairs.push((this_pair.0, sub_pairs[0].0));
+        let last_pair = (sub_pairs.last().unwrap().1, this_pair.1);
+        all_pairs.append(&mut sub_pairs);
+        all_pairs.push(last_pair);
+    }
+
+    let mut expanded_counts = HashMap::new();
+    expanded_counts.insert(all_pairs[0].0, 1);
+    for sp in all_pairs {
+        let (_, sp_counts) = expansions.get(&sp).unwrap();
+        for (c, count) in sp_counts {
+            if let Some(x) = expanded_counts.get(c) {
+                expanded_counts.insert(*c, x + count);
+            } else {
+                expanded_counts.insert(*c, *count);
+            }
+        }
+        if let Some(x) = expanded_counts.get(&sp.1) {
+            expanded_counts.insert(sp.1, x + 1);
+        } else {
+            expanded_counts.insert(sp.1, 1);
+        }
+    }
+    expanded_counts
+}
+
+fn get_expansions(insertions: &HashMap<(char, char), char>, n_steps: usize) -> HashMap<(char, char), (String, HashMap<char, usize>)> {
+    let mut all_present_chars: Vec<char> = insertions.iter().flat_map(|((c1, c2), c3)| vec!(*c1, *c2, *c3)).collect();
+    all_present_chars.sort();
+    all_present_chars.dedup();
+    let mut all_char_doubles: Vec<_> = all_present_chars.iter().map(|c| (*c, *c)).collect();
+    let all_char_combs: Vec<_> = all_present_chars.iter().combinations(2).collect();
+    let mut all_char_pairs: Vec<_> = all_char_combs.into_iter().flat_map(|v| vec!((*v[0], *v[1]), (*v[1], *v[0]))).collect();
+    all_char_pairs.append(&mut all_char_doubles);
+    let expansions: HashMap<(char, char), (String, HashMap<char, usize>)> = 
+    all_char_pairs.into_iter().map(
+        |(c1, c2)| {
+        let expansion =  insert_n( &format!("{}{}", c1, c2) , insertions, n_steps);
+        let mut exp_vec: Vec<char> = expansion.chars().collect();
+        exp_vec.sort();
+        let frequencies: HashMap<char, usize>  = exp_vec.into_iter().chunk_by(|c| *c).into_iter().map(|(k, v)| (k, v.count())).collect();
+        ((c1, c2), (expansion, frequencies))
+    }).collect();
+    expansions
+}
+
+/// The difference between the most and least common element after
+/// `n_steps`, which has to be even and at least 4 as each pair is expanded
+/// by half the steps, twice over.
+pub fn solve(poly: &String, insertions: &HashMap<(char, char), char>, n_steps: usize) -> usize {
+    let expansions = get_expansions(insertions, n_steps/2);
+    let mut poly_counts: Vec<_> =  get_expanded_counts_recursive(poly, &expansions).into_iter().collect();
+    
+    poly_counts.sort_by_key(|x| x.1);
+    let poly_min = poly_counts.first().unwrap().1;
+    let poly_max = poly_counts.last().unwrap().1;
+    
+    poly_max - poly_min
+}
+
+/// The number of each element after `n_steps`, counted in `C`. Only the
+/// pairs of neighbouring elements are counted, not the whole polymer, so
+/// any number of steps takes little time. A pair without a rule stays as
+/// it is.
+pub fn count_elements_as<C: Count>(poly: &str, insertions: &HashMap<(char, char), char>, n_steps: usize) -> Result<HashMap<char, C>, Overflow> {
+    fn add<K: Eq + Hash, C: Count>(counts: &mut HashMap<K, C>, key: K, n: &C) -> Result<(), Overflow> {
+        let count = counts.entry(key).or_insert_with(C::zero);
+        *count = count.plus(n)?;
+        Ok(())
+    }
+
+    let mut pairs: HashMap<(char, char), C> = HashMap::new();
+    for pair in pairify(poly) {
+        add(&mut pairs, pair, &C::one())?
+    }
+    for _ in 0..n_steps {
+        let mut new_pairs = HashMap::new();
+        for ((c1, c2), n) in &pairs {
+            match insertions.get(&(*c1, *c2)) {
+                Some(c) => {
+                    add(&mut new_pairs, (*c1, *c), n)?;
+                    add(&mut new_pairs, (*c, *c2), n)?
+                },
+                None => add(&mut new_pairs, (*c1, *c2), n)?
+            }
+        }
+        pairs = new_pairs
+    }
+    // Each element starts a pair, except the last, which never changes.
+    let mut counts = HashMap::new();
+    for ((c, _), n) in &pairs {
+        add(&mut counts, *c, n)?
+    }
+    if let Some(last) = poly.chars().last() {
+        add(&mut counts, last, &C::one())?
+    }
+    Ok(counts)
+}
+
+/// Like `solve`, for any number of steps, counted in `C`.
+pub fn solve_as<C: Count>(poly: &str, insertions: &HashMap<(char, char), char>, n_steps: usize) -> Result<C, Overflow> {
+    let counts = count_elements_as::<C>(poly, insertions, n_steps)?;
+    match (counts.values().max(), counts.values().min()) {
+        (Some(max), Some(min)) => max.minus(min),
+        _ => Ok(C::zero())
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = (String, HashMap<(char, char), char>);
+    type Output1 = Result<usize, Overflow>;
+    type Output2 = Result<usize, Overflow>;
+
+    fn parse(input: &str) -> Result<(String, HashMap<(char, char), char>), ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &(String, HashMap<(char, char), char>)) -> Result<usize, Overflow> {
+        Self::part_1_as(input, &Params::default())
+    }
+
+    fn part_2(input: &(String, HashMap<(char, char), char>)) -> Result<usize, Overflow> {
+        Self::part_2_as(input, &Params::default())
+    }
+}
+
+impl Counted for Day14 {
+    fn part_1_as<C: Count>(input: &(String, HashMap<(char, char), char>), _params: &Params) -> Result<C, Overflow> {
+        let (poly, insertions) = input;
+        solve_as(poly, insertions, 10)
+    }
+
+    fn part_2_as<C: Count>(input: &(String, HashMap<(char, char), char>), _params: &Params) -> Result<C, Overflow> {
+        let (poly, insertions) = input;
+        solve_as(poly, insertions, 40)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::differential::{remove_each, smaller, Differential};
+    use super::*;
+
+    /// Solves by building the whole polymer, one insertion step at a time.
+    fn solve_by_inserting(poly: &String, insertions: &HashMap<(char, char), char>, n_steps: usize) -> usize {
+        let mut poly = poly.clone();
+        for _ in 0..n_steps {
+            poly = insert(&poly, insertions)
+        }
+        let counts = poly.chars().counts();
+        counts.values().max().unwrap() - counts.values().min().unwrap()
+    }
+
+    #[test]
+    fn test_p1() {
+        let (poly, insertions) = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            solve(&poly, &insertions, 10),
+            1588
+        )
+    }
+    #[test]
+    fn test_p2() {
+        let (poly, insertions) = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            solve(&poly, &insertions, 40),
+            2188189693529
+        )
+    }
+
+    #[test]
+    fn many_steps() {
+        let (poly, insertions) = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(solve_as::<u64>(&poly, &insertions, 40), Ok(2188189693529));
+        assert_eq!(Day14::part_2_as::<u128>(&(poly.clone(), insertions.clone()), &Params::default()), Ok(2188189693529));
+        assert_eq!(solve_as::<u64>(&poly, &insertions, 70), Err(Overflow));
+        let big = solve_as::<common::count::BigUint>(&poly, &insertions, 70).unwrap();
+        assert_eq!(solve_as::<u128>(&poly, &insertions, 70).map(|n| n.to_string()), Ok(big.to_string()));
+        assert!(solve_as::<common::count::BigUint>(&poly, &insertions, 200).unwrap().bits() > 128);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("NNCB\n\nCH -> B\nHh -> N").unwrap_err();
+        assert_eq!(err, ParseError::new(4, 1, "Hh", "a pair of elements"));
+        let err = parse_input("NNCB\n\nCH -> B\nHH => N").unwrap_err();
+        assert_eq!(err, ParseError::new(4, 1, "HH => N", "` -> `"));
+    }
+
+    #[test]
+    fn same_as_inserting() {
+        Differential {
+            generate: |rng| {
+                let elements: Vec<char> = "ABCD".chars().take(2 + rng.below(3)).collect();
+                let poly: String = (0..2 + rng.below(5)).map(|_| *rng.choose(&elements)).collect();
+                let insertions: HashMap<_, _> = elements.iter().cartesian_product(&elements).map(|(c1, c2)| ((*c1, *c2), *rng.choose(&elements))).collect();
+                (poly, insertions, 4 + 2 * rng.below(3))
+            },
+            shrink: |(poly, insertions, n_steps)| {
+                let chars: Vec<char> = poly.chars().collect();
+                let mut shrunk: Vec<_> = remove_each(&chars, 2).into_iter().map(|c| (c.into_iter().collect(), insertions.clone(), *n_steps)).collect();
+                shrunk.extend(smaller(*n_steps / 2, 2).into_iter().map(|n| (poly.clone(), insertions.clone(), 2 * n)));
+                shrunk
+            },
+            optimized: |(poly, insertions, n_steps)| solve(poly, insertions, *n_steps),
+            reference: |(poly, insertions, n_steps)| solve_by_inserting(poly, insertions, *n_steps)
+        }.check(14, 300)
+    }
+
+    #[test]
+    fn pairs_same_as_inserting() {
+        Differential {
+            generate: |rng| {
+                let elements: Vec<char> = "ABCD".chars().take(2 + rng.below(3)).collect();
+                let poly: String = (0..1 + rng.below(6)).map(|_| *rng.choose(&elements)).collect();
+                let insertions: HashMap<_, _> = elements.iter().cartesian_product(&elements).map(|(c1, c2)| ((*c1, *c2), *rng.choose(&elements))).collect();
+                (poly, insertions, rng.below(10))
+            },
+            shrink: |(poly, insertions, n_steps)| {
+                let chars: Vec<char> = poly.chars().collect();
+                let mut shrunk: Vec<_> = remove_each(&chars, 1).into_iter().map(|c| (c.into_iter().collect(), insertions.clone(), *n_steps)).collect();
+                shrunk.extend(smaller(*n_steps, 0).into_iter().map(|n| (poly.clone(), insertions.clone(), n)));
+                shrunk
+            },
+            optimized: |(poly, insertions, n_steps)| solve_as::<usize>(poly, insertions, *n_steps).unwrap(),
+            reference: |(poly, insertions, n_steps)| solve_by_inserting(poly, insertions, *n_steps)
+        }.check(14, 300)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+use common::explain::{Explain, Fact};
+use grid::Grid;
+use crate::{grow_scan, lowest_risk_path, Day15};
+
+/// The positions, as row and column from 0, of the safest path and the risk
+/// of entering each one after the start.
+fn explain(scan: &Grid<usize>) -> Fact {
+    let (risk, path) = lowest_risk_path(scan);
+    Fact::record([
+        ("risk", Fact::from(risk)),
+        ("steps", Fact::from(path.len() - 1)),
+        ("step_risks", Fact::list(path.iter().skip(1).map(|pos| scan[*pos]))),
+        ("path", Fact::list(path.iter().map(|pos| vec!(pos.row, pos.col))))
+    ])
+}
+
+impl Explain for Day15 {
+    fn explain_1(scan: &Grid<usize>) -> Fact {
+        explain(scan)
+    }
+
+    /// The path through the full map, five times the scan each way.
+    fn explain_2(scan: &Grid<usize>) -> Fact {
+        explain(&grow_scan(scan))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn lowest_risk() {
+        let scan = parse_input(include_str!("../test")).unwrap();
+        let Fact::Record(fields) = Day15::explain_1(&scan) else {
+            panic!("Not a record")
+        };
+        assert_eq!(fields[0], (String::from("risk"), Fact::from(40usize)));
+        assert_eq!(fields[1], (String::from("steps"), Fact::from(18usize)));
+        let Fact::List(risks) = &fields[2].1 else {
+            panic!("Not a list")
+        };
+        assert_eq!(risks.iter().map(|r| if let Fact::Number(r) = r { *r } else { 0 }).sum::<i128>(), 40);
+        assert!(Day15::explain_1(&scan).to_text().ends_with("  - [8, 9]\n  - [9, 9]"));
+        assert!(Day15::explain_2(&scan).to_json().starts_with("{\"risk\": 315, \"steps\": 98, "));
+    }
+}
@@ -0,0 +1,14 @@
+use common::{Generate, Generated, Rng};
+use crate::Day15;
+
+impl Generate for Day15 {
+    const INPUT_SIZE: usize = 100;
+
+    /// A `size` by `size` map of risk levels from 1 to 9.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let rows: Vec<String> = (0..size).map(|_| {
+            (0..size).map(|_| char::from_digit(rng.range(1, 9) as u32, 10).unwrap()).collect()
+        }).collect();
+        Generated::new(rows.join("\n"))
+    }
+}
@@ -0,0 +1,125 @@
+mod explain;
+mod generate;
+
+use common::{ParseError, Solution};
+use grid::{Coord, Grid};
+use search::{astar, SearchState};
+
+pub fn grow_scan(scan: &Grid<usize>) -> Grid<usize> {
+    fn big_val(val: usize, rg: usize, cg: usize) -> usize {
+        let new_val = val + rg + cg;
+        if new_val < 10 {
+            new_val
+        } else {
+            new_val - 9
+        }
+    }
+    let n_row = scan.n_rows();
+    let n_col = scan.n_cols();
+    Grid::from_fn(5 * n_row, 5 * n_col, |pos|
+        big_val(scan[Coord::new(pos.row % n_row, pos.col % n_col)], pos.row / n_row, pos.col / n_col)
+    )
+}
+
+/// The risk levels, from 1 to 9, so that `heuristic` never overestimates.
+pub fn parse_input(s: &str) -> Result<Grid<usize>, ParseError> {
+    Ok(Grid::parse_chars(s, "123456789", "a risk from 1 to 9")?.map(|c| c.to_digit(10).unwrap() as usize))
+}
+
+/// A position on the way from the upper left to the lower right corner.
+#[derive(Clone)]
+struct Position<'a> {
+    scan: &'a Grid<usize>,
+    pos: Coord
+}
+
+impl Position<'_> {
+    fn steps_left(&self) -> usize {
+        (self.scan.n_rows() - 1 - self.pos.row) + (self.scan.n_cols() - 1 - self.pos.col)
+    }
+}
+
+impl SearchState for Position<'_> {
+    type Key = Coord;
+
+    fn key(&self) -> Coord {
+        self.pos
+    }
+
+    fn neighbours(&self) -> Vec<(Self, usize)> {
+        self.scan.neighbours_4(self.pos).map(|pos| (Position { scan: self.scan, pos }, self.scan[pos])).collect()
+    }
+
+    fn is_goal(&self) -> bool {
+        self.steps_left() == 0
+    }
+
+    /// Every step has a risk of at least 1, as the input has no 0.
+    fn heuristic(&self) -> usize {
+        self.steps_left()
+    }
+}
+
+pub fn calc_risc_score(scan: &Grid<usize>) -> usize {
+    lowest_risk_path(scan).0
+}
+
+/// The total risk of the safest way through, and the positions along it
+/// from the upper left to the lower right corner.
+pub fn lowest_risk_path(scan: &Grid<usize>) -> (usize, Vec<Coord>) {
+    let found = astar(Position { scan, pos: Coord::new(0, 0) }).expect("There is always a way through");
+    (found.cost, found.path.iter().map(|position| position.pos).collect())
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Grid<usize>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<usize>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(scan: &Grid<usize>) -> usize {
+        calc_risc_score(scan)
+    }
+
+    fn part_2(scan: &Grid<usize>) -> usize {
+        calc_risc_score(&grow_scan(scan))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn p1() {
+        let scan = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            calc_risc_score(&scan),
+            40
+        )
+    }
+
+    #[test]
+    fn p2() {
+        let scan = parse_input(include_str!("../test")).unwrap();
+        let scan = grow_scan(&scan);
+        assert_eq!(
+            calc_risc_score(&scan),
+            315
+        )
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(parse_input("116
+138
+2x3").unwrap_err(), ParseError::new(3, 2, "x", "a risk from 1 to 9"));
+        assert_eq!(parse_input("116
+108").unwrap_err(), ParseError::new(2, 2, "0", "a risk from 1 to 9"));
+    }
+
+}
@@ -0,0 +1,93 @@
+use common::{Generate, Generated, Rng};
+use crate::Day16;
+
+/// A generated packet: its bits, the sum of its versions and its value.
+struct Built {
+    bits: String,
+    version_sum: usize,
+    value: usize
+}
+
+fn literal(rng: &mut Rng, version: usize) -> Built {
+    let n_groups = rng.range(1, 8) as usize;
+    let value = (rng.next_u64() >> (64 - 4 * n_groups)) as usize;
+    let mut bits = format!("{:03b}100", version);
+    for group in (0..n_groups).rev() {
+        bits += &format!("{}{:04b}", if group > 0 { 1 } else { 0 }, (value >> (4 * group)) & 0xf);
+    }
+    Built { bits, version_sum: version, value }
+}
+
+/// A packet that holds `n_packets` packets, itself included.
+fn packet(rng: &mut Rng, n_packets: usize) -> Built {
+    let version = rng.below(8);
+    if n_packets == 1 {
+        return literal(rng, version)
+    }
+    let mut type_id = if n_packets >= 3 && rng.chance(0.3) { rng.range(5, 7) as usize } else { rng.below(4) };
+    let n_sub_packets = if type_id >= 5 { 2 } else { rng.range(1, 4.min(n_packets as isize - 1)) as usize };
+    let mut sizes = vec![1; n_sub_packets];
+    for _ in 0..(n_packets - 1 - n_sub_packets) {
+        sizes[rng.below(n_sub_packets)] += 1
+    }
+    let sub_packets: Vec<Built> = sizes.into_iter().map(|size| packet(rng, size)).collect();
+    let values: Vec<usize> = sub_packets.iter().map(|sp| sp.value).collect();
+    // Sums and products that do not fit become minimums instead.
+    let value = match type_id {
+        0 => values.iter().try_fold(0usize, |acc, v| acc.checked_add(*v)),
+        1 => values.iter().try_fold(1usize, |acc, v| acc.checked_mul(*v)),
+        2 => values.iter().min().copied(),
+        3 => values.iter().max().copied(),
+        5 => Some((values[0] > values[1]) as usize),
+        6 => Some((values[0] < values[1]) as usize),
+        _ => Some((values[0] == values[1]) as usize)
+    }.unwrap_or_else(|| {
+        type_id = 2;
+        *values.iter().min().unwrap()
+    });
+    let sub_bits: String = sub_packets.iter().map(|sp| sp.bits.as_str()).collect();
+    let length = if sub_bits.len() < 1 << 15 && rng.chance(0.5) {
+        format!("0{:015b}", sub_bits.len())
+    } else {
+        format!("1{:011b}", n_sub_packets)
+    };
+    Built {
+        bits: format!("{:03b}{:03b}{}{}", version, type_id, length, sub_bits),
+        version_sum: version + sub_packets.iter().map(|sp| sp.version_sum).sum::<usize>(),
+        value
+    }
+}
+
+impl Generate for Day16 {
+    const INPUT_SIZE: usize = 250;
+
+    /// A transmission of `size` packets with values that fit in a `usize`.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let built = packet(rng, size.max(1));
+        let mut bits = built.bits;
+        while !bits.len().is_multiple_of(4) {
+            bits.push('0')
+        }
+        let hex: String = bits.as_bytes().chunks(4).map(|nibble| {
+            let nibble = std::str::from_utf8(nibble).unwrap();
+            format!("{:X}", u32::from_str_radix(nibble, 2).unwrap())
+        }).collect();
+        Generated::with_answers(hex, built.version_sum, built.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Solution;
+    use super::*;
+
+    #[test]
+    fn test_generated_answers() {
+        for seed in 0..20 {
+            let generated = Day16::generate(&mut Rng::new(seed), 40);
+            let packet = Day16::parse(&generated.input).unwrap();
+            assert_eq!(generated.part_1, Some(Day16::part_1(&packet).to_string()));
+            assert_eq!(generated.part_2, Some(Day16::part_2(&packet).unwrap().to_string()));
+        }
+    }
+}
@@ -0,0 +1,438 @@
+mod generate;
+
+use common::{ParseError, Solution};
+use common::count::{self, Count, Counted, Overflow};
+use common::input::{normalize, parse_one_line};
+use common::params::Params;
+
+pub fn hex_message_to_binary(hex: &str) -> String {
+    fn padded_string(hex_char: u32) -> String {
+        let s = format!("{:b}", hex_char);
+        match s.len() {
+            0 => String::from("0000"),
+            1 => format!("000{}", s),
+            2 => format!("00{:}", s),
+            3 => format!("0{}", s),
+            4 => s,
+            _ => panic!("Unexpected hex_value")
+        }
+    }
+    hex.chars().flat_map(|c| padded_string(c.to_digit(16).unwrap()).chars().collect::<Vec<_>>()).collect()
+}
+
+/// Converts a hex message of one line to binary, failing at the first
+/// character that is not a hex digit.
+pub fn parse_hex_message(s: &str) -> Result<String, ParseError> {
+    parse_one_line(s, |hex| {
+        if let Some((col, c)) = hex.chars().enumerate().find(|(_, c)| !c.is_ascii_hexdigit()) {
+            return Err(ParseError::new(1, col + 1, &c.to_string(), "a hex digit"))
+        }
+        if hex.is_empty() {
+            return Err(ParseError::end_of_line(1, hex, "a hex digit"))
+        }
+        Ok(hex_message_to_binary(hex))
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PacketDetails {
+    Literal {
+        value: usize
+    },
+    Operator {
+        sub_packets: Vec<Packet>
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Packet {
+    pub version: usize,
+    pub type_id: usize,
+    pub details: PacketDetails
+}
+
+impl Packet {
+    pub fn eval(&self) -> Result<usize, Overflow> {
+        self.eval_as()
+    }
+
+    /// The value of the packet, counted in `C`.
+    pub fn eval_as<C: Count>(&self) -> Result<C, Overflow> {
+        match &self.details {
+            PacketDetails::Literal{ value } => C::from_usize(*value),
+            PacketDetails::Operator{ sub_packets} => {
+                let sub_values = sub_packets.iter().map(|sp| sp.eval_as()).collect::<Result<Vec<C>, _>>()?;
+                let flag = |is_set: bool| Ok(if is_set { C::one() } else { C::zero() });
+                match self.type_id {
+                    0 => count::sum(&sub_values),
+                    1 => count::product(&sub_values),
+                    2 => Ok(sub_values.iter().min().unwrap().clone()),
+                    3 => Ok(sub_values.iter().max().unwrap().clone()),
+                    5 => flag(sub_values[0] > sub_values[1]),
+                    6 => flag(sub_values[0] < sub_values[1]),
+                    7 => flag(sub_values[0] == sub_values[1]),
+                    _ => panic!("Unexpected type_id: {}", self.type_id)
+                }
+            }
+        }
+    }
+
+    pub fn version_sum(&self) -> usize {
+        match &self.details {
+            PacketDetails::Literal{ value: _ } => self.version,
+            PacketDetails::Operator{ sub_packets} => self.version + sub_packets.iter().fold(0, |acc, sp| acc + sp.version_sum())
+        }
+    }
+}
+
+/// How deep packets may be nested, so that parsing and evaluating a packet
+/// cannot run out of stack.
+pub const MAX_DEPTH: usize = 1000;
+
+/// Parses the packet at the start of `bits`, a string of `0` and `1`. On
+/// failure the error says what was expected and `bits` is left where the
+/// packet stopped making sense.
+pub fn parse_packet(bits: &mut std::str::Chars) -> Result<Packet, &'static str> {
+    fn parse_n_bit_int(bits: &mut std::str::Chars, n_bits: usize) -> Result<usize, &'static str> {
+        let mut value = 0;
+        for _ in 0..n_bits {
+            value = match bits.next() {
+                Some('0') => 2 * value,
+                Some('1') => 2 * value + 1,
+                Some(_) => return Err("a bit"),
+                None => return Err("more bits")
+            }
+        }
+        Ok(value)
+    }
+
+    fn parse_literal(bits: &mut std::str::Chars) -> Result<PacketDetails, &'static str> {
+        let mut value: usize = 0;
+        loop {
+            let last = parse_n_bit_int(bits, 1)? == 0;
+            if value >> (usize::BITS - 4) != 0 {
+                return Err("a literal that fits in 64 bits")
+            }
+            value = (value << 4) | parse_n_bit_int(bits, 4)?;
+            if last {
+                break
+            }
+        }
+        Ok(PacketDetails::Literal{value})
+    }
+
+    fn parse_operation(bits: &mut std::str::Chars, depth: usize) -> Result<PacketDetails, &'static str> {
+        let length_type_id = parse_n_bit_int(bits, 1)?;
+        let mut sub_packets: Vec<Packet> = vec!();
+        if length_type_id == 0 {
+            let n_packet_bits = parse_n_bit_int(bits, 15)?;
+            let n_remaining_at_start = bits.as_str().len();
+            while n_remaining_at_start - bits.as_str().len() < n_packet_bits {
+                sub_packets.push(parse_nested(bits, depth + 1)?);
+            }
+            if n_remaining_at_start - bits.as_str().len() != n_packet_bits {
+                return Err("sub-packets that end at their length")
+            }
+        } else {
+            let n_sub_packets = parse_n_bit_int(bits, 11)?;
+            for _ in 0..n_sub_packets {
+                sub_packets.push(parse_nested(bits, depth + 1)?)
+            }
+        }
+        Ok(PacketDetails::Operator{sub_packets})
+    }
+
+    fn parse_nested(bits: &mut std::str::Chars, depth: usize) -> Result<Packet, &'static str> {
+        if depth > MAX_DEPTH {
+            return Err("a less deeply nested packet")
+        }
+        let version = parse_n_bit_int(bits, 3)?;
+        let type_id = parse_n_bit_int(bits, 3)?;
+        let details = if type_id == 4 {
+            parse_literal(bits)?
+        } else {
+            parse_operation(bits, depth)?
+        };
+        // The comparisons take two values and the other operators at least one.
+        if let PacketDetails::Operator{sub_packets} = &details {
+            match type_id {
+                5..=7 if sub_packets.len() != 2 => return Err("two sub-packets"),
+                _ if sub_packets.is_empty() => return Err("a sub-packet"),
+                _ => ()
+            }
+        }
+        Ok(Packet {version, type_id, details})
+    }
+
+    parse_nested(bits, 0)
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Packet;
+    type Output1 = usize;
+    type Output2 = Result<usize, Overflow>;
+
+    fn parse(input: &str) -> Result<Packet, ParseError> {
+        let binary = parse_hex_message(input)?;
+        let mut bits = binary.chars();
+        parse_packet(&mut bits).map_err(|expected| {
+            let hex = normalize(input);
+            let col = (binary.len() - bits.as_str().len()) / 4;
+            match hex.get(col..(col + 1)) {
+                Some(c) => ParseError::new(1, col + 1, c, expected),
+                None => ParseError::end_of_line(1, &hex, expected)
+            }
+        })
+    }
+
+    fn part_1(message: &Packet) -> usize {
+        message.version_sum()
+    }
+
+    fn part_2(message: &Packet) -> Result<usize, Overflow> {
+        message.eval()
+    }
+}
+
+impl Counted for Day16 {
+    fn part_1_as<C: Count>(message: &Packet, _params: &Params) -> Result<C, Overflow> {
+        C::from_usize(message.version_sum())
+    }
+
+    fn part_2_as<C: Count>(message: &Packet, _params: &Params) -> Result<C, Overflow> {
+        message.eval_as()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn p1_1() {
+        let binary = &hex_message_to_binary("D2FE28");
+        assert_eq!(
+            binary,
+            "110100101111111000101000"
+        );
+        let message = parse_packet(&mut binary.chars()).unwrap();
+        assert_eq!(
+            message.version,
+            6
+        );
+        assert_eq!(
+            message.type_id,
+            4
+        );
+        assert_eq!(
+            message.eval(),
+            Ok(2021)
+        )
+    }
+    #[test]
+    fn p1_2() {
+        let binary = &hex_message_to_binary("38006F45291200");
+        assert_eq!(binary,
+            "00111000000000000110111101000101001010010001001000000000"
+        );
+        let message = parse_packet(&mut binary.chars()).unwrap();
+        assert_eq!(
+            message.version,
+            1
+        );
+        assert_eq!(
+            message.type_id,
+            6
+        );
+    }
+    #[test]
+    fn p1_3() {
+        let binary = &hex_message_to_binary("EE00D40C823060");
+        assert_eq!(
+            binary,
+            "11101110000000001101010000001100100000100011000001100000"
+        );
+        let message = parse_packet(&mut binary.chars()).unwrap();
+        assert_eq!(
+            message.version,
+            7
+        );
+        assert_eq!(
+            message.type_id,
+            3
+        );
+    }
+
+    #[test]
+    fn p1_4() {
+        let binary = &hex_message_to_binary("8A004A801A8002F478");
+        let message = parse_packet(&mut binary.chars()).unwrap();
+        assert_eq!(
+            message.version_sum(),
+            16
+        )
+    }
+
+    #[test]
+    fn p1_5() {
+        let binary = &hex_message_to_binary("C0015000016115A2E0802F182340");
+        let message = parse_packet(&mut binary.chars()).unwrap();
+        assert_eq!(
+            message.version_sum(),
+            23
+        )
+    }
+
+    #[test]
+    fn p1_6() {
+        let binary = &hex_message_to_binary("A0016C880162017C3686B18A3D4780");
+        let message = parse_packet(&mut binary.chars()).unwrap();
+        assert_eq!(
+            message.version_sum(),
+            31
+        )
+    }
+
+    #[test]
+    fn p2_1() {
+        let binary = &hex_message_to_binary("C200B40A82");
+        let message = parse_packet(&mut binary.chars()).unwrap();
+        assert_eq!(
+            message.eval(),
+            Ok(3)
+        )
+    }
+
+    #[test]
+    fn p2_2() {
+        let binary = &hex_message_to_binary("04005AC33890");
+        let message = parse_packet(&mut binary.chars()).unwrap();
+        assert_eq!(
+            message.eval(),
+            Ok(54)
+        )
+    }
+
+    #[test]
+    fn p2_3() {
+        let binary = &hex_message_to_binary("880086C3E88112");
+        let message = parse_packet(&mut binary.chars()).unwrap();
+        assert_eq!(
+            message.eval(),
+            Ok(7)
+        )
+    }
+
+    #[test]
+    fn p2_4() {
+        let binary = &hex_message_to_binary("CE00C43D881120");
+        let message = parse_packet(&mut binary.chars()).unwrap();
+        assert_eq!(
+            message.eval(),
+            Ok(9)
+        )
+    }
+
+    #[test]
+    fn p2_5() {
+        let binary = &hex_message_to_binary("D8005AC2A8F0");
+        let message = parse_packet(&mut binary.chars()).unwrap();
+        assert_eq!(
+            message.eval(),
+            Ok(1)
+        )
+    }
+
+    #[test]
+    fn p2_6() {
+        let binary = &hex_message_to_binary("F600BC2D8F");
+        let message = parse_packet(&mut binary.chars()).unwrap();
+        assert_eq!(
+            message.eval(),
+            Ok(0)
+        )
+    }
+
+    #[test]
+    fn p2_7() {
+        let binary = &hex_message_to_binary("9C005AC2F8F0");
+        let message = parse_packet(&mut binary.chars()).unwrap();
+        assert_eq!(
+            message.eval(),
+            Ok(0)
+        )
+    }
+
+    #[test]
+    fn p2_8() {
+        let binary = &hex_message_to_binary("9C0141080250320F1802104A08");
+        let message = parse_packet(&mut binary.chars()).unwrap();
+        assert_eq!(
+            message.eval(),
+            Ok(1)
+        )
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(parse_hex_message("8A004A80").unwrap(), hex_message_to_binary("8A004A80"));
+        assert_eq!(parse_hex_message("8A0O4A80").unwrap_err(), ParseError::new(1, 4, "O", "a hex digit"));
+        assert_eq!(parse_hex_message("8A004A80\n8A").unwrap_err(), ParseError::new(2, 1, "8A", "end of input"));
+    }
+
+    #[test]
+    fn test_malformed_packets() {
+        // Found by fuzzing: transmissions that end inside the first packet.
+        assert_eq!(Day16::parse("0").err(), Some(ParseError::new(1, 2, "", "more bits")));
+        assert_eq!(Day16::parse("8").err(), Some(ParseError::new(1, 2, "", "more bits")));
+        let parse = |bits: &str| parse_packet(&mut bits.chars()).err();
+        let literal = "00010000001";
+        assert_eq!(parse(&format!("0000000{:015b}{}", 1, literal)), Some("sub-packets that end at their length"));
+        assert_eq!(parse(&format!("0000000{:015b}{}", 11, literal)), None);
+        assert_eq!(parse(&format!("000101100000000001{}", literal)), Some("two sub-packets"));
+        assert_eq!(parse(&format!("0000000{:015b}", 0)), Some("a sub-packet"));
+        assert_eq!(parse(&format!("000100{}00001", "10001".repeat(15))), None);
+        assert_eq!(parse(&format!("000100{}00001", "10001".repeat(16))), Some("a literal that fits in 64 bits"));
+        assert_eq!(parse("000x"), Some("a bit"));
+        let nested = |depth: usize| format!("{}{}", "000000100000000001".repeat(depth), literal);
+        assert_eq!(parse(&nested(MAX_DEPTH)), None);
+        assert_eq!(parse(&nested(MAX_DEPTH + 1)), Some("a less deeply nested packet"));
+        assert_eq!(Day16::parse("38006F452912").err(), Some(ParseError::new(1, 13, "", "more bits")));
+    }
+
+    #[test]
+    fn large_values() {
+        let literal = |value: u64| {
+            let nibbles = format!("{:x}", value);
+            let groups: Vec<String> = nibbles.chars().enumerate().map(|(ind, c)| {
+                format!("{}{:04b}", if ind + 1 < nibbles.len() { 1 } else { 0 }, c.to_digit(16).unwrap())
+            }).collect();
+            format!("000100{}", groups.concat())
+        };
+        let product = |values: &[u64]| {
+            let sub_bits: String = values.iter().map(|v| literal(*v)).collect();
+            parse_packet(&mut format!("0000011{:011b}{}", values.len(), sub_bits).chars()).unwrap()
+        };
+        assert_eq!(product(&[1 << 30, 1 << 30]).eval_as::<u64>(), Ok(1 << 60));
+        assert_eq!(product(&[1 << 40, 1 << 40]).eval_as::<u64>(), Err(Overflow));
+        assert_eq!(Day16::part_2_as::<u128>(&product(&[1 << 40, 1 << 40]), &Params::default()), Ok(1 << 80));
+        assert_eq!(Day16::part_2(&product(&[1 << 60; 3])), Err(Overflow));
+        assert_eq!(product(&[1 << 40, 1 << 40]).eval_as::<u128>(), Ok(1 << 80));
+        assert_eq!(product(&[1 << 60; 3]).eval_as::<u128>(), Err(Overflow));
+        let big = product(&[1 << 60; 3]).eval_as::<count::BigUint>().unwrap();
+        assert_eq!(big, count::BigUint::from(1u8) << 180);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let packet = Day16::parse("8A004A801A8002F478").unwrap();
+        let json = serde_json::to_string(&packet).unwrap();
+        assert!(json.starts_with("{\"version\":4,\"type_id\":2,\"details\":{\"Operator\":{\"sub_packets\":[{\"version\":1,"));
+        let read: Packet = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&read).unwrap(), json);
+        assert_eq!(Day16::part_1(&read), 16);
+    }
+}
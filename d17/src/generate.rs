@@ -0,0 +1,19 @@
+use common::{Generate, Generated, Rng};
+use crate::Day17;
+
+impl Generate for Day17 {
+    const INPUT_SIZE: usize = 1;
+
+    /// A target area below the launcher that a probe can drop straight down
+    /// into, so the highest point is known. `size` is ignored.
+    fn generate(rng: &mut Rng, _size: usize) -> Generated {
+        let vx = rng.range(10, 25);
+        let stop_x = vx * (vx + 1) / 2;
+        let xmin = stop_x - rng.range(0, 10);
+        let xmax = stop_x + rng.range(0, 40);
+        let ymin = -rng.range(50, 150);
+        let ymax = ymin + rng.range(5, 45);
+        let input = format!("target area: x={}..{}, y={}..{}", xmin, xmax, ymin, ymax);
+        Generated { input, part_1: Some((ymin * (ymin + 1) / 2).to_string()), part_2: None }
+    }
+}
@@ -0,0 +1,194 @@
+mod generate;
+
+use common::{ParseError, Solution};
+use common::input::parse_one_line;
+use common::parse::{parse_at, split_at};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// The target area as (xmin, xmax, ymin, ymax). The solvers need it to be
+/// to the right of and below the launcher.
+pub fn parse_input(s: &str) -> Result<(isize, isize, isize, isize), ParseError> {
+    parse_one_line(s, |l| {
+        let ranges = l.strip_prefix("target area: ").ok_or_else(|| ParseError::at(1, l, l, "`target area: `"))?;
+        let (x, y) = split_at(1, l, ranges, ", ")?;
+        let parse_range = |range: &str, axis: &str| {
+            let prefix = format!("{}=", axis);
+            let range = range.strip_prefix(prefix.as_str()).ok_or_else(|| ParseError::at(1, l, range, &format!("`{}`", prefix)))?;
+            let (min, max) = split_at(1, l, range, "..")?;
+            let (min, max): (isize, isize) = (parse_at(1, l, min, "a number")?, parse_at(1, l, max, "a number")?);
+            if min > max {
+                return Err(ParseError::at(1, l, range, "an increasing range"))
+            }
+            Ok((min, max))
+        };
+        let (xmin, xmax) = parse_range(x, "x")?;
+        let (ymin, ymax) = parse_range(y, "y")?;
+        if xmin <= 0 {
+            return Err(ParseError::at(1, l, x, "a target to the right of the launcher"))
+        }
+        if ymax >= 0 {
+            return Err(ParseError::at(1, l, y, "a target below the launcher"))
+        }
+        Ok((xmin, xmax, ymin, ymax))
+    })
+}
+
+fn hits_target(vxi: isize, vyi: isize, xmin: isize, xmax: isize, ymin: isize, ymax: isize) -> Option<isize> {
+    let mut vx = vxi;
+    let mut vy = vyi;
+    let mut x = 0;
+    let mut y = 0;
+    let mut ymax_height = 0;
+
+    while x <= xmax && y >= ymin {
+        if x >= xmin && x <= xmax && y >= ymin && y <= ymax {
+            return Some(ymax_height)
+        }
+        x += vx;
+        y += vy;
+        ymax_height = ymax_height.max(y);
+        if vx > 0 {
+            vx -= 1
+        }
+        vy -= 1;
+    } 
+    None
+}
+
+pub fn find_best(xmin: isize, xmax: isize, ymin: isize, ymax: isize) -> isize {
+    let mut best = 0;
+    for vxi in 0..=(xmax*2) {
+        for vyi in ymin..(xmax*2) {
+            if let Some(height) = hits_target(vxi, vyi, xmin, xmax, ymin, ymax) {
+                best = best.max(height)
+            }
+        }
+    }
+    best
+}
+
+#[cfg(feature = "parallel")]
+pub fn find_all(xmin: isize, xmax: isize, ymin: isize, ymax: isize) -> isize {
+    (0..=(xmax*2)).into_par_iter().map(|vxi| {
+        (ymin..(xmax*2)).filter(|vyi| hits_target(vxi, *vyi, xmin, xmax, ymin, ymax).is_some()).count() as isize
+    }).sum()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn find_all(xmin: isize, xmax: isize, ymin: isize, ymax: isize) -> isize {
+    let mut count = 0;
+    for vxi in 0..=(xmax*2) {
+        for vyi in ymin..(xmax*2) {
+            if hits_target(vxi, vyi, xmin, xmax, ymin, ymax).is_some() {
+                count += 1
+            }
+        }
+    }
+    count
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = (isize, isize, isize, isize);
+    type Output1 = isize;
+    type Output2 = isize;
+
+    /// 2 reads the target from the input instead of using a fixed one.
+    const VERSION: u32 = 2;
+
+    fn parse(input: &str) -> Result<(isize, isize, isize, isize), ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(target: &(isize, isize, isize, isize)) -> isize {
+        let (xmin, xmax, ymin, ymax) = *target;
+        find_best(xmin, xmax, ymin, ymax)
+    }
+
+    fn part_2(target: &(isize, isize, isize, isize)) -> isize {
+        let (xmin, xmax, ymin, ymax) = *target;
+        find_all(xmin, xmax, ymin, ymax)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::{Generate, Rng};
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_input("target area: x=20..30, y=-10..-5\n"), Ok((20, 30, -10, -5)));
+        assert_eq!(parse_input("target area: x=20..30, z=-10..-5").unwrap_err(), ParseError::new(1, 24, "z=-10..-5", "`y=`"));
+        assert_eq!(parse_input("target area: x=30..20, y=-10..-5").unwrap_err().expected, "an increasing range");
+        assert_eq!(parse_input("target area: x=20..30, y=-10..5").unwrap_err().expected, "a target below the launcher");
+        assert_eq!(parse_input("target area: x=-30..-20, y=-10..-5").unwrap_err().expected, "a target to the right of the launcher");
+        assert_eq!(parse_input("target: x=20..30, y=-10..-5").unwrap_err().expected, "`target area: `");
+    }
+
+    #[test]
+    fn generated() {
+        for seed in 0..5 {
+            let generated = Day17::generate(&mut Rng::new(seed), 1);
+            let target = Day17::parse(&generated.input).unwrap();
+            assert_eq!(Some(Day17::part_1(&target).to_string()), generated.part_1, "Seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn hit_1() {
+        assert_eq!(
+            hits_target(7, 2, 20, 30, -10, -5),
+            Some(3)
+        )
+    }
+
+    #[test]
+    fn hit_2() {
+        assert_eq!(
+            hits_target(6, 3, 20, 30, -10, -5),
+            Some(6)
+        )
+    }
+
+    #[test]
+    fn hit_3() {
+        assert_eq!(
+            hits_target(9, 0, 20, 30, -10, -5),
+            Some(0)
+        )
+    }
+
+    #[test]
+    fn hit_4() {
+        assert_eq!(
+            hits_target(17, -4, 20, 30, -10, -5),
+            None
+        )
+    }
+
+    #[test]
+    fn hit_5() {
+        assert_eq!(
+            hits_target(6, 9, 20, 30, -10, -5),
+            Some(45)
+        )
+    }
+
+    #[test]
+    fn test_best() {
+        assert_eq!(
+            find_best(20, 30, -10, -5),
+            45
+        )        
+    }
+    #[test]
+    fn test_all() {
+        assert_eq!(
+            find_all(20, 30, -10, -5),
+            112
+        )        
+    }
+}
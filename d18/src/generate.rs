@@ -0,0 +1,22 @@
+use common::{Generate, Generated, Rng};
+use crate::Day18;
+
+/// A reduced snailfish number inside `depth` pairs: no pair is nested in
+/// four others and no regular number is above 9.
+fn number(rng: &mut Rng, depth: usize) -> String {
+    if depth == 4 || (depth > 0 && rng.chance(0.3)) {
+        rng.below(10).to_string()
+    } else {
+        format!("[{},{}]", number(rng, depth + 1), number(rng, depth + 1))
+    }
+}
+
+impl Generate for Day18 {
+    const INPUT_SIZE: usize = 100;
+
+    /// `size` reduced snailfish numbers.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let numbers: Vec<String> = (0..size).map(|_| number(rng, 0)).collect();
+        Generated::new(numbers.join("\n"))
+    }
+}
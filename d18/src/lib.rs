@@ -0,0 +1,616 @@
+mod generate;
+
+use itertools::Itertools;
+use common::{ParseError, Solution};
+use common::input::parse_lines;
+use common::parse::{expect_end, parse_at};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Token {
+    Literal {
+        value: usize
+    },
+    Comma,
+    LeftBracket,
+    RightBracket
+}
+
+impl Token {
+    fn add(self, val: usize) -> Token {
+        match self {
+            Token::Literal{value} => Token::Literal{value: value.saturating_add(val)},
+            _ => self
+        }
+    }
+}
+
+/// Splits a snailfish number into tokens. Characters that are not part of
+/// one, which `check_number` rejects, are skipped, and a number too large
+/// for a `usize` becomes `usize::MAX`.
+pub fn tokenize(chars: &mut std::iter::Peekable<&mut std::str::Chars>) -> Vec<Token> {
+    fn parse_usize(chars: &mut std::iter::Peekable<&mut std::str::Chars>) -> usize {
+        let mut value: usize = 0;
+        while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+            value = value.saturating_mul(10).saturating_add(digit as usize);
+            chars.next();
+        }
+        value
+    }
+    let mut tokens: Vec<Token> = vec!();
+    loop {
+        match chars.peek() {
+            Some('[') => {tokens.push(Token::LeftBracket); chars.next();}, 
+            Some(']') => {tokens.push(Token::RightBracket); chars.next();}, 
+            Some(',') => {tokens.push(Token::Comma); chars.next();}, 
+            Some(c) if c.is_ascii_digit() => tokens.push(Token::Literal{ value: parse_usize(chars)}),
+            Some(_) => {chars.next();},
+            None => break
+        }
+    }
+    tokens
+}
+
+pub fn add(first: Vec<Token>, second: Vec<Token>) -> Vec<Token> {
+    let mut sum = vec!();
+    sum.push(Token::LeftBracket);
+    sum.append(&mut first.clone());
+    sum.push(Token::Comma);
+    sum.append(&mut second.clone());
+    sum.push(Token::RightBracket);
+    reduce(sum)
+}
+
+/// Explodes the leftmost pair of two numbers that is nested inside four or
+/// more pairs, if there is one.
+pub fn explode(input: &Vec<Token>) -> Option<Vec<Token>> {
+    let mut exploded: Vec<Token> = vec!();
+    let mut it = input.iter();
+    let mut level = 0;
+    loop {
+        match it.next(){
+            Some(Token::LeftBracket) => {
+                level +=1; 
+                if level >= 5 {
+                    if let [Token::Literal{value: first_value}, Token::Comma, Token::Literal{value: second_value}, Token::RightBracket, rest @ ..] = it.as_slice() {
+                        if let Some(first_lit) = exploded.iter_mut().rev().find(|t| matches!(t, Token::Literal{value: _})) {
+                            *first_lit = first_lit.clone().add(*first_value);
+                        }
+                        exploded.push(Token::Literal{value: 0});
+                        let mut added = false;
+                        for t in rest {
+                            match t {
+                                Token::Literal{value: _} if !added => {
+                                    added = true;
+                                    exploded.push(t.clone().add(*second_value))
+                                },
+                                _ => exploded.push(t.clone())
+                            }
+                        }
+                        return Some(exploded)
+                    }
+                }
+                exploded.push(Token::LeftBracket);
+            },
+            Some(Token::RightBracket) => {level -=1; exploded.push(Token::RightBracket)},
+            Some(t) => exploded.push(t.clone()),
+            None => return None
+        }
+    }
+}
+
+pub fn split(input: &Vec<Token>) -> Option<Vec<Token>> {
+    let mut splitted: Vec<Token> = vec!();
+    let mut it = input.iter();
+    let mut did_split = false;
+    loop {
+        match it.next() {
+            Some(Token::Literal{value}) => {
+                if !did_split && *value >= 10 {
+                    let val_1 = value / 2;
+                    let val_2 = value - val_1;
+                    splitted.push(Token::LeftBracket);
+                    splitted.push(Token::Literal{value: val_1});
+                    splitted.push(Token::Comma);
+                    splitted.push(Token::Literal{value: val_2});
+                    splitted.push(Token::RightBracket);
+                    did_split = true;
+                } else {
+                    splitted.push(Token::Literal{value: *value})
+                }
+            }
+            Some(t) => splitted.push(t.clone()),
+            None => break
+        }
+    }
+    if did_split {
+        Some(splitted)
+    } else {
+        None
+    }
+}
+
+pub fn reduce(input: Vec<Token>) -> Vec<Token> {
+    let mut reduced = input.clone();
+    loop {
+        if let Some(exploded) = explode(&reduced) {
+            reduced = exploded
+        } else {
+            if let Some(splitted) = split(&reduced) {
+                reduced = splitted
+            } else {
+                break
+            }
+        }
+    }
+    reduced
+}
+
+pub fn sum(rows: &Vec<Vec<Token>>) -> Vec<Token> {
+    let mut it = rows.iter();
+    let mut result = it.next().unwrap().clone();
+    for row in it {
+        result = add(result, row.clone())
+    }
+    result
+}
+
+pub fn magnitude(token_stream: &mut std::slice::Iter<Token>) -> usize {
+    match token_stream.next() {
+        Some(Token::Literal{value}) => {*value}
+        Some(Token::LeftBracket)  => {
+            let left_value = magnitude(token_stream);
+            token_stream.next();
+            let right_value = magnitude(token_stream);
+            token_stream.next();
+            3 * left_value + 2 * right_value
+        }
+        _ => panic!("Unexpected token when calculating magnitude")
+    }
+}
+
+/// Checks that `l` is a reduced snailfish number, so that it can be
+/// tokenized and added: no pair is nested inside four others and no regular
+/// number is above 9.
+fn check_number(line: usize, l: &str) -> Result<(), ParseError> {
+    fn unexpected(line: usize, l: &str, pos: usize, expected: &str) -> ParseError {
+        match l[pos..].chars().next() {
+            Some(c) => ParseError::at(line, l, &l[pos..(pos + c.len_utf8())], expected),
+            None => ParseError::end_of_line(line, l, expected)
+        }
+    }
+
+    fn expect(line: usize, l: &str, pos: &mut usize, c: char) -> Result<(), ParseError> {
+        if l[*pos..].starts_with(c) {
+            *pos += 1;
+            Ok(())
+        } else {
+            Err(unexpected(line, l, *pos, &format!("`{}`", c)))
+        }
+    }
+
+    fn check_element(line: usize, l: &str, pos: &mut usize, depth: usize) -> Result<(), ParseError> {
+        if l[*pos..].starts_with('[') {
+            if depth == 4 {
+                return Err(unexpected(line, l, *pos, "a number, as pairs nest at most four deep"))
+            }
+            return check_pair(line, l, pos, depth + 1)
+        }
+        let n_digits = l[*pos..].chars().take_while(|c| c.is_ascii_digit()).count();
+        if n_digits == 0 {
+            return Err(unexpected(line, l, *pos, "a number or `[`"))
+        }
+        let digits = &l[*pos..(*pos + n_digits)];
+        if parse_at::<usize>(line, l, digits, "a number")? > 9 {
+            return Err(ParseError::at(line, l, digits, "a number below 10"))
+        }
+        *pos += n_digits;
+        Ok(())
+    }
+
+    fn check_pair(line: usize, l: &str, pos: &mut usize, depth: usize) -> Result<(), ParseError> {
+        expect(line, l, pos, '[')?;
+        check_element(line, l, pos, depth)?;
+        expect(line, l, pos, ',')?;
+        check_element(line, l, pos, depth)?;
+        expect(line, l, pos, ']')
+    }
+
+    let mut pos = 0;
+    check_pair(line, l, &mut pos, 1)?;
+    expect_end(line, l, &l[pos..])
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<Vec<Token>>, ParseError> {
+    parse_lines(s, |n_line, line| {
+        check_number(n_line, line)?;
+        Ok(tokenize(&mut (&mut line.chars()).peekable()))
+    })
+}
+
+pub fn do_home_work_2(rows: &Vec<Vec<Token>>) -> usize {
+    let row_pairs: Vec<_> = rows.iter().combinations(2).flat_map(|v| vec!((v[0].clone(), v[1].clone()), (v[1].clone(), v[0].clone()))).collect();
+    #[cfg(feature = "parallel")]
+    let row_pairs = row_pairs.into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let row_pairs = row_pairs.into_iter();
+    row_pairs.map(|(r1, r2)| magnitude(&mut add(r1, r2).iter())).max().unwrap()
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Vec<Token>>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Vec<Token>>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(rows: &Vec<Vec<Token>>) -> usize {
+        let the_sum = sum(rows);
+        magnitude(&mut the_sum.iter())
+    }
+
+    fn part_2(rows: &Vec<Vec<Token>>) -> usize {
+        do_home_work_2(rows)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Rng;
+    use common::differential::smaller;
+    use common::property::Property;
+    use super::*;
+
+    /// A random pair with pairs nested in it at most `depth` deep and regular
+    /// numbers up to 20, so that it takes both explodes and splits to reduce.
+    fn random_pair(rng: &mut Rng, depth: usize) -> Vec<Token> {
+        let element = |rng: &mut Rng| if depth > 1 && rng.chance(0.6) {
+            random_pair(rng, depth - 1)
+        } else {
+            vec!(Token::Literal{value: rng.below(21)})
+        };
+        let mut tokens = vec!(Token::LeftBracket);
+        tokens.extend(element(rng));
+        tokens.push(Token::Comma);
+        tokens.extend(element(rng));
+        tokens.push(Token::RightBracket);
+        tokens
+    }
+
+    /// The numbers with a pair inside them replaced by one of its halves, or
+    /// with a regular number made smaller.
+    fn shrink_number(tokens: &Vec<Token>) -> Vec<Vec<Token>> {
+        let mut shrunk = vec!();
+        for (start, token) in tokens.iter().enumerate() {
+            match token {
+                Token::LeftBracket if start > 0 => {
+                    let (mut level, mut comma, mut end) = (0, start, start);
+                    for (ind, t) in tokens.iter().enumerate().skip(start) {
+                        match t {
+                            Token::LeftBracket => level += 1,
+                            Token::Comma if level == 1 => comma = ind,
+                            Token::RightBracket => {
+                                level -= 1;
+                                if level == 0 {
+                                    end = ind;
+                                    break
+                                }
+                            },
+                            _ => ()
+                        }
+                    }
+                    for half in [start + 1..comma, comma + 1..end] {
+                        let mut smaller = tokens[..start].to_vec();
+                        smaller.extend_from_slice(&tokens[half]);
+                        smaller.extend_from_slice(&tokens[end + 1..]);
+                        shrunk.push(smaller)
+                    }
+                },
+                Token::Literal{value} => for value in smaller(*value, 0) {
+                    let mut smaller = tokens.clone();
+                    smaller[start] = Token::Literal{value};
+                    shrunk.push(smaller)
+                },
+                _ => ()
+            }
+        }
+        shrunk
+    }
+
+    fn to_text(tokens: &Vec<Token>) -> String {
+        tokens.iter().map(|t| match t {
+            Token::Literal{value} => value.to_string(),
+            Token::Comma => String::from(","),
+            Token::LeftBracket => String::from("["),
+            Token::RightBracket => String::from("]")
+        }).collect()
+    }
+
+    /// `add` reduces every sum once and `magnitude` trusts it to be reduced,
+    /// so reducing must leave a number the parser accepts as reduced, which
+    /// reducing again does not change. Pairs in the sum of two reduced
+    /// numbers nest five deep, so that is how deep the cases go.
+    #[test]
+    fn reduce_is_idempotent() {
+        Property {
+            generate: |rng| random_pair(rng, 5),
+            shrink: shrink_number,
+            law: |tokens| {
+                let reduced = reduce(tokens.clone());
+                check_number(1, &to_text(&reduced)).map_err(|e| format!("{} is not reduced: {}", to_text(&reduced), e))?;
+                let again = reduce(reduced.clone());
+                if again != reduced {
+                    return Err(format!("Reducing {} again gives {}", to_text(&reduced), to_text(&again)))
+                }
+                Ok(())
+            }
+        }.check(18, 1000)
+    }
+
+    #[test]
+    fn test_explode_1() {
+        let input = tokenize(&mut (&mut "[[[[[9,8],1],2],3],4]".chars()).peekable());
+        println!("{:?}", input);
+        let exploded = explode(&input);
+        if let Some(tokens) = exploded {
+            assert_eq!(
+                tokens,
+                tokenize(&mut (&mut "[[[[0,9],2],3],4]".chars()).peekable())
+            )
+        } else {
+            panic!("No tokens returned")
+        }
+    }
+
+    #[test]
+    fn test_explode_2() {
+        let input = tokenize(&mut (&mut "[7,[6,[5,[4,[3,2]]]]]".chars()).peekable());
+        println!("{:?}", input);
+        let exploded = explode(&input);
+        if let Some(tokens) = exploded {
+            assert_eq!(
+                tokens,
+                tokenize(&mut (&mut "[7,[6,[5,[7,0]]]]".chars()).peekable())
+            )
+        } else {
+            panic!("No tokens returned")
+        }
+    }
+
+    #[test]
+    fn test_explode_3() {
+        let input = tokenize(&mut (&mut "[[6,[5,[4,[3,2]]]],1]".chars()).peekable());
+        println!("{:?}", input);
+        let exploded = explode(&input);
+        if let Some(tokens) = exploded {
+            assert_eq!(
+                tokens,
+                tokenize(&mut (&mut "[[6,[5,[7,0]]],3]".chars()).peekable())
+            )
+        } else {
+            panic!("No tokens returned")
+        }
+    }
+
+    #[test]
+    fn test_explode_4() {
+        let input = tokenize(&mut (&mut "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]".chars()).peekable());
+        println!("{:?}", input);
+        let exploded = explode(&input);
+        if let Some(tokens) = exploded {
+            assert_eq!(
+                tokens,
+                tokenize(&mut (&mut "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]".chars()).peekable())
+            )
+        } else {
+            panic!("No tokens returned")
+        }
+    }
+
+    #[test]
+    fn test_explode_5() {
+        let input = tokenize(&mut (&mut "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]".chars()).peekable());
+        println!("{:?}", input);
+        let exploded = explode(&input);
+        if let Some(tokens) = exploded {
+            assert_eq!(
+                tokens,
+                tokenize(&mut (&mut "[[3,[2,[8,0]]],[9,[5,[7,0]]]]".chars()).peekable())
+            )
+        } else {
+            panic!("No tokens returned")
+        }
+    }
+
+    #[test]
+    fn test_split() {
+        let input = tokenize(&mut (&mut "[11,1]".chars()).peekable());
+        println!("{:?}", input);
+        let splitted = split(&input);
+        if let Some(tokens) = splitted {
+            assert_eq!(
+                tokens,
+                tokenize(&mut (&mut "[[5,6],1]".chars()).peekable())
+            )
+        } else {
+            panic!("No tokens returned")
+        }
+    }
+
+    #[test]
+    fn test_reduce() {
+        let input = tokenize(&mut (&mut "[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]".chars()).peekable());
+        println!("{:?}", input);
+        let reduced = reduce(input.clone());
+        assert_eq!(
+            reduced,
+            tokenize(&mut (&mut "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]".chars()).peekable())
+        )
+    }
+
+    #[test]
+    fn test_add() {
+        let inp1 = tokenize(&mut (&mut "[[[[4,3],4],4],[7,[[8,4],9]]]".chars()).peekable());
+        let inp2 = tokenize(&mut (&mut "[1,1]".chars()).peekable());
+        let reduced = add(inp1, inp2);
+        assert_eq!(
+            reduced,
+            tokenize(&mut (&mut "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]".chars()).peekable())
+        )
+    }
+
+    #[test]
+    fn test_sum_1() {
+        let rows: Vec<_> = include_str!("../sum1").lines().map(|line| tokenize(&mut (&mut line.chars()).peekable())).collect();
+        let the_sum = sum(&rows);
+        assert_eq!(
+            the_sum,
+            tokenize(&mut (&mut "[[[[1,1],[2,2]],[3,3]],[4,4]]".chars()).peekable())
+        )
+    }
+    #[test]
+    fn test_sum_2() {
+        let rows: Vec<_> = include_str!("../sum2").lines().map(|line| tokenize(&mut (&mut line.chars()).peekable())).collect();
+        let the_sum = sum(&rows);
+        assert_eq!(
+            the_sum,
+            tokenize(&mut (&mut "[[[[3,0],[5,3]],[4,4]],[5,5]]".chars()).peekable())
+        )
+    }
+    #[test]
+    fn test_sum_3() {
+        let rows: Vec<_> = include_str!("../sum3").lines().map(|line| tokenize(&mut (&mut line.chars()).peekable())).collect();
+        let the_sum = sum(&rows);
+        assert_eq!(
+            the_sum,
+            tokenize(&mut (&mut "[[[[5,0],[7,4]],[5,5]],[6,6]]".chars()).peekable())
+        )
+    }
+    #[test]
+    fn test_sum_4() {
+        let rows: Vec<_> = include_str!("../sum4").lines().map(|line| tokenize(&mut (&mut line.chars()).peekable())).collect();
+        let the_sum = sum(&rows);
+        assert_eq!(
+            the_sum,
+            tokenize(&mut (&mut "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]".chars()).peekable())
+        )
+    }
+
+    #[test]
+    fn test_magnitude_1() {
+        let input = tokenize(&mut (&mut "[[1,2],[[3,4],5]]".chars()).peekable());
+        assert_eq!(
+            magnitude(&mut input.iter()),
+            143
+        )
+    }
+    #[test]
+    fn test_magnitude_2() {
+        let input = tokenize(&mut (&mut "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]".chars()).peekable());
+        assert_eq!(
+            magnitude(&mut input.iter()),
+            1384
+        )
+    }
+    #[test]
+    fn test_magnitude_3() {
+        let input = tokenize(&mut (&mut "[[[[1,1],[2,2]],[3,3]],[4,4]]".chars()).peekable());
+        assert_eq!(
+            magnitude(&mut input.iter()),
+            445
+        )
+    }
+    #[test]
+    fn test_magnitude_4() {
+        let input = tokenize(&mut (&mut "[[[[3,0],[5,3]],[4,4]],[5,5]]".chars()).peekable());
+        assert_eq!(
+            magnitude(&mut input.iter()),
+            791
+        )
+    }
+    #[test]
+    fn test_magnitude_5() {
+        let input = tokenize(&mut (&mut "[[[[5,0],[7,4]],[5,5]],[6,6]]".chars()).peekable());
+        assert_eq!(
+            magnitude(&mut input.iter()),
+            1137
+        )
+    }
+    #[test]
+    fn test_magnitude_6() {
+        let input = tokenize(&mut (&mut "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]".chars()).peekable());
+        assert_eq!(
+            magnitude(&mut input.iter()),
+            3488
+        )
+    }
+
+    #[test]
+    fn test_homework() {
+        let rows: Vec<_> = include_str!("../testhw").lines().map(|line| tokenize(&mut (&mut line.chars()).peekable())).collect();
+        let the_sum = sum(&rows);
+        assert_eq!(
+            magnitude(&mut the_sum.iter()),
+            4140
+        )
+    }
+
+    #[test]
+    fn test_homework_2() {
+        let rows: Vec<_> = include_str!("../testhw").lines().map(|line| tokenize(&mut (&mut line.chars()).peekable())).collect();
+        assert_eq!(
+            do_home_work_2(&rows),
+            3993
+        )
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(parse_input("[[1,2],[[3,4],5]]\n[9,[8,7]]").is_ok());
+        assert_eq!(parse_input("[[1,2],[[3,4],5]]\n[9;[8,7]]").unwrap_err(), ParseError::new(2, 3, ";", "`,`"));
+        assert_eq!(parse_input("[[1,2],[[3,4],5]").unwrap_err(), ParseError::new(1, 17, "", "`]`"));
+        assert_eq!(parse_input("[[1,2],[x,5]]").unwrap_err(), ParseError::new(1, 9, "x", "a number or `[`"));
+        assert_eq!(parse_input("[1,2]]").unwrap_err(), ParseError::new(1, 6, "]", "end of line"));
+    }
+
+    #[test]
+    fn test_malformed_numbers() {
+        // Found by fuzzing: a character that is not part of a number.
+        assert_eq!(tokenize(&mut (&mut "\0".chars()).peekable()), vec!());
+        assert_eq!(parse_input("\0").unwrap_err(), ParseError::new(1, 1, "\0", "`[`"));
+        assert_eq!(
+            tokenize(&mut (&mut "[1,x2]".chars()).peekable()),
+            tokenize(&mut (&mut "[1,2]".chars()).peekable())
+        );
+        assert_eq!(
+            tokenize(&mut (&mut "[1,99999999999999999999]".chars()).peekable())[3],
+            Token::Literal{value: usize::MAX}
+        );
+        assert_eq!(explode(&tokenize(&mut (&mut "[[[[[1,2".chars()).peekable())), None);
+        assert_eq!(explode(&tokenize(&mut (&mut "]]]],[1,2]".chars()).peekable())), None);
+        assert_eq!(
+            explode(&tokenize(&mut (&mut "[[[[[[1,2],3],4],5],6],7]".chars()).peekable())),
+            Some(tokenize(&mut (&mut "[[[[[0,5],4],5],6],7]".chars()).peekable()))
+        );
+        let err = parse_input("[[[[[1,2],3],4],5],6]").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 5, "[", "a number, as pairs nest at most four deep"));
+        assert_eq!(parse_input("[10,1]").unwrap_err(), ParseError::new(1, 2, "10", "a number below 10"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let rows = Day18::parse(include_str!("../testhw")).unwrap();
+        let json = serde_json::to_string(&rows).unwrap();
+        assert!(json.starts_with("[[\"LeftBracket\",\"LeftBracket\",\"LeftBracket\",{\"Literal\":{\"value\":0}},\"Comma\","));
+        let read: Vec<Vec<Token>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, rows);
+        assert_eq!(Day18::part_1(&read), 4140);
+    }
+}
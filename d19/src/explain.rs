@@ -0,0 +1,61 @@
+use common::explain::{Explain, Fact};
+use common::geometry::{Point3, Rotation, Transform};
+use crate::{beacons, farthest_scanners, Day19, Scans};
+
+/// How a rotation turns a point `(x, y, z)`, like `(-y, x, z)`.
+fn rotation(rotation: &Rotation) -> String {
+    let Point3 { x, y, z } = rotation.apply(Point3::new(1, 2, 3));
+    let axis = |c: isize| format!("{}{}", if c < 0 { "-" } else { "" }, ["x", "y", "z"][c.unsigned_abs() - 1]);
+    format!("({}, {}, {})", axis(x), axis(y), axis(z))
+}
+
+/// Each scanner, numbered as in the input, with its position relative to
+/// scanner 0 and the rotation that turns what it sees the way scanner 0 does.
+fn poses(poses: &Vec<Transform>) -> Fact {
+    Fact::list(poses.iter().enumerate().map(|(scanner, pose)| {
+        let Point3 { x, y, z } = pose.translation;
+        Fact::record([
+            ("scanner", Fact::from(scanner)),
+            ("position", Fact::from(vec!(x, y, z))),
+            ("rotation", Fact::from(rotation(&pose.rotation)))
+        ])
+    }))
+}
+
+impl Explain for Day19 {
+    fn explain_1(scans: &Scans) -> Fact {
+        Fact::record([
+            ("scanners", poses(scans.poses())),
+            ("beacons", Fact::from(beacons(&scans.scans, scans.poses()).len()))
+        ])
+    }
+
+    /// The scanners, and the two of them that are furthest apart.
+    fn explain_2(scans: &Scans) -> Fact {
+        let (a, b, dist) = farthest_scanners(scans.poses());
+        Fact::record([
+            ("scanners", poses(scans.poses())),
+            ("furthest_apart", Fact::from(vec!(a, b))),
+            ("distance", Fact::from(dist))
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::{Generate, Rng, Solution};
+    use super::*;
+
+    #[test]
+    fn scanner_poses() {
+        assert_eq!(rotation(&Rotation::IDENTITY), "(x, y, z)");
+        assert_eq!(rotation(&Rotation::cube(1)), "(-y, x, z)");
+        let generated = Day19::generate(&mut Rng::new(19), 2);
+        let scans = Day19::parse(&generated.input).unwrap();
+        let explained = Day19::explain_1(&scans).to_text();
+        assert!(explained.starts_with("scanners:\n  - scanner: 0\n    position: [0, 0, 0]\n    rotation: (x, y, z)\n  - scanner: 1\n"), "{}", explained);
+        assert!(explained.ends_with(&format!("beacons: {}", generated.part_1.unwrap())));
+        let explained = Day19::explain_2(&scans).to_json();
+        assert!(explained.ends_with(&format!("\"furthest_apart\": [0, 1], \"distance\": {}}}", generated.part_2.unwrap())));
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::BTreeSet;
+use itertools::Itertools;
+use common::{Generate, Generated, Rng};
+use common::geometry::{Point3, Rotation};
+use crate::Day19;
+
+/// How far a scanner sees along each axis.
+const RANGE: isize = 1000;
+
+type Point = Point3<isize>;
+
+/// Adds `n` new beacons to `beacons`, anywhere from `min` to `max`.
+fn add_beacons(rng: &mut Rng, beacons: &mut BTreeSet<Point>, min: Point, max: Point, n: usize) {
+    let mut n_added = 0;
+    while n_added < n {
+        let beacon = Point3::new(rng.range(min.x, max.x), rng.range(min.y, max.y), rng.range(min.z, max.z));
+        if beacons.insert(beacon) {
+            n_added += 1
+        }
+    }
+}
+
+impl Generate for Day19 {
+    const INPUT_SIZE: usize = 33;
+
+    /// `size` scanners, at least two, in a hidden cloud of beacons. Every
+    /// scanner after the first is placed next to an earlier one and shares
+    /// twelve beacons with it, so all of them can be aligned. All beacons
+    /// are seen by some scanner, so both answers are known.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let size = size.max(2);
+        let mut positions: Vec<Point> = vec!(Point3::new(0, 0, 0));
+        let mut beacons = BTreeSet::new();
+        let range = Point3::new(RANGE, RANGE, RANGE);
+        add_beacons(rng, &mut beacons, -range, range, 20);
+        while positions.len() < size {
+            let parent = *rng.choose(&positions);
+            let pos = parent + Point3::new(rng.range(-1100, 1100), rng.range(-1100, 1100), rng.range(-1100, 1100));
+            // The alignment only searches offsets up to 8000.
+            if [pos.x, pos.y, pos.z].iter().any(|coord| coord.abs() > 7000) {
+                continue
+            }
+            let overlap_min = Point3::new(parent.x.max(pos.x), parent.y.max(pos.y), parent.z.max(pos.z)) - range;
+            let overlap_max = Point3::new(parent.x.min(pos.x), parent.y.min(pos.y), parent.z.min(pos.z)) + range;
+            add_beacons(rng, &mut beacons, overlap_min, overlap_max, 12);
+            let n_own = rng.range(5, 15) as usize;
+            add_beacons(rng, &mut beacons, pos - range, pos + range, n_own);
+            positions.push(pos)
+        }
+        let mut scans = vec!();
+        for (ind, pos) in positions.iter().enumerate() {
+            // The scan is turned back by the rotation that aligns it.
+            let rotation = if ind == 0 { Rotation::IDENTITY } else { Rotation::cube(rng.below(24)).inverse() };
+            let mut seen: Vec<String> = beacons.iter()
+                .filter(|b| (b.x - pos.x).abs() <= RANGE && (b.y - pos.y).abs() <= RANGE && (b.z - pos.z).abs() <= RANGE)
+                .map(|b| {
+                    let Point3 { x, y, z } = rotation.apply(*b - *pos);
+                    format!("{},{},{}", x, y, z)
+                }).collect();
+            rng.shuffle(&mut seen);
+            scans.push(format!("--- scanner {} ---\n{}", ind, seen.join("\n")))
+        }
+        let max_dist = positions.iter().tuple_combinations().map(|(a, b)| a.manhattan_distance(*b)).max().unwrap();
+        Generated::with_answers(scans.join("\n\n"), beacons.len(), max_dist)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Solution;
+    use super::*;
+
+    #[test]
+    fn test_generated_answers() {
+        let generated = Day19::generate(&mut Rng::new(19), 2);
+        let scans = Day19::parse(&generated.input).unwrap();
+        assert_eq!(generated.part_1, Some(Day19::part_1(&scans).to_string()));
+        assert_eq!(generated.part_2, Some(Day19::part_2(&scans).to_string()));
+    }
+}
@@ -0,0 +1,299 @@
+mod explain;
+mod generate;
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+#[cfg(feature = "parallel")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use itertools::Itertools;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use common::{ParseError, Solution};
+use common::geometry::{Point2, Point3, Rotation, Transform};
+use common::input::sections;
+use common::progress::{self, Level, Metrics};
+use common::parse::{expect_end, next_field, parse_at};
+
+fn rotate_set(in_set: &HashSet<Point3<isize>>, rotation: &Rotation) -> HashSet<Point3<isize>> {
+    in_set.iter().map(|pt| rotation.apply(*pt)).collect()
+}
+
+fn translate_set(in_set: &HashSet<Point3<isize>>, delta: Point3<isize>) -> HashSet<Point3<isize>> {
+    in_set.iter().map(|pt| *pt + delta).collect()
+}
+
+pub fn load_scans(input: &str) -> Result<Vec<HashSet<Point3<isize>>>, ParseError> {
+    fn parse_beacon(line: usize, l: &str) -> Result<Point3<isize>, ParseError> {
+        let mut it = l.split(',');
+        let mut coords = [0; 3];
+        for coord in &mut coords {
+            *coord = parse_at(line, l, next_field(&mut it, line, l, "a coordinate")?, "a coordinate")?;
+        }
+        expect_end(line, l, it.next().unwrap_or(""))?;
+        Ok(Point3::new(coords[0], coords[1], coords[2]))
+    }
+
+    fn parse_scan(s: &str) -> Result<HashSet<Point3<isize>>, ParseError> {
+        let mut lines = s.split('\n');
+        let header = lines.next().unwrap();
+        let number = header.strip_prefix("--- scanner ").and_then(|l| l.strip_suffix(" ---"));
+        if number.is_none_or(|n| n.parse::<usize>().is_err()) {
+            return Err(ParseError::at(1, header, header, "a scanner header"))
+        }
+        lines.enumerate().map(|(ind, l)| parse_beacon(ind + 2, l)).collect()
+    }
+
+    let scans = sections(input).iter().map(|section| section.parse(parse_scan)).collect::<Result<Vec<_>, _>>()?;
+    if scans.is_empty() {
+        return Err(ParseError::new(1, 1, "", "a scanner header"))
+    }
+    Ok(scans)
+}
+
+fn find_12_intersection(set_1: &HashSet<Point3<isize>>, set_2: &HashSet<Point3<isize>>) -> Option<(HashSet<Point3<isize>>, HashSet<Point3<isize>>, Transform)> {
+    let set_1_x: HashSet<_> = set_1.iter().map(|pt| pt.x).collect();
+    let set_1_xy: HashSet<_> = set_1.iter().map(|pt| Point2::new(pt.x, pt.y)).collect();
+    for orientation in 0..Rotation::N_CUBE {
+        let rotation = Rotation::cube(orientation);
+        let rotated = rotate_set(set_2, &rotation);
+        for xd in -8000..8000 {
+            progress::checkpoint();
+            let translated = translate_set(&rotated, Point3::new(xd, 0, 0));
+            let translated_x: Vec<_> = translated.iter().map(|pt| pt.x).collect();
+            if translated_x.iter().filter(|tx| set_1_x.contains(tx)).count() < 12 {
+                continue
+            }
+            for yd in -8000..8000 {
+                let translated = translate_set(&rotated, Point3::new(xd, yd, 0));
+                let translated_xy: Vec<_> = translated.iter().map(|pt| Point2::new(pt.x, pt.y)).collect();
+                if translated_xy.iter().filter(|txy| set_1_xy.contains(txy)).count() < 12 {
+                    continue
+                }
+                for zd in -8000..8000 {
+                    let position = Point3::new(xd, yd, zd);
+                    let translated = translate_set(&rotated, position);
+                    let intersecting: HashSet<_> = set_1.intersection(&translated).copied().collect();
+                    if intersecting.len() >= 12 {
+                        return Some((intersecting, translated, Transform::new(rotation, position)))
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The scans of all scanners. Both parts come out of the same slow
+/// alignment, so it is done once and kept for the other part.
+pub struct Scans {
+    pub scans: Vec<HashSet<Point3<isize>>>,
+    aligned: OnceLock<Vec<Transform>>
+}
+
+impl Scans {
+    pub fn new(scans: Vec<HashSet<Point3<isize>>>) -> Self {
+        Self { scans, aligned: OnceLock::new() }
+    }
+
+    /// Where each scanner is relative to the first one, and how it is
+    /// turned: a beacon it reports at `pt` is at `pose.apply(pt)`.
+    pub fn poses(&self) -> &Vec<Transform> {
+        self.aligned.get_or_init(|| align(&self.scans))
+    }
+
+    pub fn solve(&self) -> (usize, usize) {
+        answers(&self.scans, self.poses())
+    }
+}
+
+/// The pose of every scanner, in the order of the scans, found by aligning
+/// all scans with the first one. With the `parallel` feature, each round
+/// tries all scans that are left at the same time and pairs every one that
+/// overlaps, instead of the first one found.
+pub fn align(scans: &Vec<HashSet<Point3<isize>>>) -> Vec<Transform> {
+    let mut scans: Vec<_> = scans.iter().cloned().enumerate().collect();
+    let (_, first_scan) = scans.remove(0);
+    let mut paired_scans = vec!((first_scan, 0, Transform::IDENTITY));
+    let n_scans = scans.len() + 1;
+    #[cfg(feature = "parallel")]
+    let n_tried = AtomicUsize::new(0);
+    #[cfg(not(feature = "parallel"))]
+    let mut n_tried = 0;
+    loop {
+        let prev_scans_len = scans.len();
+        progress::log(Level::Info, || format!("Scans left to pair: {}", scans.len()));
+        #[cfg(feature = "parallel")]
+        {
+            let progress = progress::current();
+            let n_paired = paired_scans.len();
+            let found: Vec<_> = scans.par_iter().map(|(scanner, scan)| progress::within(&progress, || {
+                paired_scans.iter().find_map(|(p_scan, _, _)| {
+                    let n_tried = n_tried.fetch_add(1, Ordering::Relaxed) + 1;
+                    progress::report(|| Metrics {
+                        explored: n_tried,
+                        open: Some(prev_scans_len),
+                        best: None,
+                        done: Some((n_paired - 1) as f64 / (n_scans - 1) as f64)
+                    });
+                    find_12_intersection(p_scan, scan).map(|(_, transformed, pose)| (transformed, *scanner, pose))
+                })
+            })).collect();
+            for (ind, paired) in found.into_iter().enumerate().rev() {
+                if let Some(paired) = paired {
+                    scans.remove(ind);
+                    paired_scans.push(paired)
+                }
+            }
+        }
+        #[cfg(not(feature = "parallel"))]
+        for ind in 0..(scans.len()) {
+            let mut did_pair = false;
+            for jnd in 0..(paired_scans.len()) {
+                n_tried += 1;
+                progress::report(|| Metrics {
+                    explored: n_tried,
+                    open: Some(scans.len()),
+                    best: None,
+                    done: Some((paired_scans.len() - 1) as f64 / (n_scans - 1) as f64)
+                });
+                let p_scan = &paired_scans[jnd].0;
+                if let Some((_intersection, transformed, pose)) = find_12_intersection(p_scan, &scans[ind].1) {
+                    let (scanner, _) = scans.remove(ind);
+                    paired_scans.push((transformed, scanner, pose));
+                    did_pair = true;
+                    break;
+                }
+            }
+            if did_pair {
+                break
+            }
+        }
+        if scans.is_empty() {
+            break
+        }
+        if scans.len() == prev_scans_len {
+            panic!("Could not pair.")
+        }
+    }
+    paired_scans.sort_by_key(|(_, scanner, _)| *scanner);
+    paired_scans.into_iter().map(|(_, _, pose)| pose).collect()
+}
+
+/// All beacons, relative to the first scanner.
+pub fn beacons(scans: &Vec<HashSet<Point3<isize>>>, poses: &Vec<Transform>) -> HashSet<Point3<isize>> {
+    scans.iter().zip(poses).flat_map(|(scan, pose)| scan.iter().map(|pt| pose.apply(*pt))).collect()
+}
+
+/// The two scanners that are furthest apart, and their distance.
+pub fn farthest_scanners(poses: &Vec<Transform>) -> (usize, usize, usize) {
+    poses.iter().enumerate().tuple_combinations()
+        .map(|((ind, a), (jnd, b))| (ind, jnd, a.translation.manhattan_distance(b.translation)))
+        .max_by_key(|(_, _, dist)| *dist)
+        .unwrap_or((0, 0, 0))
+}
+
+fn answers(scans: &Vec<HashSet<Point3<isize>>>, poses: &Vec<Transform>) -> (usize, usize) {
+    (beacons(scans, poses).len(), farthest_scanners(poses).2)
+}
+
+/// The number of beacons and the largest distance between two scanners.
+pub fn solve_it(scans: &Vec<HashSet<Point3<isize>>>) -> (usize, usize) {
+    answers(scans, &align(scans))
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Scans;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Scans, ParseError> {
+        Ok(Scans::new(load_scans(input)?))
+    }
+
+    fn part_1(scans: &Scans) -> usize {
+        scans.solve().0
+    }
+
+    fn part_2(scans: &Scans) -> usize {
+        scans.solve().1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Rng;
+    use common::differential::{smaller, towards_zero};
+    use common::property::Property;
+    use super::*;
+
+    type Point = Point3<isize>;
+
+    fn rotate(pt: Point, orientation: usize) -> Point {
+        Rotation::cube(orientation).apply(pt)
+    }
+
+    /// The orientation that turns points like `first` followed by `then`.
+    fn composed(first: usize, then: usize) -> Option<usize> {
+        (0..24).find(|orientation| Rotation::cube(*orientation) == Rotation::cube(first).then(&Rotation::cube(then)))
+    }
+
+    fn random_point(rng: &mut Rng) -> Point {
+        Point3::new(rng.range(-1000, 1000), rng.range(-1000, 1000), rng.range(-1000, 1000))
+    }
+
+    /// The points with one coordinate closer to 0.
+    fn shrink_point(pt: &Point) -> Vec<Point> {
+        let mut shrunk: Vec<Point> = towards_zero(pt.x).into_iter().map(|x| Point3 { x, ..*pt }).collect();
+        shrunk.extend(towards_zero(pt.y).into_iter().map(|y| Point3 { y, ..*pt }));
+        shrunk.extend(towards_zero(pt.z).into_iter().map(|z| Point3 { z, ..*pt }));
+        shrunk
+    }
+
+    /// The alignment tries the 24 orientations to turn one scan like another,
+    /// so they must be the rotations of a cube: each one different, closed
+    /// under composition, each with an inverse, and none a reflection.
+    #[test]
+    fn rotation_group() {
+        let turned: HashSet<_> = (0..24).map(|orientation| rotate(Point3::new(1, 2, 3), orientation)).collect();
+        assert_eq!(turned.len(), 24);
+        assert_eq!(rotate(Point3::new(1, 2, 3), 0), Point3::new(1, 2, 3));
+        Property {
+            generate: |rng| (random_point(rng), random_point(rng), rng.below(24), rng.below(24)),
+            shrink: |(p, q, first, then)| {
+                let mut shrunk: Vec<_> = shrink_point(p).into_iter().map(|p| (p, *q, *first, *then)).collect();
+                shrunk.extend(shrink_point(q).into_iter().map(|q| (*p, q, *first, *then)));
+                shrunk.extend(smaller(*first, 0).into_iter().map(|first| (*p, *q, first, *then)));
+                shrunk.extend(smaller(*then, 0).into_iter().map(|then| (*p, *q, *first, then)));
+                shrunk
+            },
+            law: |&(p, q, first, then)| {
+                let Some(both) = composed(first, then) else {
+                    return Err(format!("{} followed by {} is no orientation", first, then))
+                };
+                if rotate(rotate(p, first), then) != rotate(p, both) {
+                    return Err(format!("{} followed by {} is not {} for {:?}", first, then, both, p))
+                }
+                let Some(inverse) = (0..24).find(|inverse| composed(first, *inverse) == Some(0)) else {
+                    return Err(format!("{} has no inverse", first))
+                };
+                if rotate(rotate(p, first), inverse) != p {
+                    return Err(format!("{} does not undo {} for {:?}", inverse, first, p))
+                }
+                if rotate(p.cross(q), first) != rotate(p, first).cross(rotate(q, first)) {
+                    return Err(format!("{} mirrors {:?} and {:?}", first, p, q))
+                }
+                Ok(())
+            }
+        }.check(19, 2000)
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = load_scans("--- scanner 0 ---\n404,-588,-901\n528,-643\n").unwrap_err();
+        assert_eq!(err, ParseError::new(3, 9, "", "a coordinate"));
+        let err = load_scans("--- scanner 0 ---\n404,-588,-901\n\n--- scanner one ---").unwrap_err();
+        assert_eq!(err, ParseError::new(4, 1, "--- scanner one ---", "a scanner header"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use common::{Generate, Generated, Rng};
+use crate::Day20;
+
+impl Generate for Day20 {
+    const INPUT_SIZE: usize = 100;
+
+    /// A random key and a `size` by `size` image. A key that lights dark
+    /// neighbourhoods darkens lit ones, so the lit pixels stay finite.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let pixel = |rng: &mut Rng| if rng.chance(0.5) { '#' } else { '.' };
+        let mut key: Vec<char> = (0..512).map(|_| pixel(rng)).collect();
+        if key[0] == '#' {
+            key[511] = '.'
+        }
+        let mut lines = vec!(key.into_iter().collect::<String>(), String::new());
+        for _ in 0..size {
+            lines.push((0..size).map(|_| pixel(rng)).collect())
+        }
+        Generated::new(lines.join("\n"))
+    }
+}
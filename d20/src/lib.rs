@@ -0,0 +1,135 @@
+mod generate;
+mod simulate;
+mod visualize;
+
+use common::{ParseError, Solution};
+use common::input::{end_of_input, parse_char_rows, parse_one_line, sections, unexpected_section};
+use grid::Grid;
+
+pub fn parse_input(s: &str) -> Result<(Grid<char>, Vec<char>), ParseError> {
+    fn to_bit(c: &char) -> char {
+        if *c == '#' { '1' } else { '0' }
+    }
+
+    let sections = sections(s);
+    let key = match sections.first() {
+        Some(section) => section.parse(|s| parse_one_line(s, |key_line| {
+            let key = &parse_char_rows(key_line, "#.", "`#` or `.`")?[0];
+            if key.len() != 512 {
+                return Err(ParseError::at(1, key_line, key_line, "a key of 512 characters"))
+            }
+            Ok(key.iter().map(to_bit).collect::<Vec<char>>())
+        }))?,
+        None => return Err(ParseError::new(1, 1, "", "a key of 512 characters"))
+    };
+    let grid = match sections.get(1) {
+        Some(section) => section.parse(|s| Grid::parse_chars(s, "#.", "`#` or `.`"))?,
+        None => return Err(end_of_input(s, "an empty line and an image"))
+    };
+    if let Some(extra) = sections.get(2) {
+        return Err(unexpected_section(extra))
+    }
+    Ok((grid.map(to_bit), key))
+}
+
+fn transform_cell(grid: &Grid<char>, r: isize, c: isize, key: &Vec<char>, lit_infinity: bool) -> char {
+    let mut index_chars: Vec<char> = vec!();
+    for r_ind in (r - 1)..=(r + 1) {
+        for c_ind in (c - 1)..=(c + 1) {
+            index_chars.push(
+                if let Some(pos) = grid.coord(r_ind, c_ind) {
+                    grid[pos]
+                } else if lit_infinity {
+                    '1'
+                } else {
+                    '0'
+                }
+            )
+        }
+    }
+    let index_string = index_chars.into_iter().collect::<String>();
+    let index = usize::from_str_radix(&index_string, 2).unwrap();
+    key[index]
+}
+
+/// Enhances the image once. The result is one pixel larger on every side.
+pub fn transform(grid: &Grid<char>, key: &Vec<char>, lit_infinity: bool) -> Grid<char> {
+    Grid::from_fn(grid.n_rows() + 2, grid.n_cols() + 2, |pos|
+        transform_cell(grid, pos.row as isize - 1, pos.col as isize - 1, key, lit_infinity)
+    )
+}
+
+pub fn part_1(grid: &Grid<char>, key: &Vec<char>, alternating_infinity: bool) -> usize {
+    let grid_1 = transform(grid, key, false);
+    let grid_2 = transform(&grid_1, key, alternating_infinity);
+    grid_2.values().filter(|c| **c == '1').count()
+}
+
+pub fn part_2(grid: &Grid<char>, key: &Vec<char>, alternating_infinity: bool) -> usize {
+    let mut transformed: Grid<char> = grid.clone();
+    for _ in 0..25 {
+        transformed = transform(&transformed, key, false);
+        transformed = transform(&transformed, key, alternating_infinity);
+    }
+    transformed.values().filter(|c| **c == '1').count()
+}
+
+/// Whether the infinite background flips between dark and lit on every
+/// step, which happens when the key lights a pixel with a dark neighbourhood.
+pub fn has_alternating_infinity(key: &Vec<char>) -> bool {
+    key[0] == '1'
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = (Grid<char>, Vec<char>);
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<(Grid<char>, Vec<char>), ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &(Grid<char>, Vec<char>)) -> usize {
+        let (grid, key) = input;
+        part_1(grid, key, has_alternating_infinity(key))
+    }
+
+    fn part_2(input: &(Grid<char>, Vec<char>)) -> usize {
+        let (grid, key) = input;
+        part_2(grid, key, has_alternating_infinity(key))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn p1() {
+        let (grid, key) = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            part_1(&grid, &key, false),
+            35
+        )
+    }
+
+    #[test]
+    fn p2() {
+        let (grid, key) = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            part_2(&grid, &key, false),
+            3351
+        )
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = include_str!("../test");
+        let broken = input.replacen("#..#.\n", "#..#o\n", 1);
+        assert_eq!(parse_input(&broken).unwrap_err(), ParseError::new(3, 5, "o", "`#` or `.`"));
+        let err = parse_input(&input[1..]).unwrap_err();
+        assert_eq!(err.expected, "a key of 512 characters");
+    }
+}
\ No newline at end of file
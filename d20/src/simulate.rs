@@ -0,0 +1,73 @@
+use common::params::Params;
+use common::simulate::Simulate;
+use grid::Grid;
+use crate::{has_alternating_infinity, transform, Day20};
+
+/// The image after some enhancing steps.
+#[derive(Clone)]
+pub struct Image {
+    grid: Grid<char>,
+    key: Vec<char>,
+    steps: usize,
+    n_steps: usize
+}
+
+impl Image {
+    /// Whether the infinite background around the image is lit.
+    fn lit_infinity(&self) -> bool {
+        has_alternating_infinity(&self.key) && self.steps % 2 == 1
+    }
+}
+
+impl Simulate for Day20 {
+    type State = Image;
+
+    /// Part 1 enhances the image twice and part 2 50 times.
+    fn start(input: &(Grid<char>, Vec<char>), part: usize, _params: &Params) -> Image {
+        let (grid, key) = input;
+        Image { grid: grid.clone(), key: key.clone(), steps: 0, n_steps: if part == 1 { 2 } else { 50 } }
+    }
+
+    fn step(image: &Image) -> Option<Image> {
+        if image.steps == image.n_steps {
+            return None
+        }
+        let grid = transform(&image.grid, &image.key, image.lit_infinity());
+        Some(Image { grid, key: image.key.clone(), steps: image.steps + 1, ..*image })
+    }
+
+    fn draw(image: &Image) -> Vec<String> {
+        image.grid.rows().map(|row| row.iter().map(|bit| if *bit == '1' { '#' } else { '.' }).collect()).collect()
+    }
+
+    /// The lit pixels of the image, and whether those around it are lit.
+    fn counters(image: &Image) -> Vec<(&'static str, usize)> {
+        vec!(
+            ("lit", image.grid.values().filter(|bit| **bit == '1').count()),
+            ("lit_infinity", image.lit_infinity() as usize)
+        )
+    }
+
+    /// Every step grows the image by a pixel on each side.
+    fn origin(image: &Image) -> (isize, isize) {
+        (-(image.steps as isize), -(image.steps as isize))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn steps() {
+        let input = parse_input(include_str!("../test")).unwrap();
+        let image = Day20::start(&input, 1, &Params::default());
+        assert_eq!(Day20::draw(&image)[0], "#..#.");
+        let image = Day20::step(&Day20::step(&image).unwrap()).unwrap();
+        assert!(Day20::step(&image).is_none());
+        assert_eq!(Day20::draw(&image).len(), 9);
+        assert_eq!(Day20::origin(&image), (-2, -2));
+        assert_eq!(Day20::counters(&image), vec!(("lit", 35), ("lit_infinity", 0)));
+    }
+}
@@ -0,0 +1,41 @@
+use grid::Grid;
+use render::{Frame, Palette, Visualize};
+use crate::{has_alternating_infinity, transform, Day20};
+
+const N_STEPS: usize = 50;
+
+impl Visualize for Day20 {
+    fn palette() -> Palette {
+        Palette::Gray
+    }
+
+    /// The image before enhancing and after each of the 50 steps of part 2.
+    /// All frames are the size of the last; the smaller images are framed
+    /// by the infinite background as it is at their step.
+    fn frames(input: &(Grid<char>, Vec<char>)) -> Vec<Frame> {
+        let (grid, key) = input;
+        let alternating = has_alternating_infinity(key);
+        let (n_rows, n_cols) = (grid.n_rows() + 2 * N_STEPS, grid.n_cols() + 2 * N_STEPS);
+        let mut image = grid.clone();
+        let mut frames = vec!();
+        for step in 0..=N_STEPS {
+            let lit_infinity = alternating && step % 2 == 1;
+            let margin = N_STEPS - step;
+            let mut frame = Frame::new(n_rows, n_cols, 2);
+            for row in 0..n_rows {
+                for col in 0..n_cols {
+                    let lit = match image.coord(row as isize - margin as isize, col as isize - margin as isize) {
+                        Some(pos) => image[pos] == '1',
+                        None => lit_infinity
+                    };
+                    frame.set(row, col, lit as u8)
+                }
+            }
+            frames.push(frame);
+            if step < N_STEPS {
+                image = transform(&image, key, lit_infinity)
+            }
+        }
+        frames
+    }
+}
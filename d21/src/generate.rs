@@ -0,0 +1,15 @@
+use common::{Generate, Generated, Rng};
+use crate::Day21;
+
+impl Generate for Day21 {
+    const INPUT_SIZE: usize = 1;
+
+    /// Random starting positions of both players. `size` is ignored.
+    fn generate(rng: &mut Rng, _size: usize) -> Generated {
+        let input = format!(
+            "Player 1 starting position: {}\nPlayer 2 starting position: {}",
+            rng.range(1, 10), rng.range(1, 10)
+        );
+        Generated::new(input)
+    }
+}
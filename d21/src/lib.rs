@@ -0,0 +1,297 @@
+mod generate;
+
+use itertools::Itertools;
+use common::{ParseError, Solution};
+use common::count::{self, Count, Counted, Overflow};
+use common::input::{end_of_input, parse_lines};
+use common::params::Params;
+use common::parse::parse_at;
+
+/// The starting positions of the two players.
+pub fn parse_input(s: &str) -> Result<(usize, usize), ParseError> {
+    let starts = parse_lines(s, |n_line, line| {
+        let prefix = format!("Player {} starting position: ", n_line);
+        let pos = line.strip_prefix(prefix.as_str()).ok_or_else(|| ParseError::at(n_line, line, line, &format!("`{}`", prefix)))?;
+        match parse_at(n_line, line, pos, "a position from 1 to 10")? {
+            start @ 1..=10 => Ok(start),
+            _ => Err(ParseError::at(n_line, line, pos, "a position from 1 to 10"))
+        }
+    })?;
+    match starts[..] {
+        [start_1, start_2] => Ok((start_1, start_2)),
+        [_] => Err(end_of_input(s, "the starting position of player 2")),
+        _ => Err(ParseError::new(3, 1, s.lines().nth(2).unwrap_or(""), "end of input"))
+    }
+}
+
+struct Dice {
+    next: usize,
+    n_rolls: usize
+}
+
+impl Dice {
+    fn new() -> Self {
+        Self {next: 1, n_rolls: 0}
+    }
+
+    fn roll(&mut self) -> usize {
+        let roll = self.next;
+        self.next = if self.next < 100 {
+            self.next + 1
+        } else {
+            1
+        };  
+        self.n_rolls +=1;
+        roll
+    }
+}
+
+/// Games in the same state, and in how many universes they are. The count
+/// comes last so that sorted games in the same state are next to each other.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct QuantumGame<C> {
+    roll_state: usize,
+    roll_acc: usize,
+    pos_1: usize,
+    pos_2: usize,
+    score_1: usize,
+    score_2: usize,
+    finished: bool,
+    n_instances: C
+}
+
+fn new_pos(current: usize, roll: usize) -> usize {
+    (current + roll - 1) % 10 + 1
+}
+
+impl<C: Count> QuantumGame<C> {
+    fn new(start_1: usize, start_2: usize) -> Self {
+        Self {
+            n_instances: C::one(),
+            roll_state: 0,
+            roll_acc: 0,
+            pos_1: start_1,
+            pos_2: start_2,
+            score_1: 0,
+            score_2: 0,
+            finished: false
+        }
+    }
+
+    fn clone_empty(&self) -> Self {
+        Self {
+            n_instances: C::zero(),
+            roll_state: self.roll_state,
+            roll_acc: self.roll_acc,
+            pos_1: self.pos_1,
+            pos_2: self.pos_2,
+            score_1: self.score_1,
+            score_2: self.score_2,
+            finished: self.finished
+        }
+    }
+
+    fn spawn(&self, dice_outcome: usize, winning_score: usize) -> Self {
+        match self.roll_state {
+            0 | 1 | 3 | 4 => { 
+                Self {
+                    n_instances: self.n_instances.clone(),
+                    roll_state: self.roll_state + 1,
+                    roll_acc: self.roll_acc + dice_outcome,
+                    pos_1: self.pos_1,
+                    pos_2: self.pos_2,
+                    score_1: self.score_1,
+                    score_2: self.score_2,
+                    finished: self.finished
+                }
+            },
+            2 => {
+                let acc_dice = self.roll_acc + dice_outcome;
+                let new_pos_1 = new_pos(self.pos_1, acc_dice);
+                let new_score_1 = self.score_1 + new_pos_1;
+                Self {
+                    n_instances: self.n_instances.clone(),
+                    roll_state: self.roll_state + 1,
+                    roll_acc: 0,
+                    pos_1: new_pos_1,
+                    pos_2: self.pos_2,
+                    score_1: new_score_1,
+                    score_2: self.score_2,
+                    finished: new_score_1 >= winning_score
+                }
+            },
+            5 => {
+                let acc_dice = self.roll_acc + dice_outcome;
+                let new_pos_2 = new_pos(self.pos_2, acc_dice);
+                let new_score_2 = self.score_2 + new_pos_2;
+                Self {
+                    n_instances: self.n_instances.clone(),
+                    roll_state: 0,
+                    roll_acc: 0,
+                    pos_1: self.pos_1,
+                    pos_2: new_pos_2,
+                    score_1: self.score_1,
+                    score_2: new_score_2,
+                    finished: new_score_2 >= winning_score
+                }
+            },
+            _ => panic!("Illegal roll state: {}", self.roll_state)
+        }
+    }
+
+    fn split(&self, winning_score: usize) -> Vec<QuantumGame<C>> {
+        vec!(self.spawn(1, winning_score), self.spawn(2, winning_score), self.spawn(3, winning_score))
+    }
+}
+
+pub fn part_2(start_1: usize, start_2: usize)  -> Result<usize, Overflow> {
+    most_wins(start_1, start_2, 21)
+}
+
+/// The number of universes in which the player who wins in the most
+/// universes wins, when the game is won at `winning_score`.
+pub fn most_wins(start_1: usize, start_2: usize, winning_score: usize) -> Result<usize, Overflow> {
+    most_wins_as(start_1, start_2, winning_score)
+}
+
+/// Like `most_wins`, counting the universes in `C`.
+pub fn most_wins_as<C: Count>(start_1: usize, start_2: usize, winning_score: usize) -> Result<C, Overflow> {
+    let mut ongoing_games = vec!(QuantumGame::<C>::new(start_1, start_2)); 
+    let mut finished_games: Vec<QuantumGame<C>> = vec!();
+    while !ongoing_games.is_empty() {
+        let (mut new_finished_games, mut new_ongoing_games): (Vec<QuantumGame<C>>, Vec<QuantumGame<C>>) =  ongoing_games.iter().flat_map(|g| g.split(winning_score)).partition(|qg| qg.finished);
+        new_ongoing_games.sort();
+        ongoing_games = vec!();
+        for (mut k, v) in new_ongoing_games.into_iter().chunk_by(|qg| qg.clone_empty()).into_iter() {
+            k.n_instances = v.into_iter().try_fold(C::zero(), |acc, g| acc.plus(&g.n_instances))?;
+            ongoing_games.push(k)
+        }
+        finished_games.append(&mut new_finished_games);
+    }
+    let total_games = count::sum(finished_games.iter().map(|g| &g.n_instances))?;
+    let player_1_wins = count::sum(finished_games.iter().filter(|g| g.score_1 >= winning_score).map(|g| &g.n_instances))?;
+    let player_2_wins = total_games.minus(&player_1_wins)?;
+    Ok(player_1_wins.max(player_2_wins))
+}
+
+pub fn part_1(start_1: usize, start_2: usize)  -> usize {
+    let mut pos_1 = start_1;
+    let mut pos_2 = start_2;
+    let mut score_1 = 0;
+    let mut score_2 = 0;
+    let mut dice = Dice::new();
+    loop {
+        pos_1 = new_pos(pos_1, dice.roll() + dice.roll() + dice.roll());
+        score_1 += pos_1;
+        if score_1 >= 1000 {
+            break
+        }
+        pos_2 = new_pos(pos_2, dice.roll() + dice.roll() + dice.roll());
+        score_2 += pos_2;
+        if score_2 >= 1000 {
+            break
+        }
+    }
+    dice.n_rolls * score_1.min(score_2)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = (usize, usize);
+    type Output1 = usize;
+    type Output2 = Result<usize, Overflow>;
+
+    /// 2 reads the starting positions from the input instead of using fixed ones.
+    const VERSION: u32 = 2;
+
+    fn parse(input: &str) -> Result<(usize, usize), ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(start: &(usize, usize)) -> usize {
+        part_1(start.0, start.1)
+    }
+
+    fn part_2(start: &(usize, usize)) -> Result<usize, Overflow> {
+        part_2(start.0, start.1)
+    }
+}
+
+impl Counted for Day21 {
+    fn part_1_as<C: Count>(start: &(usize, usize), _params: &Params) -> Result<C, Overflow> {
+        C::from_usize(part_1(start.0, start.1))
+    }
+
+    fn part_2_as<C: Count>(start: &(usize, usize), _params: &Params) -> Result<C, Overflow> {
+        most_wins_as(start.0, start.1, 21)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::differential::{smaller, Differential};
+    use super::*;
+
+    /// Wins of each player over all universes, playing every single one of
+    /// them out roll by roll.
+    fn wins_one_by_one(pos: [usize; 2], score: [usize; 2], player: usize, winning_score: usize) -> [usize; 2] {
+        let mut wins = [0, 0];
+        for rolls in (1..=3).cartesian_product(1..=3).cartesian_product(1..=3) {
+            let ((r1, r2), r3) = rolls;
+            let (mut pos, mut score) = (pos, score);
+            pos[player] = new_pos(pos[player], r1 + r2 + r3);
+            score[player] += pos[player];
+            if score[player] >= winning_score {
+                wins[player] += 1
+            } else {
+                let later = wins_one_by_one(pos, score, 1 - player, winning_score);
+                wins = [wins[0] + later[0], wins[1] + later[1]]
+            }
+        }
+        wins
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_input("Player 1 starting position: 4\nPlayer 2 starting position: 8\n"), Ok((4, 8)));
+        assert_eq!(parse_input("Player 1 starting position: 4\nPlayer 2 starting position: 11").unwrap_err(), ParseError::new(2, 29, "11", "a position from 1 to 10"));
+        assert_eq!(parse_input("Player 1 starting position: 4").unwrap_err().expected, "the starting position of player 2");
+        assert_eq!(parse_input("Player 2 starting position: 4").unwrap_err().expected, "`Player 1 starting position: `");
+    }
+
+    #[test]
+    fn p1() {
+        let (start_1, start_2) = (4, 8);
+        assert_eq!(
+            part_1(start_1, start_2),
+            739785
+        )
+    }
+
+    #[test]
+    fn many_universes() {
+        assert_eq!(most_wins_as::<u32>(4, 8, 21), Err(Overflow));
+        assert_eq!(most_wins_as::<u64>(4, 8, 21), Ok(444356092776315));
+        assert_eq!(most_wins_as::<u128>(4, 8, 21), Ok(444356092776315));
+        assert_eq!(Day21::part_2_as::<u32>(&(4, 8), &Params::default()), Err(Overflow));
+    }
+
+    #[test]
+    fn same_as_every_universe() {
+        Differential {
+            generate: |rng| (1 + rng.below(10), 1 + rng.below(10), 1 + rng.below(8)),
+            shrink: |(start_1, start_2, winning_score)| {
+                let mut shrunk: Vec<_> = smaller(*winning_score, 1).into_iter().map(|w| (*start_1, *start_2, w)).collect();
+                shrunk.extend(smaller(*start_1, 1).into_iter().map(|s| (s, *start_2, *winning_score)));
+                shrunk.extend(smaller(*start_2, 1).into_iter().map(|s| (*start_1, s, *winning_score)));
+                shrunk
+            },
+            optimized: |(start_1, start_2, winning_score)| most_wins(*start_1, *start_2, *winning_score).unwrap(),
+            reference: |(start_1, start_2, winning_score)| {
+                let wins = wins_one_by_one([*start_1, *start_2], [0, 0], 0, *winning_score);
+                wins[0].max(wins[1])
+            }
+        }.check(21, 100)
+    }
+}
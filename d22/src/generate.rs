@@ -0,0 +1,34 @@
+use common::{Generate, Generated, Rng};
+use crate::Day22;
+
+/// A reboot step with a random range of `min_len` to `max_len` cubes on
+/// each axis, starting within `min..=max`.
+fn step(rng: &mut Rng, on: bool, min: isize, max: isize, min_len: isize, max_len: isize) -> String {
+    let mut ranges = vec!();
+    for axis in ["x", "y", "z"] {
+        let start = rng.range(min, max);
+        ranges.push(format!("{}={}..{}", axis, start, start + rng.range(min_len, max_len) - 1));
+    }
+    format!("{} {}", if on { "on" } else { "off" }, ranges.join(","))
+}
+
+impl Generate for Day22 {
+    const INPUT_SIZE: usize = 420;
+
+    /// `size` reboot steps. The first twentieth of them, starting with an
+    /// `on`, stay inside the initialization region; the others are far
+    /// larger than it.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let n_init = (size / 20).max(1);
+        let steps: Vec<String> = (0..size).map(|ind| {
+            let on = ind == 0 || rng.chance(0.7);
+            if ind < n_init {
+                let len = rng.range(10, 50);
+                step(rng, on, -50, 51 - len, len, len)
+            } else {
+                step(rng, on, -100000, 100000, 5000, 50000)
+            }
+        }).collect();
+        Generated::new(steps.join("\n"))
+    }
+}
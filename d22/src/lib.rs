@@ -0,0 +1,289 @@
+mod generate;
+
+use common::{ParseError, Solution};
+use common::geometry::{Box3, Point3};
+use common::input::parse_lines;
+use common::parse::{expect_end, next_field, parse_at, split_at};
+
+/// A cuboid of cubes, from its lowest corner to its highest.
+pub type Cube = Box3;
+
+/// The largest coordinate, in either direction, of a cube. It keeps the
+/// volume of every region in the range of an `isize`.
+pub const MAX_COORDINATE: isize = 1_000_000;
+
+pub fn parse_input(s: &str) -> Result<Vec<(bool, Cube)>, ParseError> {
+    fn parse_coordinate(line: usize, l: &str, text: &str) -> Result<isize, ParseError> {
+        let expected = format!("a coordinate from {} to {}", -MAX_COORDINATE, MAX_COORDINATE);
+        match parse_at::<isize>(line, l, text, &expected)? {
+            coordinate if coordinate.abs() <= MAX_COORDINATE => Ok(coordinate),
+            _ => Err(ParseError::at(line, l, text, &expected))
+        }
+    }
+
+    fn parse_range(line: usize, l: &str, coord_it: &mut std::str::Split<char>, axis: &str) -> Result<(isize, isize), ParseError> {
+        let expected = format!("`{}=`", axis);
+        let spec = next_field(coord_it, line, l, &expected)?;
+        let range = spec.strip_prefix(axis).and_then(|s| s.strip_prefix('=')).ok_or_else(|| ParseError::at(line, l, spec, &expected))?;
+        let (min, max) = split_at(line, l, range, "..")?;
+        let min = parse_coordinate(line, l, min)?;
+        let max = parse_coordinate(line, l, max)?;
+        if min > max {
+            return Err(ParseError::at(line, l, range, "an increasing range"))
+        }
+        Ok((min, max))
+    }
+
+    parse_lines(s, |n_line, line| {
+        let (on, coord_specs) = split_at(n_line, line, line, " ")?;
+        let on = match on {
+            "on" => true,
+            "off" => false,
+            _ => return Err(ParseError::at(n_line, line, on, "on or off"))
+        };
+        let mut coord_it = coord_specs.split(',');
+        let (xmin, xmax) = parse_range(n_line, line, &mut coord_it, "x")?;
+        let (ymin, ymax) = parse_range(n_line, line, &mut coord_it, "y")?;
+        let (zmin, zmax) = parse_range(n_line, line, &mut coord_it, "z")?;
+        expect_end(n_line, line, coord_it.next().unwrap_or(""))?;
+        Ok((on, Cube::new(Point3::new(xmin, ymin, zmin), Point3::new(xmax, ymax, zmax))))
+    })
+}
+
+/// Adds `cube` to cuboids that do not overlap, keeping them apart.
+pub fn merge(cube: &Cube, set: Vec<Cube>) -> Vec<Cube> {
+    if set.iter().any(|member| member.contains(cube)) {
+        set
+    } else {
+        let set = set.into_iter().filter(|c| !cube.contains(c));
+        let (intersecting, mut set): (Vec<Cube>, Vec<Cube>) = set.into_iter().partition(|p| cube.intersects(p));
+        let mut diff_set: Vec<_> = intersecting.into_iter().flat_map(|p| p.difference(cube)).collect();
+        set.append(&mut diff_set);
+        set.push(*cube);
+        set
+    }
+}
+
+/// The region of the initialization procedure in part 1.
+pub const INIT_REGION: Cube = Cube::new(Point3::new(-50, -50, -50), Point3::new(50, 50, 50));
+
+pub fn initialization_steps(cubes: &Vec<(bool, Cube)>) -> Vec<(bool, Cube)> {
+    cubes.iter().filter(|(_, cube)| INIT_REGION.contains(cube)).cloned().collect()
+}
+
+pub fn reboot(cubes: &Vec<(bool, Cube)>) -> Vec<Cube> {
+    let mut merged = vec!();
+    for (on, cube) in cubes {
+        if *on {
+            merged = merge(cube, merged)
+        } else {
+            merged = merged.iter().flat_map(|c| c.difference(cube)).collect()
+        }
+    }
+    merged
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Vec<(bool, Cube)>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<(bool, Cube)>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(cubes: &Vec<(bool, Cube)>) -> usize {
+        reboot(&initialization_steps(cubes)).iter().map(|c| c.volume()).sum()
+    }
+
+    fn part_2(cubes: &Vec<(bool, Cube)>) -> usize {
+        reboot(cubes).iter().map(|c| c.volume()).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use common::Rng;
+    use common::differential::{remove_each, shrink_each, Differential};
+    use common::property::Property;
+    use super::*;
+
+    /// Counts the cubes that are on by switching every single one of them.
+    fn reboot_voxels(cubes: &Vec<(bool, Cube)>) -> usize {
+        let mut voxels = HashSet::new();
+        for (on, cube) in cubes {
+            for x in cube.min.x..=cube.max.x {
+                for y in cube.min.y..=cube.max.y {
+                    for z in cube.min.z..=cube.max.z {
+                        if *on {
+                            voxels.insert((x, y, z));
+                        } else {
+                            voxels.remove(&(x, y, z));
+                        }
+                    }
+                }
+            }
+        }
+        voxels.len()
+    }
+
+    /// The cuboids one smaller than `cube` in one direction.
+    fn thinner(cube: &Cube) -> Vec<Cube> {
+        let (min, max) = (cube.min, cube.max);
+        let mut thinner = vec!();
+        if min.x < max.x {
+            thinner.push(Cube::new(Point3 { x: min.x + 1, ..min }, max));
+            thinner.push(Cube::new(min, Point3 { x: max.x - 1, ..max }));
+        }
+        if min.y < max.y {
+            thinner.push(Cube::new(Point3 { y: min.y + 1, ..min }, max));
+            thinner.push(Cube::new(min, Point3 { y: max.y - 1, ..max }));
+        }
+        if min.z < max.z {
+            thinner.push(Cube::new(Point3 { z: min.z + 1, ..min }, max));
+            thinner.push(Cube::new(min, Point3 { z: max.z - 1, ..max }));
+        }
+        thinner
+    }
+
+    fn random_cube(rng: &mut Rng) -> Cube {
+        let mut range = || {
+            let (a, b) = (rng.range(-6, 6), rng.range(-6, 6));
+            (a.min(b), a.max(b))
+        };
+        let ((xmin, xmax), (ymin, ymax), (zmin, zmax)) = (range(), range(), range());
+        Cube::new(Point3::new(xmin, ymin, zmin), Point3::new(xmax, ymax, zmax))
+    }
+
+    /// The overlap of two cubes, if they overlap.
+    fn overlap(a: &Cube, b: &Cube) -> Option<Cube> {
+        let min = Point3::new(a.min.x.max(b.min.x), a.min.y.max(b.min.y), a.min.z.max(b.min.z));
+        let max = Point3::new(a.max.x.min(b.max.x), a.max.y.min(b.max.y), a.max.z.min(b.max.z));
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Cube::new(min, max))
+    }
+
+    /// `difference` keeps the pieces of `split` outside the other cube, so
+    /// the pieces must fill the cube without overlapping, and each must be
+    /// either inside the other cube or outside it.
+    #[test]
+    fn split_partitions() {
+        Property {
+            generate: |rng| (random_cube(rng), random_cube(rng)),
+            shrink: |(cube, wedge)| {
+                let mut shrunk: Vec<_> = thinner(cube).into_iter().map(|c| (c, *wedge)).collect();
+                shrunk.extend(thinner(wedge).into_iter().map(|w| (*cube, w)));
+                shrunk
+            },
+            law: |(cube, wedge)| {
+                let pieces = cube.split(wedge);
+                if let Some(piece) = pieces.iter().find(|piece| !cube.contains(piece)) {
+                    return Err(format!("{:?} sticks out", piece))
+                }
+                for (ind, piece) in pieces.iter().enumerate() {
+                    if let Some(other) = pieces[ind + 1..].iter().find(|other| piece.intersects(other)) {
+                        return Err(format!("{:?} overlaps {:?}", piece, other))
+                    }
+                    if piece.intersects(wedge) && !wedge.contains(piece) {
+                        return Err(format!("{:?} is partly inside the wedge", piece))
+                    }
+                }
+                let volume: usize = pieces.iter().map(|piece| piece.volume()).sum();
+                if volume != cube.volume() {
+                    return Err(format!("The pieces have volume {} instead of {}", volume, cube.volume()))
+                }
+                let left: usize = cube.difference(wedge).iter().map(|piece| piece.volume()).sum();
+                let removed = overlap(cube, wedge).map_or(0, |o| o.volume());
+                if left + removed != cube.volume() {
+                    return Err(format!("The difference has volume {} instead of {}", left, cube.volume() - removed))
+                }
+                Ok(())
+            }
+        }.check(22, 2000)
+    }
+
+    #[test]
+    fn diff() {
+        let c1 = Cube::new(Point3::new(0, 0, 0), Point3::new(2, 2, 2));
+        let c2 = Cube::new(Point3::new(1, 1, 1), Point3::new(1, 1, 1));
+
+        assert_eq!(
+            c1.volume(),
+            27
+        );
+        assert_eq!(
+            c2.volume(),
+            1
+        );
+        let diff = c1.difference(&c2);
+        println!("The diff {:#?}", diff);
+        assert_eq!(
+            diff.iter().map(|d| d.volume()).sum::<usize>(),
+            26
+        )
+    }
+
+    #[test]
+    fn t5() {
+        let cubes = parse_input(include_str!("../test5")).unwrap();
+        let merged = reboot(&cubes);
+        assert_eq!(merged.iter().map(|c| c.volume()).sum::<usize>(), 590784);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("on x=-20..26,y=-36..17,z=-47..7\nof x=-20..33,y=-21..23,z=-26..28").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 1, "of", "on or off"));
+        let err = parse_input("on x=-20..26,y=-36..17,z=-47.7").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 26, "-47.7", "`..`"));
+        let err = parse_input("on x=-20..26,y=-36..17").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 23, "", "`z=`"));
+        let err = parse_input("off x=26..-20,y=-36..17,z=-47..7").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 7, "26..-20", "an increasing range"));
+    }
+
+    #[test]
+    fn same_as_voxels() {
+        Differential {
+            generate: |rng| (0..1 + rng.below(8)).map(|_| {
+                let mut range = || {
+                    let (a, b) = (rng.range(-4, 4), rng.range(-4, 4));
+                    (a.min(b), a.max(b))
+                };
+                let ((xmin, xmax), (ymin, ymax), (zmin, zmax)) = (range(), range(), range());
+                (rng.chance(0.6), Cube::new(Point3::new(xmin, ymin, zmin), Point3::new(xmax, ymax, zmax)))
+            }).collect::<Vec<_>>(),
+            shrink: |cubes| {
+                let mut shrunk = remove_each(cubes, 1);
+                shrunk.extend(shrink_each(cubes, |(on, cube)| thinner(cube).into_iter().map(|c| (*on, c)).collect()));
+                shrunk
+            },
+            optimized: |cubes| reboot(cubes).iter().map(|c| c.volume()).sum(),
+            reference: reboot_voxels
+        }.check(22, 500)
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        // Found by fuzzing: a cube whose volume does not fit.
+        let err = parse_input("on x=0..2,y=-6..7,z=6..3333333333333333333").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 24, "3333333333333333333", "a coordinate from -1000000 to 1000000"));
+        let err = parse_input("on x=-1000001..0,y=0..0,z=0..0").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 6, "-1000001", "a coordinate from -1000000 to 1000000"));
+        let cubes = parse_input("on x=-1000000..1000000,y=-1000000..1000000,z=-1000000..1000000").unwrap();
+        assert_eq!(Day22::part_2(&cubes), 2000001usize.pow(3));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let cubes = parse_input(include_str!("../test5")).unwrap();
+        let json = serde_json::to_string(&cubes).unwrap();
+        assert!(json.starts_with("[[true,{\"min\":{\"x\":-20,\"y\":-36,\"z\":-47},\"max\":{\"x\":26,"));
+        let read: Vec<(bool, Cube)> = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&read).unwrap(), json);
+        assert_eq!(Day22::part_1(&read), 590784);
+    }
+}
@@ -0,0 +1,60 @@
+use common::explain::{Explain, Fact};
+use crate::{find_moves, unfold, Day23, Pod};
+
+/// The moves in the order they are made, with the hallway in row 0 and the
+/// slots below it.
+fn explain(config: &Vec<Pod>) -> Fact {
+    let moves = find_moves(config);
+    let energy = moves.iter().map(|m| m.energy).sum::<usize>();
+    Fact::record([
+        ("moves", Fact::list(moves.into_iter().map(|m| Fact::record([
+            ("pod", Fact::from(m.kind)),
+            ("from", Fact::from(vec!(m.from.0, m.from.1))),
+            ("to", Fact::from(vec!(m.to.0, m.to.1))),
+            ("energy", Fact::from(m.energy))
+        ])))),
+        ("energy", Fact::from(energy))
+    ])
+}
+
+impl Explain for Day23 {
+    fn explain_1(config: &Vec<Pod>) -> Fact {
+        explain(config)
+    }
+
+    /// The moves in the unfolded burrow.
+    fn explain_2(config: &Vec<Pod>) -> Fact {
+        explain(&unfold(config))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn move_sequence() {
+        let config = vec!(
+            Pod::new('B', 1, 2),
+            Pod::new('A', 2, 2),
+            Pod::new('C', 1, 4),
+            Pod::new('D', 2, 4),
+            Pod::new('B', 1, 6),
+            Pod::new('C', 2, 6),
+            Pod::new('D', 1, 8),
+            Pod::new('A', 2, 8),
+        );
+        let moves = find_moves(&config);
+        assert_eq!(moves.iter().map(|m| m.energy).sum::<usize>(), 12521);
+        let mut positions: Vec<_> = config.iter().map(|pod| (pod.kind, pod.row, pod.col)).collect();
+        for m in &moves {
+            let pod = positions.iter_mut().find(|(kind, row, col)| (*kind, *row, *col) == (m.kind, m.from.0, m.from.1)).unwrap();
+            (pod.1, pod.2) = m.to;
+        }
+        positions.sort();
+        assert_eq!(positions, vec!(('A', 1, 2), ('A', 2, 2), ('B', 1, 4), ('B', 2, 4), ('C', 1, 6), ('C', 2, 6), ('D', 1, 8), ('D', 2, 8)));
+        let explained = Day23::explain_1(&config).to_text();
+        assert!(explained.starts_with("moves:\n  - pod: "), "{}", explained);
+        assert!(explained.ends_with("\nenergy: 12521"));
+    }
+}
@@ -0,0 +1,18 @@
+use common::{Generate, Generated, Rng};
+use crate::Day23;
+
+impl Generate for Day23 {
+    const INPUT_SIZE: usize = 1;
+
+    /// The eight amphipods shuffled over the side rooms. `size` is ignored.
+    fn generate(rng: &mut Rng, _size: usize) -> Generated {
+        let mut pods: Vec<char> = "AABBCCDD".chars().collect();
+        rng.shuffle(&mut pods);
+        let room_row = |row: &[char]| row.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("#");
+        let input = format!(
+            "#############\n#...........#\n###{}###\n  #{}#\n  #########",
+            room_row(&pods[..4]), room_row(&pods[4..])
+        );
+        Generated::new(input)
+    }
+}
@@ -0,0 +1,442 @@
+mod explain;
+mod generate;
+mod simulate;
+
+use common::{ParseError, Solution};
+use common::input::{end_of_input, normalize};
+use search::{dijkstra, SearchState};
+
+static VALID_POSITIONS: [(usize, usize); 27] = [
+    (0, 0), (0, 1), (0, 2), (0, 3), (0, 4), (0, 5), (0, 6), (0, 7), (0, 8), (0, 9), (0, 10),
+                    (1, 2), (2, 2), (3, 2), (4, 2),
+                    (1, 4), (2, 4), (3, 4), (4, 4),
+                    (1, 6), (2, 6), (3, 6), (4, 6),
+                    (1, 8), (2, 8), (3, 8), (4, 8)
+];
+
+static SLOT_COLS: [usize; 4] = [2, 4, 6, 8];
+fn get_slot(kind: char, depth: usize) -> Vec<(usize, usize)> {
+    let col = match kind {
+        'A' => SLOT_COLS[0],
+        'B' => SLOT_COLS[1],
+        'C' => SLOT_COLS[2],
+        'D' => SLOT_COLS[3],
+        _ => panic!("Illegal pod kind.") 
+    };
+    (1..=depth).map(|row| (row, col)).collect()
+}
+
+/// Number of pods that fit in each slot, two for part 1 and four for part 2.
+fn slot_depth(config: &Vec<Pod>) -> usize {
+    config.len() / SLOT_COLS.len()
+}
+
+static ENTRANCES: [(usize, usize); 4] = [(0, 2), (0, 4), (0, 6), (0, 8)];
+fn get_entrance(pod: &Pod) -> (usize, usize) {
+    match pod.kind {
+        'A' => ENTRANCES[0],
+        'B' => ENTRANCES[1],
+        'C' => ENTRANCES[2],
+        'D' => ENTRANCES[3],
+        _ => panic!("Illegal pod kind.") 
+    }
+}
+
+fn is_entrance(pos: (usize, usize)) -> bool {
+    ENTRANCES.contains(&pos)
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pod {
+    kind: char,
+    row: usize,
+    col: usize,
+    total_cost: usize
+}
+
+impl Pod {
+    pub fn new(kind: char, row: usize, col: usize) -> Self {
+        let total_cost = 0;
+        Self {
+            kind, row, col, total_cost
+        }
+    }
+
+    fn state_without_cost(&self) -> (char, usize, usize) {
+        (self.kind, self.row, self.col)
+    }
+
+    fn is_in_hallway(&self) -> bool {
+        self.row == 0
+    }    
+
+    fn step_cost(&self) -> usize {
+        match self.kind {
+            'A' => 1,
+            'B' => 10,
+            'C' => 100,
+            'D' => 1000,
+            _ => panic!("Illegal Pod kind.")
+        }
+    }
+
+    fn clone_and_move(&self, new_pos: (usize, usize)) -> Pod {
+        let n_steps = 
+            if self.row == 0 || new_pos.0 == 0 {
+                ((self.row as isize - new_pos.0 as isize).abs() + (self.col as isize - new_pos.1 as isize).abs()) as usize
+            } else {
+                self.row + new_pos.0 + (self.col as isize - new_pos.1 as isize).unsigned_abs()
+            };
+        let movement_cost = n_steps * self.step_cost();
+        let mut pod = self.clone();
+        pod.row = new_pos.0;
+        pod.col = new_pos.1;
+        pod.total_cost += movement_cost;
+        pod
+    }
+}
+
+fn is_empty(pos: (usize, usize), config: &Vec<Pod>) -> bool {
+    !config.iter().any(|p| (p.row, p.col) == pos)
+}
+
+fn path_to_entrance_open(pod: &Pod, config: &Vec<Pod>) -> bool {
+    let entrance = get_entrance(pod);
+    let col_pod = pod.col;
+    let col_entrance = entrance.1;
+    is_path_from_pod_position_open(col_pod, col_entrance, config)
+}
+
+fn get_destination(pod: &Pod, config: &Vec<Pod>) -> Option<(usize, usize)> {
+    let slot = get_slot(pod.kind, slot_depth(config));
+
+    if path_to_entrance_open(pod, config) {
+        if let Some(_first_empty) = slot.iter().find(|pos| is_empty(**pos, config)) {
+            if let Some(first_occupied) = slot.iter().find(|pos| !is_empty(**pos, config)) {
+                let first_pod = config.iter().find(|p| (p.row, p.col) == *first_occupied).unwrap();
+                if is_pod_done(first_pod, config) {
+                    Some((first_occupied.0 - 1, first_occupied.1))
+                } else {
+                    None
+                }
+            } else {
+                Some(slot[slot.len()-1])
+            }
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+fn is_path_from_pod_position_open(pod_col: usize, col_other: usize, config: &Vec<Pod>) -> bool {
+    let mut path_cols = if pod_col < col_other {
+        (pod_col + 1)..col_other
+    } else {
+        (col_other)..pod_col
+    };
+    path_cols.all(|col| is_empty((0, col), config))
+}
+
+fn get_hallway_destinations(config: &Vec<Pod>, pod: &Pod) -> Vec<(usize, usize)> {
+    VALID_POSITIONS.iter().filter(|pos| pos.0 == 0 && !is_entrance(**pos) && is_empty(**pos, config) && is_path_from_pod_position_open(pod.col, pos.1, config)).copied().collect()
+}
+
+fn make_configuration(current_config: &Vec<Pod>, to_move: &Pod, destination: (usize, usize)) -> Vec<Pod> {
+    let mut new_config: Vec<_> = current_config.iter().filter(|p| *p != to_move).cloned().collect();
+    new_config.push(
+        to_move.clone_and_move(destination)
+    );
+    new_config
+}
+
+fn position_ok(pod: &Pod, config: &Vec<Pod>) -> bool {
+    let slot = get_slot(pod.kind, slot_depth(config));
+    slot.contains(&(pod.row, pod.col)) && {
+        let start_row = pod.row + 1;
+        let end_row = slot.len();
+        (start_row..=end_row).all(|row| config.iter().any(|p| p.row == row && p.col == pod.col && p.kind == pod.kind))
+    }
+}
+
+fn can_leave_slot(pod: &Pod, config: &Vec<Pod>) -> bool {
+    if pod.row == 1 {
+        return !position_ok(pod, config)
+    }
+    let start_row = 1;
+    let end_row = pod.row;
+    let all_empty = (start_row..end_row).all(|row| is_empty((row, pod.col), config));
+    let pos_ok = position_ok(pod, config);
+    
+    all_empty && !pos_ok
+}
+
+fn is_pod_done(pod: &Pod, config: &Vec<Pod>) -> bool {
+    let slot = get_slot(pod.kind, slot_depth(config));
+    if pod.col != slot[0].1 {
+        return false
+    } 
+    let first_row = pod.row;
+    let last_row = slot.len();
+    (first_row..=last_row).all(|row| config.iter().any(|p| (p.row, p.col) == (row, pod.col) && p.kind == pod.kind)) 
+}
+
+fn is_config_done(path: &Vec<Pod>) -> bool {
+    path.iter().all(|pod| is_pod_done(pod, path))
+}
+
+fn get_next_paths(path: &Vec<Pod>) -> Vec<Vec<Pod>> {
+    let config = path;
+    let non_done_pods: Vec<_> = config.iter().filter(|p| !is_pod_done(p, config)).collect();
+    let mut next_configs: Vec<Vec<Pod>> = vec!();
+    for pod in non_done_pods {
+        if pod.is_in_hallway() {
+            if let Some(dest) = get_destination(pod, config) {
+                let next_conf = make_configuration(config, pod, dest);
+                next_configs.push(next_conf)
+            } 
+        } else if can_leave_slot(pod, config) {
+            if let Some(dest) = get_destination(pod, config) {
+                let next_conf = make_configuration(config, pod, dest);
+                next_configs.push(next_conf)
+            }
+            for dest in get_hallway_destinations(config, pod) {
+                next_configs.push(make_configuration(config, pod, dest))
+            }
+        }
+    }
+    next_configs
+}
+
+/// The burrow as drawn in the puzzle input, with `?` for an amphipod.
+static DIAGRAM: [&str; 5] = ["#############", "#...........#", "###?#?#?#?###", "  #?#?#?#?#", "  #########"];
+
+/// The amphipods in the side rooms of a burrow drawn like in the puzzle,
+/// with an empty hallway and two of each kind.
+pub fn parse_input(s: &str) -> Result<Vec<Pod>, ParseError> {
+    let input = normalize(s);
+    let lines: Vec<_> = input.split('\n').collect();
+    let what = |e: char| if e == '?' { String::from("an amphipod `A`, `B`, `C` or `D`") } else { format!("`{}`", e) };
+    let mut config = vec!();
+    for (row, expected) in DIAGRAM.iter().enumerate() {
+        let n_line = row + 1;
+        let Some(line) = lines.get(row).map(|line| line.trim_end()) else {
+            let missing = if expected.contains('?') { String::from("a row of side rooms") } else { format!("`{}`", expected) };
+            return Err(end_of_input(s, &missing))
+        };
+        for (col, (c, e)) in line.chars().zip(expected.chars()).enumerate() {
+            let fits = if e == '?' { "ABCD".contains(c) } else { c == e };
+            if !fits {
+                return Err(ParseError::new(n_line, col + 1, &c.to_string(), &what(e)))
+            }
+            if e == '?' {
+                config.push(Pod::new(c, row - 1, col - 1))
+            }
+        }
+        // The line matches the diagram as far as it goes, so it is ASCII.
+        match (line.len(), expected.len()) {
+            (n, n_expected) if n < n_expected => return Err(ParseError::end_of_line(n_line, line, &what(expected.as_bytes()[n] as char))),
+            (n, n_expected) if n > n_expected => return Err(ParseError::at(n_line, line, &line[n_expected..], "end of line")),
+            _ => ()
+        }
+    }
+    if let Some(extra) = lines.get(DIAGRAM.len()) {
+        return Err(ParseError::new(DIAGRAM.len() + 1, 1, extra, "end of input"))
+    }
+    for kind in ['A', 'B', 'C', 'D'] {
+        if let Some(third) = config.iter().filter(|pod| pod.kind == kind).nth(2) {
+            return Err(ParseError::new(third.row + 2, third.col + 2, &kind.to_string(), "two amphipods of each kind"))
+        }
+    }
+    Ok(config)
+}
+
+/// The rows that part 2 unfolds between the two rows of the diagram.
+static UNFOLDED_ROWS: [[char; 4]; 2] = [['D', 'C', 'B', 'A'], ['D', 'B', 'A', 'C']];
+
+/// Unfolds a configuration with slots of depth two into the one of part 2.
+pub fn unfold(config: &Vec<Pod>) -> Vec<Pod> {
+    let mut unfolded: Vec<_> = config.iter().map(|pod| {
+        let row = if pod.row == 1 { 1 } else { 2 + UNFOLDED_ROWS.len() };
+        Pod::new(pod.kind, row, pod.col)
+    }).collect();
+    for (ind, kinds) in UNFOLDED_ROWS.iter().enumerate() {
+        for (kind, col) in kinds.iter().zip(SLOT_COLS) {
+            unfolded.push(Pod::new(*kind, ind + 2, col))
+        }
+    }
+    unfolded
+}
+
+fn total_cost(config: &Vec<Pod>) -> usize {
+    config.iter().map(|pod| pod.total_cost).sum()
+}
+
+/// A configuration of the pods, as a state in the search for the cheapest
+/// way of moving them all into their slots.
+#[derive(Clone)]
+struct Burrow(Vec<Pod>);
+
+impl SearchState for Burrow {
+    type Key = Vec<(char, usize, usize)>;
+
+    fn key(&self) -> Self::Key {
+        let mut config_state_without_cost: Vec<_> = self.0.iter().map(|pod| pod.state_without_cost()).collect();
+        config_state_without_cost.sort();
+        config_state_without_cost
+    }
+
+    fn neighbours(&self) -> Vec<(Self, usize)> {
+        let cost = total_cost(&self.0);
+        get_next_paths(&self.0).into_iter().map(|next| {
+            let step_cost = total_cost(&next) - cost;
+            (Burrow(next), step_cost)
+        }).collect()
+    }
+
+    fn is_goal(&self) -> bool {
+        is_config_done(&self.0)
+    }
+}
+
+pub fn find_minimal_cost(config: &Vec<Pod>) -> usize {
+    dijkstra(Burrow(config.clone())).expect("The pods can always be moved into their slots").cost
+}
+
+/// A pod moving from one position to another, as row and column with the
+/// hallway in row 0, and the energy that took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    pub kind: char,
+    pub from: (usize, usize),
+    pub to: (usize, usize),
+    pub energy: usize
+}
+
+/// The cheapest way of moving all pods into their slots, one move at a time.
+pub fn find_moves(config: &Vec<Pod>) -> Vec<Move> {
+    let found = dijkstra(Burrow(config.clone())).expect("The pods can always be moved into their slots");
+    found.path.windows(2).map(|configs| {
+        let (before, after) = (&configs[0].0, &configs[1].0);
+        // `make_configuration` puts the pod that moved last.
+        let moved = after.last().unwrap();
+        let from = before.iter().find(|pod| !after.contains(pod)).unwrap();
+        Move { kind: moved.kind, from: (from.row, from.col), to: (moved.row, moved.col), energy: moved.total_cost - from.total_cost }
+    }).collect()
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Vec<Pod>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    /// 2 reads the burrow from the input instead of using a fixed one.
+    const VERSION: u32 = 2;
+
+    fn parse(input: &str) -> Result<Vec<Pod>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(config: &Vec<Pod>) -> usize {
+        find_minimal_cost(config)
+    }
+
+    fn part_2(config: &Vec<Pod>) -> usize {
+        find_minimal_cost(&unfold(config))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_config() -> Vec<Pod> {
+        vec!(
+            Pod::new('B', 1, 2),
+            Pod::new('D', 2, 2),
+            Pod::new('D', 3, 2),
+            Pod::new('A', 4, 2),
+            Pod::new('C', 1, 4),
+            Pod::new('C', 2, 4),
+            Pod::new('B', 3, 4),
+            Pod::new('D', 4, 4),
+            Pod::new('B', 1, 6),
+            Pod::new('B', 2, 6),
+            Pod::new('A', 3, 6),
+            Pod::new('C', 4, 6),
+            Pod::new('D', 1, 8),
+            Pod::new('A', 2, 8),
+            Pod::new('C', 3, 8),
+            Pod::new('A', 4, 8),
+        )
+    }
+
+    fn folded_test_config() -> Vec<Pod> {
+        vec!(
+            Pod::new('B', 1, 2),
+            Pod::new('A', 2, 2),
+            Pod::new('C', 1, 4),
+            Pod::new('D', 2, 4),
+            Pod::new('B', 1, 6),
+            Pod::new('C', 2, 6),
+            Pod::new('D', 1, 8),
+            Pod::new('A', 2, 8),
+        )
+    }
+
+    #[test]
+    fn parse() {
+        let mut parsed: Vec<_> = parse_input(include_str!("../test")).unwrap().iter().map(|pod| pod.state_without_cost()).collect();
+        let mut expected: Vec<_> = folded_test_config().iter().map(|pod| pod.state_without_cost()).collect();
+        parsed.sort();
+        expected.sort();
+        assert_eq!(parsed, expected);
+        let input = include_str!("../test");
+        assert_eq!(parse_input(&input.replacen("B", "E", 1)).unwrap_err(), ParseError::new(3, 4, "E", "an amphipod `A`, `B`, `C` or `D`"));
+        assert_eq!(parse_input(&input.replacen("B", "A", 1)).unwrap_err(), ParseError::new(4, 10, "A", "two amphipods of each kind"));
+        assert_eq!(parse_input(&input.replacen("#...", "#A..", 1)).unwrap_err(), ParseError::new(2, 2, "A", "`.`"));
+        assert_eq!(parse_input(&input.replacen("#A#D#C#A#", "#A#D#C#A", 1)).unwrap_err().expected, "`#`");
+        assert_eq!(parse_input(&format!("{}\n#", input)).unwrap_err().expected, "end of input");
+        assert_eq!(parse_input(&input[..30]).unwrap_err(), ParseError::new(3, 3, "", "`#`"));
+        assert_eq!(parse_input(&input[..28]).unwrap_err().expected, "a row of side rooms");
+    }
+
+    #[test]
+    fn unfolded() {
+        let mut unfolded: Vec<_> = unfold(&folded_test_config()).iter().map(|pod| pod.state_without_cost()).collect();
+        let mut expected: Vec<_> = test_config().iter().map(|pod| pod.state_without_cost()).collect();
+        unfolded.sort();
+        expected.sort();
+        assert_eq!(unfolded, expected)
+    }
+
+    #[test]
+    fn p1_folded() {
+        assert_eq!(
+            find_minimal_cost(&folded_test_config()),
+            12521
+        )
+    }
+
+    #[test]
+    fn p1() {
+        assert_eq!(
+            find_minimal_cost(&test_config()),
+            44169
+        )
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let config = parse_input(include_str!("../test")).unwrap();
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.starts_with("[{\"kind\":\"B\",\"row\":1,\"col\":2,\"total_cost\":0},"));
+        let read: Vec<Pod> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, config);
+    }
+}
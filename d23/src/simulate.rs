@@ -0,0 +1,82 @@
+use common::params::Params;
+use common::simulate::Simulate;
+use crate::{find_moves, get_next_paths, make_configuration, slot_depth, total_cost, unfold, Day23, Move, Pod, SLOT_COLS};
+
+/// The pods after some of the moves of the cheapest way into their slots.
+#[derive(Clone)]
+pub struct Burrow {
+    config: Vec<Pod>,
+    moves: Vec<Move>,
+    n_made: usize
+}
+
+impl Simulate for Day23 {
+    type State = Burrow;
+
+    /// Part 2 moves the pods of the unfolded burrow.
+    fn start(config: &Vec<Pod>, part: usize, _params: &Params) -> Burrow {
+        let config = if part == 1 { config.clone() } else { unfold(config) };
+        let moves = find_moves(&config);
+        Burrow { config, moves, n_made: 0 }
+    }
+
+    /// Makes the next move.
+    fn step(burrow: &Burrow) -> Option<Burrow> {
+        let next = burrow.moves.get(burrow.n_made)?;
+        let pod = burrow.config.iter().find(|pod| (pod.row, pod.col) == next.from).unwrap();
+        let config = make_configuration(&burrow.config, pod, next.to);
+        Some(Burrow { config, moves: burrow.moves.clone(), n_made: burrow.n_made + 1 })
+    }
+
+    /// The burrow the way the puzzle input draws it.
+    fn draw(burrow: &Burrow) -> Vec<String> {
+        let cell = |row: usize, col: usize| burrow.config.iter()
+            .find(|pod| (pod.row, pod.col) == (row, col))
+            .map_or('.', |pod| pod.kind);
+        let mut lines = vec!(String::from("#############"));
+        lines.push(format!("#{}#", (0..11).map(|col| cell(0, col)).collect::<String>()));
+        for row in 1..=slot_depth(&burrow.config) {
+            let slots: Vec<_> = SLOT_COLS.iter().map(|col| cell(row, *col).to_string()).collect();
+            let edge = if row == 1 { "##" } else { "  " };
+            lines.push(format!("{}#{}#{}", edge, slots.join("#"), edge.trim()));
+        }
+        lines.push(String::from("  #########"));
+        lines
+    }
+
+    /// The energy used so far, the moves made and how many moves there are
+    /// to choose from next.
+    fn counters(burrow: &Burrow) -> Vec<(&'static str, usize)> {
+        vec!(
+            ("energy", total_cost(&burrow.config)),
+            ("moves", burrow.n_made),
+            ("options", get_next_paths(&burrow.config).len())
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn moves() {
+        let config = vec!(
+            Pod::new('B', 1, 2),
+            Pod::new('A', 2, 2),
+            Pod::new('C', 1, 4),
+            Pod::new('D', 2, 4),
+            Pod::new('B', 1, 6),
+            Pod::new('C', 2, 6),
+            Pod::new('D', 1, 8),
+            Pod::new('A', 2, 8),
+        );
+        let mut burrow = Day23::start(&config, 1, &Params::default());
+        assert_eq!(Day23::draw(&burrow), vec!("#############", "#...........#", "###B#C#B#D###", "  #A#D#C#A#", "  #########"));
+        while let Some(next) = Day23::step(&burrow) {
+            burrow = next;
+        }
+        assert_eq!(Day23::draw(&burrow)[2..4], ["###A#B#C#D###", "  #A#B#C#D#"]);
+        assert_eq!(Day23::counters(&burrow), vec!(("energy", 12521), ("moves", burrow.moves.len()), ("options", 0)));
+    }
+}
@@ -0,0 +1,80 @@
+use common::{Generate, Generated, Rng};
+use crate::Day24;
+
+/// The digit program of the real input, with its three parameters.
+fn digit_program(divisor: isize, x_offset: isize, y_offset: isize) -> String {
+    format!(
+        "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\nmul y 0\nadd y 25\n\
+         mul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {}\nmul y x\nadd z y",
+        divisor, x_offset, y_offset
+    )
+}
+
+impl Generate for Day24 {
+    const INPUT_SIZE: usize = 1;
+
+    /// A MONAD of the same shape as the real one: seven digits push onto a
+    /// stack in base 26 and seven pop, and each popping digit must equal
+    /// the digit it pops plus a known difference. That makes the largest
+    /// and smallest model numbers known. `size` is ignored.
+    fn generate(rng: &mut Rng, _size: usize) -> Generated {
+        // Pushes and pops in an order that never pops an empty stack.
+        let mut pushes = vec!(true);
+        let (mut n_open, mut n_pushed) = (1, 1);
+        while pushes.len() < 14 {
+            let push = n_pushed < 7 && (n_open == 0 || rng.chance(0.5));
+            n_open = if push { n_open + 1 } else { n_open - 1 };
+            n_pushed += push as usize;
+            pushes.push(push)
+        }
+        let mut programs = vec!();
+        let mut stack = vec!();
+        let mut largest = [0; 14];
+        let mut smallest = [0; 14];
+        for (ind, push) in pushes.into_iter().enumerate() {
+            if push {
+                let y_offset = rng.range(1, 16);
+                stack.push((ind, y_offset));
+                programs.push(digit_program(1, rng.range(10, 16), y_offset))
+            } else {
+                let (pushed, y_offset) = stack.pop().unwrap();
+                let diff = rng.range(-8, 8);
+                largest[ind] = 9.min(9 + diff);
+                largest[pushed] = largest[ind] - diff;
+                smallest[ind] = 1.max(1 + diff);
+                smallest[pushed] = smallest[ind] - diff;
+                programs.push(digit_program(26, diff - y_offset, rng.range(1, 16)))
+            }
+        }
+        let number = |digits: [isize; 14]| digits.iter().fold(0, |acc, digit| acc * 10 + digit);
+        Generated::with_answers(programs.join("\n\n"), number(largest), number(smallest))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Solution;
+    use crate::ALU;
+    use super::*;
+
+    fn z_after(dig_progs: &Vec<Vec<crate::Instruction>>, model_number: &str) -> isize {
+        let mut alu = ALU::new();
+        for (prog, digit) in dig_progs.iter().zip(model_number.chars()) {
+            for instruction in prog {
+                alu.process_instruction(instruction, &mut vec!(digit.to_digit(10).unwrap() as isize)).unwrap()
+            }
+        }
+        alu.z
+    }
+
+    #[test]
+    fn test_generated_answers() {
+        let generated = Day24::generate(&mut Rng::new(24), 1);
+        let dig_progs = Day24::parse(&generated.input).unwrap();
+        let largest = generated.part_1.unwrap();
+        let smallest = generated.part_2.unwrap();
+        assert_eq!(z_after(&dig_progs, &largest), 0);
+        assert_eq!(z_after(&dig_progs, &smallest), 0);
+        assert!(smallest <= largest);
+    }
+}
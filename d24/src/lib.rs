@@ -0,0 +1,379 @@
+mod generate;
+
+use std::collections::HashSet;
+use common::{ParseError, Solution};
+use common::input::{end_of_input, parse_lines, sections};
+use common::parse::{expect_end, next_field, parse_at};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operand {
+    Int {val: isize},
+    Register {register: char}
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Instruction{
+    Inp {a: Operand},
+    Add {a: Operand, b: Operand},
+    Mul {a: Operand, b: Operand},
+    Div {a: Operand, b: Operand},
+    Mod {a: Operand, b: Operand},
+    Eql {a: Operand, b: Operand}
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ALU {
+    pub w: isize,
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+} 
+
+pub fn parse_instruction(line: usize, s: &str) -> Result<Instruction, ParseError> {
+    fn get_operand(words: &mut std::str::SplitWhitespace, line: usize, s: &str, register_only: bool) -> Result<Operand, ParseError> {
+        let expected = if register_only { "a register" } else { "a register or a number" };
+        let operand_word  = next_field(words, line, s, expected)?;
+        match  operand_word {
+            "w" | "x" | "y" | "z" => Ok(Operand::Register{ register: operand_word.chars().next().unwrap()}),
+            _ if register_only    => Err(ParseError::at(line, s, operand_word, expected)),
+            _                     => Ok(Operand::Int{ val: parse_at(line, s, operand_word, expected)?})
+        }
+    }
+    let mut words = s.split_whitespace();
+    let ins_word = next_field(&mut words, line, s, "an instruction")?;
+    let instruction = match ins_word {
+        "inp" => Instruction::Inp{ a: get_operand(&mut words, line, s, true)? },
+        "add" => Instruction::Add{ a: get_operand(&mut words, line, s, true)?, b: get_operand(&mut words, line, s, false)? },
+        "mul" => Instruction::Mul{ a: get_operand(&mut words, line, s, true)?, b: get_operand(&mut words, line, s, false)? },
+        "div" => Instruction::Div{ a: get_operand(&mut words, line, s, true)?, b: get_operand(&mut words, line, s, false)? },
+        "mod" => Instruction::Mod{ a: get_operand(&mut words, line, s, true)?, b: get_operand(&mut words, line, s, false)? },
+        "eql" => Instruction::Eql{ a: get_operand(&mut words, line, s, true)?, b: get_operand(&mut words, line, s, false)? },
+        _ => return Err(ParseError::at(line, s, ins_word, "an instruction"))
+    };
+    expect_end(line, s, words.next().unwrap_or(""))?;
+    Ok(instruction)
+}
+
+/// Parses the digit programs, one for each of the 14 digits of the model
+/// number, separated by empty lines.
+pub fn parse_dig_progs(s: &str) -> Result<Vec<Vec<Instruction>>, ParseError> {
+    let dig_progs = sections(s).iter()
+        .map(|section| section.parse(|s| parse_lines(s, parse_instruction)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if dig_progs.len() != 14 {
+        return Err(end_of_input(s, "14 digit programs separated by empty lines"))
+    }
+    Ok(dig_progs)
+}
+
+impl ALU {
+    pub fn new() -> Self {
+        Self{ w: 0, x: 0, y: 0, z: 0 }
+    }
+
+    fn get(&self, operand: Operand) -> isize {
+        match operand {
+            Operand::Register{ register } =>
+                match register {
+                    'w' => self.w,
+                    'x' => self.x,
+                    'y' => self.y,
+                    'z' => self.z,
+                    _  =>  panic!("Illegal register {}", register)
+                },
+            Operand::Int{ val } => val
+        }
+    }
+
+    fn set(&mut self, operand: Operand, val: isize) {
+        match operand {
+            Operand::Register{ register } =>
+                match register {
+                    'w' => self.w = val,
+                    'x' => self.x = val,
+                    'y' => self.y = val,
+                    'z' => self.z = val,
+                    _  =>  panic!("Illegal register {}", register)
+                },
+            Operand::Int { val: _ } => panic!("Cannot set an integer literal.")
+        }
+    }
+
+    /// Runs one instruction. An instruction the ALU cannot carry out leaves
+    /// it as it was and fails with the fault.
+    pub fn process_instruction(&mut self, instruction: &Instruction, input_stack: &mut Vec<isize>) -> Result<(), Fault> {
+        let (a, val) = match instruction {
+            Instruction::Inp {a}    => (a, input_stack.pop().ok_or(Fault::NoInput)?),
+            Instruction::Add {a, b} => (a, self.get(*a).checked_add(self.get(*b)).ok_or(Fault::Overflow)?),
+            Instruction::Mul {a, b} => (a, self.get(*a).checked_mul(self.get(*b)).ok_or(Fault::Overflow)?),
+            Instruction::Div {a, b} => match self.get(*b) {
+                0 => return Err(Fault::DivisionByZero),
+                b => (a, self.get(*a).checked_div(b).ok_or(Fault::Overflow)?)
+            },
+            Instruction::Mod {a, b} => match (self.get(*a), self.get(*b)) {
+                (a_val, b_val) if a_val < 0 || b_val <= 0 => return Err(Fault::InvalidModulo),
+                (a_val, b_val) => (a, a_val % b_val)
+            },
+            Instruction::Eql {a, b} => (a, if self.get(*a) == self.get(*b) { 1 } else {0} )
+        };
+        self.set(*a, val);
+        Ok(())
+    }
+}
+
+/// Why an ALU could not carry out an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// `inp` with no input left.
+    NoInput,
+    /// `div` by zero.
+    DivisionByZero,
+    /// `mod` of a negative number or by a number that is not positive.
+    InvalidModulo,
+    /// A result that does not fit in an `isize`.
+    Overflow
+}
+
+fn run_delta(prog: &Vec<Instruction>, alu: &ALU, input: isize) -> Result<ALU, Fault> {
+    let mut alu = alu.clone();
+    for instruction in prog {
+        alu.process_instruction(instruction, &mut vec!(input))?;
+    }
+    Ok(alu)
+}
+
+/// Finds the largest or smallest model number accepted by the digit programs.
+/// Model numbers that make the ALU fault are not accepted.
+pub fn find_model_number(dig_progs: &Vec<Vec<Instruction>>, largest: bool) -> isize {
+    let digits: Vec<isize> = if largest { (1..=9).rev().collect() } else { (1..=9).collect() };
+    let mut memo_0: HashSet<ALU> =  HashSet::new();
+    let mut memo_1: HashSet<ALU> =  HashSet::new();
+    let mut memo_2: HashSet<ALU> =  HashSet::new();
+    let mut memo_3: HashSet<ALU> =  HashSet::new();
+    let mut memo_4: HashSet<ALU> =  HashSet::new();
+    let mut memo_5: HashSet<ALU> =  HashSet::new();
+    let mut memo_6: HashSet<ALU> =  HashSet::new();
+    let mut memo_7: HashSet<ALU> =  HashSet::new();
+    let mut memo_8: HashSet<ALU> =  HashSet::new();
+    let mut memo_9: HashSet<ALU> =  HashSet::new();
+    let mut memo_10: HashSet<ALU> =  HashSet::new();
+    let mut memo_11: HashSet<ALU> =  HashSet::new();
+    let mut memo_12: HashSet<ALU> =  HashSet::new();
+
+    for &ind0 in &digits {
+        let Ok(alu0) = run_delta(&dig_progs[0], &ALU::new(), ind0) else {
+            continue
+        };
+        if memo_0.contains(&alu0) {
+            continue
+        }
+        memo_0.insert(alu0.clone());
+        for &ind1 in &digits {
+            let Ok(alu1) = run_delta(&dig_progs[1], &alu0, ind1) else {
+                continue
+            };
+            if memo_1.contains(&alu1) {
+                continue
+            }
+            memo_1.insert(alu1.clone());
+            for &ind2 in &digits {
+                let Ok(alu2) = run_delta(&dig_progs[2], &alu1, ind2) else {
+                    continue
+                };
+                if memo_2.contains(&alu2) {
+                    continue
+                }
+                memo_2.insert(alu2.clone());
+                for &ind3 in &digits {
+                    let Ok(alu3) = run_delta(&dig_progs[3], &alu2, ind3) else {
+                        continue
+                    };
+                    if memo_3.contains(&alu3) {
+                        continue
+                    }
+                    memo_3.insert(alu3.clone());
+                    for &ind4 in &digits {
+                        let Ok(alu4) = run_delta(&dig_progs[4], &alu3, ind4) else {
+                            continue
+                        };
+                        if memo_4.contains(&alu4) {
+                            continue
+                        }
+                        memo_4.insert(alu4.clone());
+                        for &ind5 in &digits {
+                            let Ok(alu5) = run_delta(&dig_progs[5], &alu4, ind5) else {
+                                continue
+                            };
+                            if memo_5.contains(&alu5) {
+                                continue
+                            }
+                            memo_5.insert(alu5.clone());
+                            for &ind6 in &digits {
+                                let Ok(alu6) = run_delta(&dig_progs[6], &alu5, ind6) else {
+                                    continue
+                                };
+                                if memo_6.contains(&alu6) {
+                                    continue
+                                }
+                                memo_6.insert(alu6.clone());
+
+                                for &ind7 in &digits {
+                                    let Ok(alu7) = run_delta(&dig_progs[7], &alu6, ind7) else {
+                                        continue
+                                    };
+                                    if memo_7.contains(&alu7) {
+                                        continue
+                                    }
+                                    memo_7.insert(alu7.clone());
+                                    for &ind8 in &digits {
+                                        let Ok(alu8) = run_delta(&dig_progs[8], &alu7, ind8) else {
+                                            continue
+                                        };
+                                        if memo_8.contains(&alu8) {
+                                            continue
+                                        }
+                                        memo_8.insert(alu8.clone());
+                                        for &ind9 in &digits {
+                                            let Ok(alu9) = run_delta(&dig_progs[9], &alu8, ind9) else {
+                                                continue
+                                            };
+                                            if memo_9.contains(&alu9) {
+                                                continue
+                                            }
+                                            memo_9.insert(alu9.clone());
+                                            for &ind10 in &digits {
+                                                let Ok(alu10) = run_delta(&dig_progs[10], &alu9, ind10) else {
+                                                    continue
+                                                };
+                                                if memo_10.contains(&alu10) {
+                                                    continue
+                                                }
+                                                memo_10.insert(alu10.clone());
+                                                for &ind11 in &digits {
+                                                    let Ok(alu11) = run_delta(&dig_progs[11], &alu10, ind11) else {
+                                                        continue
+                                                    };
+                                                    if memo_11.contains(&alu11) {
+                                                        continue
+                                                    }
+                                                    memo_11.insert(alu11.clone());
+                                                    for &ind12 in &digits {
+                                                        let Ok(alu12) = run_delta(&dig_progs[12], &alu11, ind12) else {
+                                                            continue
+                                                        };
+                                                        if memo_12.contains(&alu12) {
+                                                            continue
+                                                        }
+                                                        memo_12.insert(alu12.clone());
+                                                        for &ind13 in &digits {
+                                                            let Ok(alu13) = run_delta(&dig_progs[13], &alu12, ind13) else {
+                                                                continue
+                                                            };
+                                                            if alu13.z == 0 {
+                                                                return 
+                                                                ind13 + 
+                                                                ind12 * 10 + 
+                                                                ind11 * 100 + 
+                                                                ind10 * 1000 + 
+                                                                ind9  * 10000 + 
+                                                                ind8  * 100000 + 
+                                                                ind7  * 1000000 + 
+                                                                ind6  * 10000000 + 
+                                                                ind5  * 100000000 + 
+                                                                ind4  * 1000000000 + 
+                                                                ind3  * 10000000000 + 
+                                                                ind2  * 100000000000 + 
+                                                                ind1  * 1000000000000 + 
+                                                                ind0  * 10000000000000;  
+                                                            }
+                                                        }                       
+                                                    }                       
+                                                }            
+                                            }            
+                                        }            
+                                    }            
+                                }            
+                            }            
+                        }            
+                    }            
+                }        
+            }
+        }
+    }
+    panic!("No solution found!")
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<Vec<Instruction>>;
+    type Output1 = isize;
+    type Output2 = isize;
+
+    fn parse(input: &str) -> Result<Vec<Vec<Instruction>>, ParseError> {
+        parse_dig_progs(input)
+    }
+
+    fn part_1(dig_progs: &Vec<Vec<Instruction>>) -> isize {
+        find_model_number(dig_progs, true)
+    }
+
+    fn part_2(dig_progs: &Vec<Vec<Instruction>>) -> isize {
+        find_model_number(dig_progs, false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(parse_instruction(3, "add x w 1").unwrap_err(), ParseError::new(3, 9, "1", "end of line"));
+        assert_eq!(parse_instruction(3, "mul 26 x").unwrap_err(), ParseError::new(3, 5, "26", "a register"));
+        assert_eq!(parse_instruction(3, "sub x 2").unwrap_err(), ParseError::new(3, 1, "sub", "an instruction"));
+        assert_eq!(parse_instruction(3, "div z").unwrap_err(), ParseError::new(3, 6, "", "a register or a number"));
+        let err = parse_dig_progs("inp w\nadd z w\n\ninp w\nadd z w").unwrap_err();
+        assert_eq!(err, ParseError::new(5, 8, "", "14 digit programs separated by empty lines"));
+    }
+
+    #[test]
+    fn test_faults() {
+        let run = |program: &str, inputs: &[isize]| {
+            let mut alu = ALU::new();
+            let mut inputs = inputs.to_vec();
+            for (ind, l) in program.lines().enumerate() {
+                alu.process_instruction(&parse_instruction(ind + 1, l).unwrap(), &mut inputs)?
+            }
+            Ok(alu)
+        };
+        // Found by fuzzing: a modulo by zero and more inputs than digits.
+        assert_eq!(run("mod x 0", &[]), Err(Fault::InvalidModulo));
+        assert_eq!(run("inp w\ninp w", &[1]), Err(Fault::NoInput));
+        assert_eq!(run("add x -1\nmod x 2", &[]), Err(Fault::InvalidModulo));
+        assert_eq!(run("div x 0", &[]), Err(Fault::DivisionByZero));
+        assert_eq!(run("add x 9223372036854775807\nadd x 1", &[]), Err(Fault::Overflow));
+        assert_eq!(run("add x -9223372036854775807\nadd x -1\ndiv x -1", &[]), Err(Fault::Overflow));
+        assert_eq!(run("inp w\nadd x -7\ndiv x w\nadd z 7\nmod z w", &[2]), Ok(ALU { w: 2, x: -3, y: 0, z: 1 }));
+        let mut alu = ALU { w: 0, x: 5, y: 0, z: 0 };
+        assert_eq!(alu.process_instruction(&parse_instruction(1, "div x w").unwrap(), &mut vec!()), Err(Fault::DivisionByZero));
+        assert_eq!(alu.x, 5);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let dig_progs = Day24::parse(include_str!("../input")).unwrap();
+        let json = serde_json::to_string(&dig_progs).unwrap();
+        assert!(json.starts_with("[[{\"Inp\":{\"a\":{\"Register\":{\"register\":\"w\"}}}},"));
+        let read: Vec<Vec<Instruction>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&read).unwrap(), json);
+        let alu = ALU { w: 1, x: -2, y: 3, z: 26 };
+        let json = serde_json::to_string(&alu).unwrap();
+        assert_eq!(json, "{\"w\":1,\"x\":-2,\"y\":3,\"z\":26}");
+        assert_eq!(serde_json::from_str::<ALU>(&json).unwrap(), alu);
+    }
+}
@@ -0,0 +1,19 @@
+use common::{Generate, Generated, Rng};
+use crate::Day25;
+
+impl Generate for Day25 {
+    const INPUT_SIZE: usize = 137;
+
+    /// A map of `size` rows and a few more columns, with as many east as
+    /// south facing sea cucumbers and about two in five spots free.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let rows: Vec<String> = (0..size).map(|_| {
+            (0..(size + 2)).map(|_| match rng.below(10) {
+                0..=2 => '>',
+                3..=5 => 'v',
+                _ => '.'
+            }).collect()
+        }).collect();
+        Generated::new(rows.join("\n"))
+    }
+}
@@ -0,0 +1,106 @@
+mod generate;
+mod simulate;
+mod visualize;
+
+use common::{NoAnswer, ParseError, Solution};
+use grid::{Coord, Grid};
+
+pub fn parse_input(s: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse_chars(s, ">v.", "`>`, `v` or `.`")
+}
+
+#[derive(PartialEq)]
+enum Direction {
+    East,
+    South
+}
+
+/// Where the sea cucumber at `pos` moves to, if that spot is free. The map
+/// wraps around at the edges.
+fn get_move(pos: Coord, map: &Grid<char>, direction: &Direction) -> Option<Coord> {
+    let target = match direction {
+        Direction::East =>  map.wrapping_offset(pos, 0, 1),
+        Direction::South => map.wrapping_offset(pos, 1, 0)
+    };
+    if map[target] == '.' {
+        Some(target)
+    } else {
+        None
+    }
+}
+
+fn update_map(map: &Grid<char>, direction: Direction) -> (Grid<char>, usize) {
+    let mut new_map = map.clone();
+
+    let mut n_moved = 0;
+    for pos in map.coords() {
+        if map[pos] == '>' && direction == Direction::East || map[pos] == 'v' && direction == Direction::South {
+            if let Some(new_pos) = get_move(pos, map, &direction) {
+                new_map[new_pos] = map[pos];
+                new_map[pos] = '.';
+                n_moved += 1;
+            } else {
+                new_map[pos] = map[pos];
+            }    
+        }
+    }
+    (new_map, n_moved)
+}
+
+pub fn step(map: &Grid<char>) -> (Grid<char>, usize) {
+    let (new_map, n_moved_east) = update_map(map, Direction::East);
+    let (new_map, n_moved_south) = update_map(&new_map, Direction::South);
+    (new_map, n_moved_east + n_moved_south)
+}
+
+pub fn part_1(map: &Grid<char>) -> usize {
+    let mut working_map = map.clone();
+    let mut n_steps = 1;
+    loop {
+        let (new_working_map, n_moved) = step(&working_map);
+        if n_moved == 0 {
+            break;
+        }
+        working_map = new_working_map;
+        n_steps += 1;
+    }
+    n_steps
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Grid<char>;
+    type Output1 = usize;
+    type Output2 = NoAnswer;
+
+    fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(map: &Grid<char>) -> usize {
+        part_1(map)
+    }
+
+    fn part_2(_map: &Grid<char>) -> NoAnswer {
+        NoAnswer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn p1() {
+        let map = parse_input(include_str!("../test")).unwrap();
+        assert_eq!(
+            part_1(&map), 58
+        )
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(parse_input("v...>>\n..<...").unwrap_err(), ParseError::new(2, 3, "<", "`>`, `v` or `.`"))
+    }
+}
\ No newline at end of file
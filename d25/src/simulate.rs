@@ -0,0 +1,57 @@
+use common::params::Params;
+use common::simulate::Simulate;
+use grid::Grid;
+use crate::{step, Day25};
+
+/// The sea floor after some steps, with how many sea cucumbers just moved.
+#[derive(Clone)]
+pub struct SeaFloor {
+    map: Grid<char>,
+    moved: usize,
+    stuck: bool
+}
+
+impl Simulate for Day25 {
+    type State = SeaFloor;
+
+    fn start(map: &Grid<char>, _part: usize, _params: &Params) -> SeaFloor {
+        SeaFloor { map: map.clone(), moved: 0, stuck: false }
+    }
+
+    /// Steps until the first step in which no sea cucumber moves.
+    fn step(floor: &SeaFloor) -> Option<SeaFloor> {
+        if floor.stuck {
+            return None
+        }
+        let (map, moved) = step(&floor.map);
+        Some(SeaFloor { map, moved, stuck: moved == 0 })
+    }
+
+    fn draw(floor: &SeaFloor) -> Vec<String> {
+        floor.map.rows().map(|row| row.iter().collect()).collect()
+    }
+
+    fn counters(floor: &SeaFloor) -> Vec<(&'static str, usize)> {
+        vec!(("moved", floor.moved))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse_input, part_1};
+
+    #[test]
+    fn steps() {
+        let map = parse_input(include_str!("../test")).unwrap();
+        let mut floor = Day25::start(&map, 1, &Params::default());
+        assert_eq!(Day25::draw(&floor)[0], "v...>>.vv>");
+        let mut n_steps = 0;
+        while let Some(next) = Day25::step(&floor) {
+            floor = next;
+            n_steps += 1;
+        }
+        assert_eq!(n_steps, part_1(&map));
+        assert_eq!(Day25::counters(&floor), vec!(("moved", 0)));
+    }
+}
@@ -0,0 +1,48 @@
+use grid::Grid;
+use render::{Frame, Palette, Visualize};
+use crate::{step, Day25};
+
+fn to_frame(map: &Grid<char>) -> Frame {
+    Frame::from_grid(map, 3, |c| match c {
+        '>' => 1,
+        'v' => 2,
+        _ => 0
+    })
+}
+
+impl Visualize for Day25 {
+    fn palette() -> Palette {
+        Palette::Colors(vec!([0x0b, 0x1e, 0x3c], [0xff, 0x9f, 0x1c], [0x2e, 0xc4, 0xb6]))
+    }
+
+    /// The map before the first `step` and after every step that moved a
+    /// sea cucumber, with the east and south facing herds in two colours.
+    fn frames(map: &Grid<char>) -> Vec<Frame> {
+        let mut map = map.clone();
+        let mut frames = vec!(to_frame(&map));
+        loop {
+            let (new_map, n_moved) = step(&map);
+            if n_moved == 0 {
+                break
+            }
+            map = new_map;
+            frames.push(to_frame(&map))
+        }
+        frames
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse_input, part_1};
+
+    #[test]
+    fn frames() {
+        let map = parse_input(include_str!("../test")).unwrap();
+        let frames = Day25::frames(&map);
+        assert_eq!(frames.len(), part_1(&map));
+        assert_eq!(frames[0].get(0, 0), 2);
+        assert_eq!(frames[0].get(0, 4), 1);
+    }
+}
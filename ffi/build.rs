@@ -0,0 +1,25 @@
+//! Generates the C header of the library from its source.
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let config = cbindgen::Config {
+        language: cbindgen::Language::C,
+        include_guard: Some(String::from("AOC_H")),
+        header: Some(String::from("/* Generated from ffi/src/lib.rs by cbindgen. Do not edit. */")),
+        cpp_compat: true,
+        usize_is_size_t: true,
+        enumeration: cbindgen::EnumConfig {
+            rename_variants: cbindgen::RenameRule::ScreamingSnakeCase,
+            prefix_with_name: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("Could not generate the C header")
+        .write_to_file(format!("{}/include/aoc.h", crate_dir));
+}
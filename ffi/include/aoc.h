@@ -0,0 +1,78 @@
+/* Generated from ffi/src/lib.rs by cbindgen. Do not edit. */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * What came of a call to `aoc_solve`.
+ */
+typedef enum AocStatus {
+  /**
+   * The result is the answer.
+   */
+  AOC_STATUS_OK = 0,
+  /**
+   * The day is not from 1 to 25. The result is NULL.
+   */
+  AOC_STATUS_INVALID_DAY = 1,
+  /**
+   * The part is not 1 or 2. The result is NULL.
+   */
+  AOC_STATUS_INVALID_PART = 2,
+  /**
+   * The input or the result pointer is NULL, or the input is not UTF-8.
+   * The result, if there is one, is NULL.
+   */
+  AOC_STATUS_INVALID_INPUT = 3,
+  /**
+   * The input could not be parsed. The result says where and why.
+   */
+  AOC_STATUS_PARSE_ERROR = 4,
+  /**
+   * The solver failed on the input, or the part has no answer for it,
+   * like a count too large for its type. The result says why.
+   */
+  AOC_STATUS_SOLVER_FAILED = 5,
+} AocStatus;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Solves `part` of `day` for the `input_len` bytes at `input`, which need
+ * not end with a NUL. Sets `*result` to a NUL terminated string, the
+ * answer or an error message, that must be freed with `aoc_free`, or to
+ * NULL.
+ *
+ * # Safety
+ *
+ * `input` must point to `input_len` readable bytes and `result` to a
+ * writable pointer.
+ */
+enum AocStatus aoc_solve(uint32_t day,
+                         uint32_t part,
+                         const uint8_t *input,
+                         size_t input_len,
+                         char **result);
+
+/**
+ * Frees a result of `aoc_solve`. Does nothing for NULL.
+ *
+ * # Safety
+ *
+ * `result` must be NULL or a result of `aoc_solve` that was not freed yet.
+ */
+void aoc_free(char *result);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AOC_H */
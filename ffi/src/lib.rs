@@ -0,0 +1,129 @@
+//! A C interface to the solutions of all days, built as a shared library.
+//! Its header, `include/aoc.h`, is generated from this file by the build.
+//!
+//! Nothing panics across the boundary: every failure comes back as an
+//! `AocStatus`, with a message in the result where there is one.
+
+use std::ffi::{c_char, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use aoc::days::{self, N_DAYS};
+
+/// What came of a call to `aoc_solve`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    /// The result is the answer.
+    Ok = 0,
+    /// The day is not from 1 to 25. The result is NULL.
+    InvalidDay = 1,
+    /// The part is not 1 or 2. The result is NULL.
+    InvalidPart = 2,
+    /// The input or the result pointer is NULL, or the input is not UTF-8.
+    /// The result, if there is one, is NULL.
+    InvalidInput = 3,
+    /// The input could not be parsed. The result says where and why.
+    ParseError = 4,
+    /// The solver failed on the input, or the part has no answer for it,
+    /// like a count too large for its type. The result says why.
+    SolverFailed = 5
+}
+
+fn into_c_string(s: String) -> *mut c_char {
+    CString::new(s.replace('\0', " ")).unwrap().into_raw()
+}
+
+fn solve(day: u32, part: u32, input: &[u8]) -> Result<String, (AocStatus, Option<String>)> {
+    let day = day as usize;
+    if day == 0 || day > N_DAYS {
+        return Err((AocStatus::InvalidDay, None))
+    }
+    if part != 1 && part != 2 {
+        return Err((AocStatus::InvalidPart, None))
+    }
+    let input = std::str::from_utf8(input).map_err(|_| (AocStatus::InvalidInput, None))?;
+    let puzzle = days::get(day).unwrap();
+    let parsed = puzzle.parse(input).map_err(|e| (AocStatus::ParseError, Some(e.diagnostic(input))))?;
+    panic::catch_unwind(AssertUnwindSafe(|| puzzle.solve(parsed.as_ref(), part as usize)))
+        .map_err(|payload| (AocStatus::SolverFailed, Some(aoc::panic_message(payload))))?
+        .map_err(|e| (AocStatus::SolverFailed, Some(e)))
+}
+
+/// Solves `part` of `day` for the `input_len` bytes at `input`, which need
+/// not end with a NUL. Sets `*result` to a NUL terminated string, the
+/// answer or an error message, that must be freed with `aoc_free`, or to
+/// NULL.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes and `result` to a
+/// writable pointer.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(day: u32, part: u32, input: *const u8, input_len: usize, result: *mut *mut c_char) -> AocStatus {
+    if result.is_null() {
+        return AocStatus::InvalidInput
+    }
+    *result = ptr::null_mut();
+    if input.is_null() {
+        return AocStatus::InvalidInput
+    }
+    let input = std::slice::from_raw_parts(input, input_len);
+    let solved = panic::catch_unwind(|| solve(day, part, input)).unwrap_or(Err((AocStatus::SolverFailed, None)));
+    match solved {
+        Ok(answer) => {
+            *result = into_c_string(answer);
+            AocStatus::Ok
+        },
+        Err((status, message)) => {
+            if let Some(message) = message {
+                *result = into_c_string(message)
+            }
+            status
+        }
+    }
+}
+
+/// Frees a result of `aoc_solve`. Does nothing for NULL.
+///
+/// # Safety
+///
+/// `result` must be NULL or a result of `aoc_solve` that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free(result: *mut c_char) {
+    if !result.is_null() {
+        drop(CString::from_raw(result))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ffi::CStr;
+    use super::*;
+
+    fn call(day: u32, part: u32, input: &str) -> (AocStatus, Option<String>) {
+        let mut result = ptr::null_mut();
+        let status = unsafe { aoc_solve(day, part, input.as_ptr(), input.len(), &mut result) };
+        let text = (!result.is_null()).then(|| unsafe { CStr::from_ptr(result) }.to_string_lossy().into_owned());
+        unsafe { aoc_free(result) };
+        (status, text)
+    }
+
+    #[test]
+    fn solves() {
+        assert_eq!(call(1, 2, include_str!("../../d01/test")), (AocStatus::Ok, Some(String::from("5"))));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(call(26, 1, ""), (AocStatus::InvalidDay, None));
+        assert_eq!(call(1, 3, ""), (AocStatus::InvalidPart, None));
+        let (status, message) = call(1, 1, "199\n2x0");
+        assert_eq!(status, AocStatus::ParseError);
+        assert!(message.unwrap().contains("line 2, column 1"));
+        // The product of three literals of 2^60 does not fit in a usize.
+        let product = "0600C48C210842108421084200123084210842108421080048C210842108421084200";
+        assert_eq!(call(16, 2, product), (AocStatus::SolverFailed, Some(String::from("the count does not fit in its type"))));
+        let status = unsafe { aoc_solve(1, 1, ptr::null(), 0, &mut ptr::null_mut()) };
+        assert_eq!(status, AocStatus::InvalidInput);
+    }
+}
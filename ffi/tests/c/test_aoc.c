@@ -0,0 +1,44 @@
+/* Calls the library the way C tooling would. Exits with a non-zero status on failure. */
+
+#include <stdio.h>
+#include <string.h>
+#include "aoc.h"
+
+static int failures = 0;
+
+static void check(int ok, const char *what) {
+    if (!ok) {
+        fprintf(stderr, "FAILED: %s\n", what);
+        failures++;
+    }
+}
+
+int main(void) {
+    const char *input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+    char *result = NULL;
+
+    AocStatus status = aoc_solve(1, 1, (const uint8_t *)input, strlen(input), &result);
+    check(status == AOC_STATUS_OK, "day 1 part 1 is solved");
+    check(result != NULL && strcmp(result, "7") == 0, "day 1 part 1 is 7");
+    aoc_free(result);
+
+    status = aoc_solve(1, 2, (const uint8_t *)input, strlen(input), &result);
+    check(status == AOC_STATUS_OK && strcmp(result, "5") == 0, "day 1 part 2 is 5");
+    aoc_free(result);
+
+    status = aoc_solve(1, 1, (const uint8_t *)"199\n2x0", 7, &result);
+    check(status == AOC_STATUS_PARSE_ERROR, "a broken input is a parse error");
+    check(result != NULL && strstr(result, "line 2, column 1") != NULL, "the parse error says where");
+    aoc_free(result);
+
+    check(aoc_solve(26, 1, (const uint8_t *)input, strlen(input), &result) == AOC_STATUS_INVALID_DAY, "day 26 is invalid");
+    check(result == NULL, "an invalid day has no result");
+    check(aoc_solve(1, 3, (const uint8_t *)input, strlen(input), &result) == AOC_STATUS_INVALID_PART, "part 3 is invalid");
+    check(aoc_solve(1, 1, NULL, 0, &result) == AOC_STATUS_INVALID_INPUT, "a NULL input is invalid");
+    aoc_free(NULL);
+
+    if (failures == 0) {
+        printf("All C checks passed\n");
+    }
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,27 @@
+//! Builds the C test program against the shared library and runs it.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+fn c_program() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // The test runs from target/<profile>/deps, where the library is built for it.
+    let lib_dir = std::env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    let program = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("test_aoc");
+    let compiler = std::env::var("CC").unwrap_or_else(|_| String::from("cc"));
+    let status = Command::new(&compiler)
+        .arg(manifest_dir.join("tests/c/test_aoc.c"))
+        .arg("-I").arg(manifest_dir.join("include"))
+        .arg("-L").arg(&lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-laoc_ffi")
+        .arg("-Wall").arg("-Werror")
+        .arg("-o").arg(&program)
+        .status()
+        .unwrap_or_else(|e| panic!("Could not run {}: {}", compiler, e));
+    assert!(status.success(), "Could not build the C test program");
+    let output = Command::new(&program).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "All C checks passed\n");
+}
@@ -0,0 +1,13 @@
+#![no_main]
+
+use common::Solution;
+use d16::Day16;
+use libfuzzer_sys::fuzz_target;
+
+// Most text fails as hex; what is left exercises the packet parser.
+fuzz_target!(|input: &str| {
+    if let Ok(packet) = Day16::parse(input) {
+        packet.version_sum();
+        let _ = packet.eval_as::<u64>();
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use common::Solution;
+use d18::{explode, tokenize, Day18};
+use libfuzzer_sys::fuzz_target;
+
+// The tokenizer and an explosion take any text; the sum only what parses.
+fuzz_target!(|input: &str| {
+    let tokens = tokenize(&mut (&mut input.chars()).peekable());
+    explode(&tokens);
+    if let Ok(rows) = Day18::parse(input) {
+        Day18::part_1(&rows);
+    }
+});
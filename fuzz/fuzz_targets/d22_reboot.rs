@@ -0,0 +1,12 @@
+#![no_main]
+
+use common::Solution;
+use d22::Day22;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(cubes) = Day22::parse(input) {
+        Day22::part_1(&cubes);
+        Day22::part_2(&cubes);
+    }
+});
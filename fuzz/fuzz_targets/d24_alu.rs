@@ -0,0 +1,19 @@
+#![no_main]
+
+use d24::{parse_instruction, ALU};
+use libfuzzer_sys::fuzz_target;
+
+// Runs the lines that parse as a single program, with the digits of a model
+// number as input, until the ALU faults.
+fuzz_target!(|input: &str| {
+    let program: Vec<_> = input.lines().enumerate()
+        .filter_map(|(ind, l)| parse_instruction(ind + 1, l).ok())
+        .collect();
+    let mut digits: Vec<isize> = (1..=9).cycle().take(14).collect();
+    let mut alu = ALU::new();
+    for instruction in &program {
+        if alu.process_instruction(instruction, &mut digits).is_err() {
+            break
+        }
+    }
+});
@@ -0,0 +1,248 @@
+//! A rectangular grid of cells, as used by the days whose input is a map.
+
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+use common::ParseError;
+use common::input::{parse_char_rows, parse_digit_rows};
+
+/// Position of a cell, counting rows from the top and columns from the left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Coord {
+    pub row: usize,
+    pub col: usize
+}
+
+impl Coord {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+}
+
+static NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
+static NEIGHBOURS_8: [(isize, isize); 8] = [(-1, 0), (0, -1), (1, 0), (0, 1), (-1, -1), (-1, 1), (1, -1), (1, 1)];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    n_rows: usize,
+    n_cols: usize
+}
+
+impl<T> Grid<T> {
+    /// Grid from its rows, which must all be of the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let n_rows = rows.len();
+        let n_cols = rows.first().map_or(0, |row| row.len());
+        assert!(rows.iter().all(|row| row.len() == n_cols), "Rows of a grid must be of equal length");
+        Self { cells: rows.into_iter().flatten().collect(), n_rows, n_cols }
+    }
+
+    /// Grid with the value of each cell given by `f` of its position.
+    pub fn from_fn(n_rows: usize, n_cols: usize, mut f: impl FnMut(Coord) -> T) -> Self {
+        let cells = (0..n_rows).flat_map(|row| (0..n_cols).map(move |col| Coord::new(row, col))).map(&mut f).collect();
+        Self { cells, n_rows, n_cols }
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.n_rows
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.n_cols
+    }
+
+    pub fn n_cells(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn contains(&self, pos: Coord) -> bool {
+        pos.row < self.n_rows && pos.col < self.n_cols
+    }
+
+    pub fn get(&self, pos: Coord) -> Option<&T> {
+        if self.contains(pos) { Some(&self[pos]) } else { None }
+    }
+
+    pub fn get_mut(&mut self, pos: Coord) -> Option<&mut T> {
+        if self.contains(pos) { Some(&mut self[pos]) } else { None }
+    }
+
+    /// Position of (`row`, `col`) if it is inside the grid.
+    pub fn coord(&self, row: isize, col: isize) -> Option<Coord> {
+        let pos = Coord::new(usize::try_from(row).ok()?, usize::try_from(col).ok()?);
+        if self.contains(pos) { Some(pos) } else { None }
+    }
+
+    /// Position `d_row` rows and `d_col` columns from `pos`, if inside the grid.
+    pub fn offset(&self, pos: Coord, d_row: isize, d_col: isize) -> Option<Coord> {
+        self.coord(pos.row as isize + d_row, pos.col as isize + d_col)
+    }
+
+    /// Position `d_row` rows and `d_col` columns from `pos` on a grid whose
+    /// edges wrap around, so that leaving on one side enters on the other.
+    pub fn wrapping_offset(&self, pos: Coord, d_row: isize, d_col: isize) -> Coord {
+        Coord::new(
+            (pos.row as isize + d_row).rem_euclid(self.n_rows as isize) as usize,
+            (pos.col as isize + d_col).rem_euclid(self.n_cols as isize) as usize
+        )
+    }
+
+    /// The horizontal and vertical neighbours of `pos` inside the grid.
+    pub fn neighbours_4(&self, pos: Coord) -> impl Iterator<Item = Coord> + '_ {
+        NEIGHBOURS_4.iter().filter_map(move |(d_row, d_col)| self.offset(pos, *d_row, *d_col))
+    }
+
+    /// The neighbours of `pos` inside the grid, diagonals included.
+    pub fn neighbours_8(&self, pos: Coord) -> impl Iterator<Item = Coord> + '_ {
+        NEIGHBOURS_8.iter().filter_map(move |(d_row, d_col)| self.offset(pos, *d_row, *d_col))
+    }
+
+    /// All positions of the grid, row by row.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let n_cols = self.n_cols;
+        (0..self.n_rows).flat_map(move |row| (0..n_cols).map(move |col| Coord::new(row, col)))
+    }
+
+    /// All cells of the grid with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[(row * self.n_cols)..((row + 1) * self.n_cols)]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.n_cols.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.n_cols, "Column {} outside grid", col);
+        self.cells.iter().skip(col).step_by(self.n_cols)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { cells: self.cells.iter().map(f).collect(), n_rows: self.n_rows, n_cols: self.n_cols }
+    }
+}
+
+impl Grid<u32> {
+    /// Parses a grid of single digits, one row per line.
+    pub fn parse_digits(s: &str) -> Result<Self, ParseError> {
+        Ok(Self::from_rows(parse_digit_rows(s)?))
+    }
+}
+
+impl Grid<char> {
+    /// Parses a grid of the characters in `allowed`, one row per line.
+    pub fn parse_chars(s: &str, allowed: &str, expected: &str) -> Result<Self, ParseError> {
+        Ok(Self::from_rows(parse_char_rows(s, allowed, expected)?))
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Coord) -> &T {
+        assert!(self.contains(pos), "{:?} outside grid", pos);
+        &self.cells[pos.row * self.n_cols + pos.col]
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, pos: Coord) -> &mut T {
+        assert!(self.contains(pos), "{:?} outside grid", pos);
+        &mut self.cells[pos.row * self.n_cols + pos.col]
+    }
+}
+
+/// One line per row, with the cells of a row written next to each other.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (ind, row) in self.rows().enumerate() {
+            if ind > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A grid is written as its rows.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Grid<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.rows())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Grid<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rows = Vec::<Vec<T>>::deserialize(deserializer)?;
+        let n_cols = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != n_cols) {
+            return Err(serde::de::Error::custom("rows of a grid must be of equal length"))
+        }
+        Ok(Grid::from_rows(rows))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        let grid = Grid::parse_digits("123\n456").unwrap();
+        assert_eq!((grid.n_rows(), grid.n_cols()), (2, 3));
+        assert_eq!(grid[Coord::new(1, 0)], 4);
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(Grid::parse_chars("#.\n.x", "#.", "`#` or `.`").unwrap_err(), ParseError::new(2, 2, "x", "`#` or `.`"));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::from_fn(3, 3, |pos| pos.row * 3 + pos.col);
+        assert_eq!(grid.neighbours_4(Coord::new(0, 0)).collect::<Vec<_>>(), vec!(Coord::new(1, 0), Coord::new(0, 1)));
+        assert_eq!(grid.neighbours_4(Coord::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours_8(Coord::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours_8(Coord::new(2, 2)).map(|pos| grid[pos]).sum::<usize>(), 4 + 5 + 7);
+    }
+
+    #[test]
+    fn wrapping() {
+        let grid = Grid::from_rows(vec!(vec!('a', 'b', 'c'), vec!('d', 'e', 'f')));
+        assert_eq!(grid.wrapping_offset(Coord::new(0, 2), 0, 1), Coord::new(0, 0));
+        assert_eq!(grid.wrapping_offset(Coord::new(1, 1), 1, 0), Coord::new(0, 1));
+        assert_eq!(grid.wrapping_offset(Coord::new(0, 0), -1, -1), Coord::new(1, 2));
+        assert_eq!(grid.offset(Coord::new(0, 0), -1, 0), None);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::from_rows(vec!(vec!(1, 2, 3), vec!(4, 5, 6)));
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec!(2, 5));
+        assert_eq!(grid.iter().find(|(_, v)| **v == 6).map(|(pos, _)| pos), Some(Coord::new(1, 2)));
+        assert_eq!(grid.map(|v| v * 2).values().sum::<i32>(), 42);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let grid = Grid::from_rows(vec!(vec!(1, 2, 3), vec!(4, 5, 6)));
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(json, "[[1,2,3],[4,5,6]]");
+        assert_eq!(serde_json::from_str::<Grid<u32>>(&json).unwrap(), grid);
+        assert!(serde_json::from_str::<Grid<u32>>("[[1,2],[3]]").is_err());
+    }
+}
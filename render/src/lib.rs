@@ -0,0 +1,290 @@
+//! Pictures of the days whose puzzles play out on a map, as PPM, PNG or SVG
+//! images and as numbered frame sequences.
+//!
+//! A day only says which level, a small number, each cell is at in every
+//! frame. The palette that colours the levels and the scale of a cell are
+//! picked when the frames are written.
+
+mod png;
+
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use common::Solution;
+use grid::Grid;
+
+pub type Rgb = [u8; 3];
+
+/// A picture of a map, with a level from 0 to `levels - 1` for each cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    cells: Vec<u8>,
+    n_rows: usize,
+    n_cols: usize,
+    levels: u8
+}
+
+impl Frame {
+    /// Frame with every cell at level 0.
+    pub fn new(n_rows: usize, n_cols: usize, levels: u8) -> Self {
+        assert!(levels > 0, "A frame needs at least one level");
+        Self { cells: vec![0; n_rows * n_cols], n_rows, n_cols, levels }
+    }
+
+    /// Frame with the level of each cell of `grid` given by `f` of its value.
+    pub fn from_grid<T>(grid: &Grid<T>, levels: u8, mut f: impl FnMut(&T) -> u8) -> Self {
+        let mut frame = Self::new(grid.n_rows(), grid.n_cols(), levels);
+        for (pos, value) in grid.iter() {
+            frame.set(pos.row, pos.col, f(value))
+        }
+        frame
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.n_rows
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.n_cols
+    }
+
+    pub fn levels(&self) -> u8 {
+        self.levels
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> u8 {
+        self.cells[row * self.n_cols + col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, level: u8) {
+        assert!(level < self.levels, "Level {} out of {} levels", level, self.levels);
+        self.cells[row * self.n_cols + col] = level
+    }
+}
+
+/// How the levels of a frame are coloured.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Palette {
+    /// From black for the lowest level to white for the highest.
+    Gray,
+    /// From black through red and yellow to white.
+    Heat,
+    /// A colour per level, starting over when there are more levels.
+    Colors(Vec<Rgb>)
+}
+
+impl Palette {
+    pub fn color(&self, level: u8, levels: u8) -> Rgb {
+        let t = if levels > 1 { level as f64 / (levels - 1) as f64 } else { 1.0 };
+        let channel = |t: f64| (255.0 * t.clamp(0.0, 1.0)).round() as u8;
+        match self {
+            Palette::Gray => [channel(t); 3],
+            Palette::Heat => [channel(3.0 * t), channel(3.0 * t - 1.0), channel(3.0 * t - 2.0)],
+            Palette::Colors(colors) => colors[level as usize % colors.len()]
+        }
+    }
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    /// `gray`, `heat` or a comma separated list of colours like `#ff8000`.
+    fn from_str(s: &str) -> Result<Palette, String> {
+        fn parse_color(s: &str) -> Option<Rgb> {
+            let hex = s.strip_prefix('#').unwrap_or(s);
+            if hex.len() != 6 || !hex.is_ascii() {
+                return None
+            }
+            let channel = |ind: usize| u8::from_str_radix(&hex[ind..ind + 2], 16).ok();
+            Some([channel(0)?, channel(2)?, channel(4)?])
+        }
+
+        match s {
+            "gray" => Ok(Palette::Gray),
+            "heat" => Ok(Palette::Heat),
+            _ => s.split(',').map(|color| parse_color(color.trim()).ok_or(format!("Invalid colour: {}", color)))
+                .collect::<Result<Vec<_>, _>>().map(Palette::Colors)
+        }
+    }
+}
+
+/// A day that can be drawn.
+pub trait Visualize: Solution {
+    /// The palette the day looks best in.
+    fn palette() -> Palette {
+        Palette::Heat
+    }
+
+    /// The frames showing how the answer comes about, in order. A day that
+    /// is a single picture has a single frame.
+    fn frames(input: &Self::Input) -> Vec<Frame>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+    Svg
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+            Format::Svg => "svg"
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        [Format::Ppm, Format::Png, Format::Svg].into_iter().find(|format| format.extension() == s)
+            .ok_or_else(|| format!("Invalid format: {}", s))
+    }
+}
+
+/// The colours and size of the pictures written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Style {
+    pub palette: Palette,
+    /// The side of a cell in pixels.
+    pub scale: usize
+}
+
+/// The pixels of a frame drawn in a style, row by row.
+fn pixels(frame: &Frame, style: &Style) -> Vec<Rgb> {
+    let colors: Vec<Rgb> = (0..frame.levels).map(|level| style.palette.color(level, frame.levels)).collect();
+    let width = frame.n_cols * style.scale;
+    let mut pixels = Vec::with_capacity(width * frame.n_rows * style.scale);
+    for row in 0..frame.n_rows {
+        let line: Vec<Rgb> = (0..width).map(|x| colors[frame.get(row, x / style.scale) as usize]).collect();
+        for _ in 0..style.scale {
+            pixels.extend_from_slice(&line)
+        }
+    }
+    pixels
+}
+
+fn to_ppm(frame: &Frame, style: &Style) -> Vec<u8> {
+    let mut ppm = format!("P6\n{} {}\n255\n", frame.n_cols * style.scale, frame.n_rows * style.scale).into_bytes();
+    ppm.extend(pixels(frame, style).into_iter().flatten());
+    ppm
+}
+
+/// A rectangle per run of cells of the same level on a row.
+fn to_svg(frame: &Frame, style: &Style) -> Vec<u8> {
+    let hex = |[r, g, b]: Rgb| format!("#{:02x}{:02x}{:02x}", r, g, b);
+    let (width, height) = (frame.n_cols * style.scale, frame.n_rows * style.scale);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\n",
+        width, height, frame.n_cols, frame.n_rows);
+    for row in 0..frame.n_rows {
+        let mut col = 0;
+        while col < frame.n_cols {
+            let level = frame.get(row, col);
+            let start = col;
+            while col < frame.n_cols && frame.get(row, col) == level {
+                col += 1
+            }
+            svg += &format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"{}\"/>\n",
+                start, row, col - start, hex(style.palette.color(level, frame.levels)));
+        }
+    }
+    svg += "</svg>\n";
+    svg.into_bytes()
+}
+
+/// The file contents of a frame drawn in `style`.
+pub fn encode(frame: &Frame, style: &Style, format: Format) -> Vec<u8> {
+    match format {
+        Format::Ppm => to_ppm(frame, style),
+        Format::Png => png::encode(frame.n_cols * style.scale, frame.n_rows * style.scale, &pixels(frame, style)),
+        Format::Svg => to_svg(frame, style)
+    }
+}
+
+/// Writes a frame to `path`.
+pub fn write_image(path: &Path, frame: &Frame, style: &Style, format: Format) -> Result<(), String> {
+    fs::write(path, encode(frame, style, format)).map_err(|e| format!("Could not write {}: {}", path.display(), e))
+}
+
+/// Writes the frames to `dir`, which is created if needed, as `frame0000`,
+/// `frame0001` and so on, with as many digits as the last one needs. Returns
+/// the files written.
+pub fn write_frames(dir: &Path, frames: &[Frame], style: &Style, format: Format) -> Result<Vec<PathBuf>, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {}", dir.display(), e))?;
+    let digits = frames.len().saturating_sub(1).to_string().len().max(4);
+    let mut paths = vec!();
+    for (ind, frame) in frames.iter().enumerate() {
+        let path = dir.join(format!("frame{:0digits$}.{}", ind, format.extension()));
+        write_image(&path, frame, style, format)?;
+        paths.push(path)
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn checkers() -> Frame {
+        let mut frame = Frame::new(2, 3, 2);
+        frame.set(0, 0, 1);
+        frame.set(1, 1, 1);
+        frame.set(0, 2, 1);
+        frame
+    }
+
+    #[test]
+    fn palettes() {
+        assert_eq!(Palette::Gray.color(0, 3), [0, 0, 0]);
+        assert_eq!(Palette::Gray.color(1, 3), [128, 128, 128]);
+        assert_eq!(Palette::Heat.color(1, 4), [255, 0, 0]);
+        assert_eq!(Palette::Heat.color(3, 4), [255, 255, 255]);
+        assert_eq!("#ff8000, 000000".parse(), Ok(Palette::Colors(vec!([255, 128, 0], [0, 0, 0]))));
+        assert_eq!("heat".parse(), Ok(Palette::Heat));
+        assert!("#ff80".parse::<Palette>().is_err());
+        assert_eq!("svg".parse(), Ok(Format::Svg));
+        assert!("gif".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn ppm() {
+        let ppm = encode(&checkers(), &Style { palette: Palette::Gray, scale: 2 }, Format::Ppm);
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+        let pixel = |x: usize, y: usize| ppm[header.len() + 3 * (6 * y + x)];
+        assert_eq!((pixel(0, 0), pixel(1, 1), pixel(2, 0), pixel(3, 3), pixel(5, 1)), (255, 255, 0, 255, 255));
+    }
+
+    #[test]
+    fn svg() {
+        let svg = String::from_utf8(encode(&checkers(), &Style { palette: Palette::Gray, scale: 10 }, Format::Svg)).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"20\" viewBox=\"0 0 3 2\""));
+        assert_eq!(svg.matches("<rect").count(), 6);
+        assert!(svg.contains("<rect x=\"1\" y=\"0\" width=\"1\" height=\"1\" fill=\"#000000\"/>"));
+    }
+
+    #[test]
+    fn frames() {
+        let dir = std::env::temp_dir().join(format!("aoc-render-{}", std::process::id()));
+        let frames = vec![checkers(); 3];
+        let paths = write_frames(&dir, &frames, &Style { palette: Palette::Heat, scale: 1 }, Format::Png).unwrap();
+        let names: Vec<_> = paths.iter().map(|p| p.file_name().unwrap().to_string_lossy().into_owned()).collect();
+        let png = fs::read(&paths[2]).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(names, vec!("frame0000.png", "frame0001.png", "frame0002.png"));
+        assert_eq!(png, encode(&frames[2], &Style { palette: Palette::Heat, scale: 1 }, Format::Png));
+    }
+}
@@ -0,0 +1,279 @@
+//! Searches over the states of a puzzle, shared by the days that look for
+//! the cheapest way to a goal or count the ways of getting there. The
+//! searches report how far they got through `common::progress` and can be
+//! cancelled through it.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use common::count::{Count, Overflow};
+use common::progress::{self, Level, Metrics};
+
+/// A state of a search, and the states that can be reached from it.
+pub trait SearchState: Clone {
+    /// Identifies the state. States with the same key are considered the
+    /// same, so only the cheapest way to reach them is kept.
+    type Key: Hash + Eq;
+
+    fn key(&self) -> Self::Key;
+
+    /// The states one step away, with the cost of taking that step.
+    fn neighbours(&self) -> Vec<(Self, usize)>;
+
+    fn is_goal(&self) -> bool;
+
+    /// Lower bound of the cost from this state to a goal, used by `astar`.
+    fn heuristic(&self) -> usize {
+        0
+    }
+}
+
+/// The cheapest way to a goal: its total cost and the states along the way,
+/// from the start to the goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found<S> {
+    pub cost: usize,
+    pub path: Vec<S>
+}
+
+fn path_to<S: Clone>(states: &Vec<(S, Option<usize>)>, ind: usize) -> Vec<S> {
+    let mut path = vec!(states[ind].0.clone());
+    let mut parent = states[ind].1;
+    while let Some(ind) = parent {
+        path.push(states[ind].0.clone());
+        parent = states[ind].1;
+    }
+    path.reverse();
+    path
+}
+
+/// Breadth-first search, which ignores the step costs. The cost of the
+/// result is the number of steps taken.
+pub fn bfs<S: SearchState>(start: S) -> Option<Found<S>> {
+    let mut seen: HashSet<S::Key> = [start.key()].into_iter().collect();
+    let mut states: Vec<(S, Option<usize>)> = vec!((start, None));
+    let mut queue: VecDeque<(usize, usize)> = [(0, 0)].into_iter().collect();
+    while let Some((ind, n_steps)) = queue.pop_front() {
+        progress::checkpoint();
+        progress::report(|| Metrics { explored: ind + 1, open: Some(queue.len()), best: Some(n_steps), done: None });
+        if states[ind].0.is_goal() {
+            return Some(Found { cost: n_steps, path: path_to(&states, ind) })
+        }
+        for (next, _) in states[ind].0.neighbours() {
+            if seen.insert(next.key()) {
+                states.push((next, Some(ind)));
+                queue.push_back((states.len() - 1, n_steps + 1));
+            }
+        }
+    }
+    None
+}
+
+fn best_first<S: SearchState>(start: S, heuristic: impl Fn(&S) -> usize) -> Option<Found<S>> {
+    let mut best_cost: HashMap<S::Key, usize> = [(start.key(), 0)].into_iter().collect();
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((heuristic(&start), 0, 0)));
+    let mut states: Vec<(S, Option<usize>)> = vec!((start, None));
+    let mut n_explored = 0;
+    while let Some(Reverse((bound, cost, ind))) = queue.pop() {
+        let state = &states[ind].0;
+        if best_cost.get(&state.key()).is_some_and(|best| *best < cost) {
+            continue
+        }
+        n_explored += 1;
+        progress::checkpoint();
+        progress::report(|| Metrics { explored: n_explored, open: Some(queue.len()), best: Some(bound), done: None });
+        if state.is_goal() {
+            progress::log(Level::Debug, || format!("Found a way costing {} after exploring {} states", cost, n_explored));
+            return Some(Found { cost, path: path_to(&states, ind) })
+        }
+        for (next, step_cost) in state.neighbours() {
+            let next_cost = cost + step_cost;
+            let key = next.key();
+            if best_cost.get(&key).is_none_or(|best| next_cost < *best) {
+                best_cost.insert(key, next_cost);
+                queue.push(Reverse((next_cost + heuristic(&next), next_cost, states.len())));
+                states.push((next, Some(ind)));
+            }
+        }
+    }
+    None
+}
+
+/// The cheapest way from `start` to a goal.
+pub fn dijkstra<S: SearchState>(start: S) -> Option<Found<S>> {
+    best_first(start, |_| 0)
+}
+
+/// The cheapest way from `start` to a goal, guided by the heuristic of the
+/// states. The heuristic must never overestimate the remaining cost.
+pub fn astar<S: SearchState>(start: S) -> Option<Found<S>> {
+    best_first(start, S::heuristic)
+}
+
+/// Every way from `start` to a goal, ending at the first goal reached.
+/// The states must not lead back to themselves, or this never ends.
+pub fn all_paths<S: SearchState>(start: S) -> Vec<Vec<S>> {
+    fn extend<S: SearchState>(path: &mut Vec<S>, paths: &mut Vec<Vec<S>>) {
+        let last = path.last().unwrap();
+        if last.is_goal() {
+            paths.push(path.clone());
+            return
+        }
+        for (next, _) in last.neighbours() {
+            path.push(next);
+            extend(path, paths);
+            path.pop();
+        }
+    }
+
+    let mut paths = vec!();
+    extend(&mut vec!(start), &mut paths);
+    paths
+}
+
+/// Number of ways from `start` to a goal, as `all_paths` would find them.
+/// The count from a state is remembered by its key, so the states must
+/// carry everything that decides where they can go next.
+pub fn count_paths<S: SearchState>(start: S) -> Result<usize, Overflow> {
+    count_paths_as(start)
+}
+
+/// Like `count_paths`, counting in `C`.
+pub fn count_paths_as<S: SearchState, C: Count>(start: S) -> Result<C, Overflow> {
+    fn count<S: SearchState, C: Count>(state: &S, counts: &mut HashMap<S::Key, C>) -> Result<C, Overflow> {
+        if state.is_goal() {
+            return Ok(C::one())
+        }
+        let key = state.key();
+        if let Some(n_paths) = counts.get(&key) {
+            return Ok(n_paths.clone())
+        }
+        let mut n_paths = C::zero();
+        for (next, _) in state.neighbours() {
+            n_paths = n_paths.plus(&count(&next, counts)?)?
+        }
+        counts.insert(key, n_paths.clone());
+        Ok(n_paths)
+    }
+
+    count(&start, &mut HashMap::new())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A walk on a small weighted map of digits towards its lower right corner.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Walk {
+        row: usize,
+        col: usize
+    }
+
+    static MAP: [[usize; 3]; 3] = [[1, 1, 6], [1, 3, 8], [2, 1, 3]];
+
+    impl SearchState for Walk {
+        type Key = (usize, usize);
+
+        fn key(&self) -> (usize, usize) {
+            (self.row, self.col)
+        }
+
+        fn neighbours(&self) -> Vec<(Walk, usize)> {
+            let mut neighbours = vec!();
+            if self.row > 0 { neighbours.push(Walk { row: self.row - 1, col: self.col }) }
+            if self.col > 0 { neighbours.push(Walk { row: self.row, col: self.col - 1 }) }
+            if self.row < 2 { neighbours.push(Walk { row: self.row + 1, col: self.col }) }
+            if self.col < 2 { neighbours.push(Walk { row: self.row, col: self.col + 1 }) }
+            neighbours.into_iter().map(|w| { let cost = MAP[w.row][w.col]; (w, cost) }).collect()
+        }
+
+        fn is_goal(&self) -> bool {
+            (self.row, self.col) == (2, 2)
+        }
+
+        fn heuristic(&self) -> usize {
+            4 - self.row - self.col
+        }
+    }
+
+    /// A walk that only goes right or down, so that its ways can be counted.
+    #[derive(Clone)]
+    struct Monotone(Walk);
+
+    impl SearchState for Monotone {
+        type Key = (usize, usize);
+
+        fn key(&self) -> (usize, usize) {
+            self.0.key()
+        }
+
+        fn neighbours(&self) -> Vec<(Monotone, usize)> {
+            self.0.neighbours().into_iter().filter(|(w, _)| w.row + w.col > self.0.row + self.0.col).map(|(w, c)| (Monotone(w), c)).collect()
+        }
+
+        fn is_goal(&self) -> bool {
+            self.0.is_goal()
+        }
+    }
+
+    fn walk(row: usize, col: usize) -> Walk {
+        Walk { row, col }
+    }
+
+    #[test]
+    fn cheapest() {
+        let expected = Found { cost: 7, path: vec!(walk(0, 0), walk(1, 0), walk(2, 0), walk(2, 1), walk(2, 2)) };
+        assert_eq!(dijkstra(walk(0, 0)), Some(expected.clone()));
+        assert_eq!(astar(walk(0, 0)), Some(expected));
+    }
+
+    #[test]
+    fn fewest_steps() {
+        let found = bfs(walk(0, 0)).unwrap();
+        assert_eq!(found.cost, 4);
+        assert_eq!(found.path.len(), 5);
+        assert_eq!(found.path.last(), Some(&walk(2, 2)));
+    }
+
+    #[test]
+    fn start_at_goal() {
+        assert_eq!(dijkstra(walk(2, 2)), Some(Found { cost: 0, path: vec!(walk(2, 2)) }));
+        assert_eq!(count_paths(Monotone(walk(2, 2))), Ok(1));
+    }
+
+    #[test]
+    fn unreachable() {
+        #[derive(Clone)]
+        struct Stuck;
+
+        impl SearchState for Stuck {
+            type Key = ();
+
+            fn key(&self) {}
+
+            fn neighbours(&self) -> Vec<(Stuck, usize)> {
+                vec!()
+            }
+
+            fn is_goal(&self) -> bool {
+                false
+            }
+        }
+
+        assert!(bfs(Stuck).is_none());
+        assert!(dijkstra(Stuck).is_none());
+        assert!(all_paths(Stuck).is_empty());
+        assert_eq!(count_paths(Stuck), Ok(0));
+    }
+
+    #[test]
+    fn paths() {
+        let paths = all_paths(Monotone(walk(0, 0)));
+        assert_eq!(paths.len(), 6);
+        assert!(paths.iter().all(|path| path.len() == 5));
+        assert_eq!(count_paths(Monotone(walk(0, 0))), Ok(6));
+        assert_eq!(count_paths(Monotone(walk(1, 1))), Ok(2));
+    }
+}